@@ -0,0 +1,158 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use lib_genetic_algorithm::benchmark::{Candidate, OneMax, Sphere};
+use lib_genetic_algorithm::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Roughly the size of a bird's brain.
+const GENES: usize = 1000;
+
+type SphereOptimizer = Box<dyn Optimizer<Candidate<Sphere>>>;
+
+fn crossovers(c: &mut Criterion) {
+    let mut rng = ChaCha8Rng::from_seed(Default::default());
+    let parent_a: Chromosome = (0..GENES).map(|_| rng.gen_range(-1.0..1.0)).collect();
+    let parent_b: Chromosome = (0..GENES).map(|_| rng.gen_range(-1.0..1.0)).collect();
+
+    let methods: Vec<(&str, Box<dyn CrossoverMethod>)> = vec![
+        ("uniform", Box::new(UniformCrossover)),
+        ("single point", Box::new(SinglePointCrossover)),
+        ("k point", Box::new(KPointCrossover::new(4))),
+        ("arithmetic", Box::new(ArithmeticCrossover::new(0.5))),
+        ("blend", Box::new(BlendCrossover::new(0.5))),
+        (
+            "simulated binary",
+            Box::new(SimulatedBinaryCrossover::new(2.0)),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("crossover");
+
+    for (name, method) in methods {
+        group.bench_function(name, |b| {
+            b.iter(|| method.crossover(&mut rng, black_box(&parent_a), black_box(&parent_b)))
+        });
+    }
+
+    group.finish();
+
+    let parent_a: Chromosome<usize> = (0..GENES).collect();
+    let parent_b: Chromosome<usize> = (0..GENES).rev().collect();
+
+    let methods: Vec<(&str, Box<dyn CrossoverMethod<usize>>)> = vec![
+        ("partially mapped", Box::new(PartiallyMappedCrossover)),
+        ("order", Box::new(OrderCrossover)),
+        ("cycle", Box::new(CycleCrossover)),
+    ];
+
+    let mut group = c.benchmark_group("permutation crossover");
+
+    for (name, method) in methods {
+        group.bench_function(name, |b| {
+            b.iter(|| method.crossover(&mut rng, black_box(&parent_a), black_box(&parent_b)))
+        });
+    }
+
+    group.finish();
+}
+
+fn mutations(c: &mut Criterion) {
+    let mut rng = ChaCha8Rng::from_seed(Default::default());
+    let chromosome: Chromosome = (0..GENES).map(|_| rng.gen_range(-1.0..1.0)).collect();
+
+    let methods: Vec<(&str, Box<dyn MutationMethod>)> = vec![
+        (
+            "uniform perturbation",
+            Box::new(UniformPerturbationMutation::new(0.01, 0.2)),
+        ),
+        ("normal", Box::new(NormalMutation::new(0.01, 0.2))),
+        ("cauchy", Box::new(CauchyMutation::new(0.01, 0.2))),
+        (
+            "polynomial",
+            Box::new(PolynomialMutation::new(0.01, 20.0, -1.0, 1.0)),
+        ),
+        (
+            "self adaptive",
+            Box::new(SelfAdaptiveMutation::new(0.2, 0.01)),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("mutation");
+
+    for (name, method) in methods {
+        group.bench_function(name, |b| {
+            b.iter_batched_ref(
+                || chromosome.clone(),
+                |child| method.mutate(&mut rng, child),
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+fn generations(c: &mut Criterion) {
+    let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+    let population: Vec<Candidate<Sphere>> = (0..50)
+        .map(|_| Candidate::create((0..100).map(|_| rng.gen_range(-2.0..2.0)).collect()))
+        .collect();
+
+    let mut group = c.benchmark_group("generation");
+
+    let ga = GeneticAlgorithm::new(
+        RouletteWheelSelection,
+        UniformCrossover,
+        NormalMutation::new(0.01, 0.2),
+    );
+
+    group.bench_function("genetic algorithm", |b| {
+        b.iter(|| ga.evolve(&mut rng, black_box(&population)))
+    });
+
+    let optimizers: Vec<(&str, SphereOptimizer)> = vec![
+        ("cma-es", Box::new(CmaEs::new(0.5))),
+        ("sep-cma-es", Box::new(CmaEs::separable(0.5))),
+        (
+            "differential evolution",
+            Box::new(DifferentialEvolution::new(
+                DifferentialEvolutionStrategy::Rand1Bin,
+                0.6,
+                0.9,
+            )),
+        ),
+        (
+            "particle swarm",
+            Box::new(ParticleSwarm::new(0.7, 1.5, 1.5, 0.5)),
+        ),
+    ];
+
+    for (name, mut optimizer) in optimizers {
+        // Let stateful optimizers initialise themselves first
+        optimizer.evolve(&mut rng, &population);
+
+        group.bench_function(name, |b| {
+            b.iter(|| optimizer.evolve(&mut rng, black_box(&population)))
+        });
+    }
+
+    let population: Vec<Candidate<OneMax>> = (0..50)
+        .map(|_| Candidate::create((0..GENES).map(|_| rng.gen_bool(0.5)).collect()))
+        .collect();
+
+    let ga = GeneticAlgorithm::new(
+        RouletteWheelSelection,
+        UniformCrossover,
+        BitFlipMutation::new(0.01),
+    );
+
+    group.bench_function("genetic algorithm (one max)", |b| {
+        b.iter(|| ga.evolve(&mut rng, black_box(&population)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, crossovers, mutations, generations);
+criterion_main!(benches);
@@ -0,0 +1,614 @@
+//! Standard fitness landscapes, handy for testing & comparing operators.
+//!
+//! Real-valued functions (`sphere()`, `rastrigin()`, `rosenbrock()`,
+//! `ackley()`) are to be minimised and have their global minimum of 0.0;
+//! binary ones (`one_max()`, `deceptive_trap()`) are to be maximised and
+//! reach the chromosome's length at best; the permutation one
+//! (`circle_tour()`) is to be minimised and is shortest when cities are
+//! visited in order.
+//!
+//! Since `Individual::fitness()` must be non-negative and to be maximised,
+//! each function comes with a `Landscape` that converts it into fitness,
+//! and `Candidate` is an individual evaluated on such landscape.
+
+use std::f32::consts::{E, TAU};
+use std::marker::PhantomData;
+
+use crate::{Chromosome, Gene, Individual};
+
+pub fn sphere(x: &[f32]) -> f32 {
+    x.iter().map(|x| x * x).sum()
+}
+
+/// Sphere with a regular grid of local minima laid over it.
+pub fn rastrigin(x: &[f32]) -> f32 {
+    10.0 * (x.len() as f32)
+        + x.iter()
+            .map(|x| x * x - 10.0 * (TAU * x).cos())
+            .sum::<f32>()
+}
+
+/// Long, narrow, curved valley with the minimum at (1, 1, ...).
+pub fn rosenbrock(x: &[f32]) -> f32 {
+    x.windows(2)
+        .map(|w| 100.0 * (w[1] - w[0] * w[0]).powi(2) + (1.0 - w[0]).powi(2))
+        .sum()
+}
+
+/// Nearly flat plane full of local minima, with a single deep hole in the
+/// middle.
+pub fn ackley(x: &[f32]) -> f32 {
+    let n = x.len() as f32;
+    let squares = x.iter().map(|x| x * x).sum::<f32>() / n;
+    let cosines = x.iter().map(|x| (TAU * x).cos()).sum::<f32>() / n;
+
+    // Might end up a tiny bit below zero due to rounding errors
+    (-20.0 * (-0.2 * squares.sqrt()).exp() - cosines.exp() + 20.0 + E).max(0.0)
+}
+
+/// Number of ones.
+pub fn one_max(x: &[bool]) -> f32 {
+    x.iter().filter(|&&bit| bit).count() as f32
+}
+
+/// Concatenated traps of `k` bits each (Deb & Goldberg, 1993): a trap is
+/// worth `k` when all its bits are set, but otherwise it pays the more the
+/// *fewer* bits are set - leading hill-climbers away from the optimum.
+pub fn deceptive_trap(x: &[bool], k: usize) -> f32 {
+    assert!(k > 0);
+    assert_eq!(x.len() % k, 0);
+
+    x.chunks(k)
+        .map(|trap| {
+            let ones = one_max(trap) as usize;
+
+            if ones == k {
+                k as f32
+            } else {
+                (k - 1 - ones) as f32
+            }
+        })
+        .sum()
+}
+
+/// Length of a closed tour through `x.len()` cities spread evenly on a unit
+/// circle, visiting them in the order given by permutation `x` - so the
+/// shortest tour simply goes around the circle.
+pub fn circle_tour(x: &[usize]) -> f32 {
+    let city = |idx: usize| {
+        let angle = TAU * idx as f32 / x.len() as f32;
+        (angle.cos(), angle.sin())
+    };
+
+    (0..x.len())
+        .map(|idx| {
+            let (ax, ay) = city(x[idx]);
+            let (bx, by) = city(x[(idx + 1) % x.len()]);
+
+            (ax - bx).hypot(ay - by)
+        })
+        .sum()
+}
+
+/// Fitness function built on top of a benchmark function.
+pub trait Landscape {
+    type Gene: Gene;
+
+    /// Non-negative, with higher values being better.
+    fn fitness(genes: &[Self::Gene]) -> f32;
+
+    /// Fitness of the global optimum for chromosomes of given length.
+    fn optimum(len: usize) -> f32;
+}
+
+macro_rules! minimisation {
+    ($(#[$doc:meta])* $name:ident, $function:ident) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, Debug)]
+        pub struct $name;
+
+        impl Landscape for $name {
+            type Gene = f32;
+
+            fn fitness(genes: &[f32]) -> f32 {
+                1.0 / (1.0 + $function(genes))
+            }
+
+            fn optimum(_: usize) -> f32 {
+                1.0
+            }
+        }
+    };
+}
+
+minimisation!(
+    /// `1 / (1 + sphere(x))`.
+    Sphere,
+    sphere
+);
+
+minimisation!(
+    /// `1 / (1 + rastrigin(x))`.
+    Rastrigin,
+    rastrigin
+);
+
+minimisation!(
+    /// `1 / (1 + rosenbrock(x))`.
+    Rosenbrock,
+    rosenbrock
+);
+
+minimisation!(
+    /// `1 / (1 + ackley(x))`.
+    Ackley,
+    ackley
+);
+
+#[derive(Clone, Copy, Debug)]
+pub struct OneMax;
+
+impl Landscape for OneMax {
+    type Gene = bool;
+
+    fn fitness(genes: &[bool]) -> f32 {
+        one_max(genes)
+    }
+
+    fn optimum(len: usize) -> f32 {
+        len as f32
+    }
+}
+
+/// Concatenated traps of `K` bits each (see `deceptive_trap()`).
+#[derive(Clone, Copy, Debug)]
+pub struct DeceptiveTrap<const K: usize>;
+
+impl<const K: usize> Landscape for DeceptiveTrap<K> {
+    type Gene = bool;
+
+    fn fitness(genes: &[bool]) -> f32 {
+        deceptive_trap(genes, K)
+    }
+
+    fn optimum(len: usize) -> f32 {
+        len as f32
+    }
+}
+
+/// `shortest / circle_tour(x)`, so that the optimal tour scores 1.0.
+#[derive(Clone, Copy, Debug)]
+pub struct CircleTour;
+
+impl Landscape for CircleTour {
+    type Gene = usize;
+
+    fn fitness(genes: &[usize]) -> f32 {
+        let length = circle_tour(genes);
+
+        if length > 0.0 {
+            let shortest = circle_tour(&(0..genes.len()).collect::<Vec<_>>());
+
+            // Rotated & reversed tours sum the same distances in different
+            // order, so they might end up a tiny bit shorter
+            (shortest / length).min(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    fn optimum(_: usize) -> f32 {
+        1.0
+    }
+}
+
+/// Individual evaluated on landscape `L` (fitness is computed once, upon
+/// creation).
+#[derive(Clone, Debug)]
+pub struct Candidate<L>
+where
+    L: Landscape,
+{
+    chromosome: Chromosome<L::Gene>,
+    fitness: f32,
+    _landscape: PhantomData<L>,
+}
+
+impl<L> Individual<L::Gene> for Candidate<L>
+where
+    L: Landscape,
+{
+    fn create(chromosome: Chromosome<L::Gene>) -> Self {
+        Self {
+            fitness: L::fitness(&chromosome.genes),
+            chromosome,
+            _landscape: PhantomData,
+        }
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+
+    fn chromosome(&self) -> &Chromosome<L::Gene> {
+        &self.chromosome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use approx::assert_relative_eq;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, RngCore, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use test_case::test_case;
+
+    /// Number of seeds each configuration gets run with.
+    const SEEDS: u64 = 5;
+
+    /// Fraction of the runs that must reach the target for a configuration
+    /// to pass - a single unlucky seed shouldn't fail the whole suite.
+    const MIN_SUCCESS_RATE: f32 = 0.8;
+
+    type Crossovers<G> = [(&'static str, fn() -> Box<dyn CrossoverMethod<G>>)];
+    type Mutations<G> = [(&'static str, fn() -> Box<dyn MutationMethod<G>>)];
+
+    #[derive(Clone, Copy, Debug)]
+    enum Selection {
+        RouletteWheel,
+        Tournament,
+    }
+
+    const SELECTIONS: [Selection; 2] = [Selection::RouletteWheel, Selection::Tournament];
+
+    fn ga<L>(
+        selection: Selection,
+        crossover: Box<dyn CrossoverMethod<L::Gene>>,
+        mutation: Box<dyn MutationMethod<L::Gene>>,
+    ) -> Box<dyn Optimizer<Candidate<L>, L::Gene>>
+    where
+        L: Landscape + 'static,
+    {
+        match selection {
+            Selection::RouletteWheel => Box::new(GeneticAlgorithm::new(
+                RouletteWheelSelection,
+                crossover,
+                mutation,
+            )),
+
+            Selection::Tournament => Box::new(GeneticAlgorithm::new(
+                TournamentSelection::new(2),
+                crossover,
+                mutation,
+            )),
+        }
+    }
+
+    /// Runs `optimizer` for up to `generations` generations and returns the
+    /// number of the generation that reached `target` fitness (if any).
+    fn generations_to_reach<L, O>(
+        mut optimizer: O,
+        random_genes: fn(&mut dyn RngCore, usize) -> Vec<L::Gene>,
+        (population, genes): (usize, usize),
+        generations: usize,
+        target: f32,
+        seed: u64,
+    ) -> Option<usize>
+    where
+        L: Landscape,
+        O: Optimizer<Candidate<L>, L::Gene>,
+    {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        let mut population: Vec<_> = (0..population)
+            .map(|_| Candidate::<L>::create(random_genes(&mut rng, genes).into_iter().collect()))
+            .collect();
+
+        for generation in 0..generations {
+            if population
+                .iter()
+                .any(|candidate| candidate.fitness() >= target)
+            {
+                return Some(generation);
+            }
+
+            population = optimizer.evolve(&mut rng, &population).0;
+        }
+
+        None
+    }
+
+    /// Same as `generations_to_reach()`, but repeated for `SEEDS` seeds
+    /// (each with a fresh optimizer); returns the fraction of successful
+    /// runs.
+    fn success_rate<L, O>(
+        optimizer: impl Fn() -> O,
+        random_genes: fn(&mut dyn RngCore, usize) -> Vec<L::Gene>,
+        size: (usize, usize),
+        generations: usize,
+        target: f32,
+    ) -> f32
+    where
+        L: Landscape,
+        O: Optimizer<Candidate<L>, L::Gene>,
+    {
+        let successes = (0..SEEDS)
+            .filter(|&seed| {
+                generations_to_reach::<L, _>(
+                    optimizer(),
+                    random_genes,
+                    size,
+                    generations,
+                    target,
+                    seed,
+                )
+                .is_some()
+            })
+            .count();
+
+        successes as f32 / SEEDS as f32
+    }
+
+    /// Checks every selection × crossover × mutation combination, listing
+    /// all the ones that don't converge often enough.
+    fn assert_all_converge<L>(
+        crossovers: &Crossovers<L::Gene>,
+        mutations: &Mutations<L::Gene>,
+        random_genes: fn(&mut dyn RngCore, usize) -> Vec<L::Gene>,
+        size: (usize, usize),
+        generations: usize,
+        target: f32,
+    ) where
+        L: Landscape + 'static,
+    {
+        let mut failures = Vec::new();
+
+        for selection in SELECTIONS {
+            for (crossover_name, crossover) in crossovers {
+                for (mutation_name, mutation) in mutations {
+                    let rate = success_rate::<L, _>(
+                        || ga::<L>(selection, crossover(), mutation()),
+                        random_genes,
+                        size,
+                        generations,
+                        target,
+                    );
+
+                    if rate < MIN_SUCCESS_RATE {
+                        failures.push(format!(
+                            "{:?} + {} + {}: {:.0}%",
+                            selection,
+                            crossover_name,
+                            mutation_name,
+                            100.0 * rate
+                        ));
+                    }
+                }
+            }
+        }
+
+        assert!(
+            failures.is_empty(),
+            "configurations that don't converge:\n{}",
+            failures.join("\n")
+        );
+    }
+
+    fn real_genes(rng: &mut dyn RngCore, len: usize) -> Vec<f32> {
+        (0..len).map(|_| rng.gen_range(-2.0..2.0)).collect()
+    }
+
+    fn binary_genes(rng: &mut dyn RngCore, len: usize) -> Vec<bool> {
+        (0..len).map(|_| rng.gen_bool(0.5)).collect()
+    }
+
+    fn permutation_genes(rng: &mut dyn RngCore, len: usize) -> Vec<usize> {
+        let mut genes: Vec<_> = (0..len).collect();
+        genes.shuffle(rng);
+        genes
+    }
+
+    const REAL_CROSSOVERS: &Crossovers<f32> = &[
+        ("uniform", || Box::new(UniformCrossover)),
+        ("single point", || Box::new(SinglePointCrossover)),
+        ("k point", || Box::new(KPointCrossover::new(2))),
+        ("arithmetic", || Box::new(ArithmeticCrossover::new(0.5))),
+        ("blend", || Box::new(BlendCrossover::new(0.5))),
+        ("simulated binary", || {
+            Box::new(SimulatedBinaryCrossover::new(2.0))
+        }),
+    ];
+
+    const REAL_MUTATIONS: &Mutations<f32> = &[
+        ("uniform perturbation", || {
+            Box::new(UniformPerturbationMutation::new(0.2, 0.2))
+        }),
+        ("normal", || Box::new(NormalMutation::new(0.2, 0.1))),
+        ("cauchy", || Box::new(CauchyMutation::new(0.2, 0.05))),
+        ("polynomial", || {
+            Box::new(PolynomialMutation::new(0.2, 20.0, -2.0, 2.0))
+        }),
+        ("self adaptive", || {
+            Box::new(SelfAdaptiveMutation::new(0.2, 0.01))
+        }),
+        ("random reset", || {
+            Box::new(RandomResetMutation::new(0.1, -2.0, 2.0))
+        }),
+    ];
+
+    const BINARY_CROSSOVERS: &Crossovers<bool> = &[
+        ("uniform", || Box::new(UniformCrossover)),
+        ("single point", || Box::new(SinglePointCrossover)),
+        ("k point", || Box::new(KPointCrossover::new(2))),
+    ];
+
+    const BINARY_MUTATIONS: &Mutations<bool> = &[
+        ("bit flip", || Box::new(BitFlipMutation::new(0.02))),
+        ("adaptive bit flip", || {
+            Box::new(AdaptiveMutation::new(
+                vec![BitFlipMutation::new(0.01), BitFlipMutation::new(0.05)],
+                0.1,
+            ))
+        }),
+    ];
+
+    const PERMUTATION_CROSSOVERS: &Crossovers<usize> = &[
+        ("order", || Box::new(OrderCrossover)),
+        ("partially mapped", || Box::new(PartiallyMappedCrossover)),
+        ("cycle", || Box::new(CycleCrossover)),
+    ];
+
+    const PERMUTATION_MUTATIONS: &Mutations<usize> = &[
+        ("swap", || Box::new(SwapMutation::new(0.05))),
+        ("inversion", || Box::new(InversionMutation::new(0.3))),
+    ];
+
+    #[test]
+    fn operators_minimise_sphere() {
+        assert_all_converge::<Sphere>(
+            REAL_CROSSOVERS,
+            REAL_MUTATIONS,
+            real_genes,
+            (50, 4),
+            200,
+            0.9,
+        );
+    }
+
+    /// Fitness of 0.6 means `rastrigin(x) < 0.67` - reachable only within
+    /// the global minimum's basin, since the nearest local minima are worth
+    /// about 1.0.
+    #[test]
+    fn operators_minimise_rastrigin() {
+        assert_all_converge::<Rastrigin>(
+            REAL_CROSSOVERS,
+            REAL_MUTATIONS,
+            real_genes,
+            (100, 2),
+            300,
+            0.6,
+        );
+    }
+
+    #[test]
+    fn operators_solve_one_max() {
+        assert_all_converge::<OneMax>(
+            BINARY_CROSSOVERS,
+            BINARY_MUTATIONS,
+            binary_genes,
+            (50, 20),
+            200,
+            20.0,
+        );
+    }
+
+    /// Reaching the optimum means combining all four traps, each of them
+    /// leading hill-climbers towards zeros.
+    #[test]
+    fn operators_solve_deceptive_trap() {
+        assert_all_converge::<DeceptiveTrap<3>>(
+            BINARY_CROSSOVERS,
+            BINARY_MUTATIONS,
+            binary_genes,
+            (200, 12),
+            300,
+            12.0,
+        );
+    }
+
+    #[test]
+    fn operators_solve_circle_tour() {
+        assert_all_converge::<CircleTour>(
+            PERMUTATION_CROSSOVERS,
+            PERMUTATION_MUTATIONS,
+            permutation_genes,
+            (50, 8),
+            300,
+            0.999,
+        );
+    }
+
+    #[test_case(|| Box::new(CmaEs::new(0.5)) ; "cma-es")]
+    #[test_case(|| Box::new(CmaEs::separable(0.5)) ; "sep-cma-es")]
+    #[test_case(|| Box::new(DifferentialEvolution::new(
+        DifferentialEvolutionStrategy::Rand1Bin, 0.6, 0.9
+    )) ; "differential evolution")]
+    #[test_case(|| Box::new(ParticleSwarm::new(0.7, 1.5, 1.5, 0.5)) ; "particle swarm")]
+    fn optimizers_minimise_rosenbrock(
+        optimizer: fn() -> Box<dyn Optimizer<Candidate<Rosenbrock>>>,
+    ) {
+        let rate = success_rate(optimizer, real_genes, (20, 2), 500, 0.99);
+
+        assert!(rate >= MIN_SUCCESS_RATE, "success rate: {}", rate);
+    }
+
+    #[test_case(|| Box::new(CmaEs::new(1.0)) ; "cma-es")]
+    #[test_case(|| Box::new(DifferentialEvolution::new(
+        DifferentialEvolutionStrategy::Best1Bin, 0.6, 0.9
+    )) ; "differential evolution")]
+    fn optimizers_minimise_multimodal_functions(
+        optimizer: fn() -> Box<dyn Optimizer<Candidate<Ackley>>>,
+    ) {
+        let rate = success_rate(optimizer, real_genes, (30, 4), 500, 0.99);
+
+        assert!(rate >= MIN_SUCCESS_RATE, "success rate: {}", rate);
+    }
+
+    #[test]
+    fn real_functions() {
+        let optimum = [0.0; 4];
+
+        assert_relative_eq!(sphere(&optimum), 0.0);
+        assert_relative_eq!(rastrigin(&optimum), 0.0);
+        assert_relative_eq!(rosenbrock(&[1.0; 4]), 0.0);
+        assert_relative_eq!(ackley(&optimum), 0.0, epsilon = 1e-5);
+
+        let x = [1.0, -2.0];
+
+        assert_relative_eq!(sphere(&x), 5.0);
+        assert_relative_eq!(rastrigin(&x), 5.0, epsilon = 1e-4);
+        assert_relative_eq!(rosenbrock(&x), 900.0);
+        assert_relative_eq!(ackley(&x), 5.42213, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn binary_functions() {
+        assert_relative_eq!(one_max(&[true, false, true, true]), 3.0);
+
+        // The optimum ...
+        assert_relative_eq!(deceptive_trap(&[true; 8], 4), 8.0);
+
+        // ... and the deceptive attractor
+        assert_relative_eq!(deceptive_trap(&[false; 8], 4), 6.0);
+
+        // A single bit away from the optimum is the worst place to be
+        assert_relative_eq!(
+            deceptive_trap(&[true, true, true, false, true, true, true, true], 4),
+            4.0
+        );
+    }
+
+    #[test]
+    fn permutation_functions() {
+        // Square's perimeter ...
+        assert_relative_eq!(circle_tour(&[0, 1, 2, 3]), 4.0 * 2.0f32.sqrt());
+
+        // ... is shorter than going across it
+        assert_relative_eq!(circle_tour(&[0, 2, 1, 3]), 4.0 + 2.0 * 2.0f32.sqrt());
+
+        assert_relative_eq!(CircleTour::fitness(&[3, 2, 1, 0]), 1.0, epsilon = 1e-6);
+        assert!(CircleTour::fitness(&[0, 2, 1, 3]) < 1.0);
+    }
+
+    #[test]
+    fn landscapes() {
+        let candidate = Candidate::<Sphere>::create(vec![1.0, 2.0].into_iter().collect());
+        assert_relative_eq!(candidate.fitness(), 1.0 / 6.0);
+        assert_relative_eq!(Sphere::optimum(2), 1.0);
+
+        let candidate = Candidate::<DeceptiveTrap<2>>::create(vec![true; 4].into_iter().collect());
+        assert_relative_eq!(candidate.fitness(), DeceptiveTrap::<2>::optimum(4));
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{Chromosome, Gene};
+
+/// How an individual has come into existence - reported by
+/// `Optimizer::evolve_traced()`, so that callers can track genealogy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Birth {
+    /// Indices of both parents within the evolved population; `None` for
+    /// individuals sampled rather than bred (e.g. by `CmaEs`).
+    pub parents: Option<[usize; 2]>,
+
+    /// How many genes have been inherited unchanged from each parent -
+    /// genes blended by crossover (e.g. `ArithmeticCrossover`) count for
+    /// neither.
+    pub inherited: [usize; 2],
+
+    /// Indices of genes changed by mutation.
+    pub mutated: Vec<usize>,
+}
+
+impl Birth {
+    pub fn sampled() -> Self {
+        Self {
+            parents: None,
+            inherited: [0, 0],
+            mutated: Vec::new(),
+        }
+    }
+
+    /// Compares child's genes before (`crossed`) and after (`child`)
+    /// mutation against its parents.
+    pub(crate) fn bred<G>(
+        parents: [usize; 2],
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+        crossed: &Chromosome<G>,
+        child: &Chromosome<G>,
+    ) -> Self
+    where
+        G: Gene,
+    {
+        let mut inherited = [0, 0];
+
+        for ((gene, a), b) in child.iter().zip(parent_a.iter()).zip(parent_b.iter()) {
+            if gene == a {
+                inherited[0] += 1;
+            } else if gene == b {
+                inherited[1] += 1;
+            }
+        }
+
+        let mutated = crossed
+            .iter()
+            .zip(child.iter())
+            .enumerate()
+            .filter(|(_, (before, after))| before != after)
+            .map(|(idx, _)| idx)
+            .collect();
+
+        Self {
+            parents: Some(parents),
+            inherited,
+            mutated,
+        }
+    }
+}
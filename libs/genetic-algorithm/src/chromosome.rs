@@ -1,39 +1,90 @@
-use std::ops::Index;
+use std::ops::{Index, IndexMut};
+
+use crate::Gene;
 
 #[derive(Clone, Debug)]
-pub struct Chromosome {
-    pub genes: Vec<f32>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chromosome<G = f32> {
+    pub genes: Vec<G>,
+
+    /// Mutation step size carried along the genes, as in evolution
+    /// strategies; used (and evolved) only by `SelfAdaptiveMutation`.
+    sigma: Option<f32>,
 }
-impl Chromosome {
+impl<G> Chromosome<G> {
+    pub fn sigma(&self) -> Option<f32> {
+        self.sigma
+    }
+
+    pub fn set_sigma(&mut self, sigma: Option<f32>) {
+        self.sigma = sigma;
+    }
+
+    /// Gives child the geometric mean of its parents' step sizes.
+    ///
+    /// Crossover operators only ever look at the genes, so this is called
+    /// right after crossover, while breeding.
+    pub(crate) fn inherit_sigma(&mut self, parent_a: &Self, parent_b: &Self) {
+        self.sigma = match (parent_a.sigma, parent_b.sigma) {
+            (Some(a), Some(b)) => Some((a * b).sqrt()),
+            (a, b) => a.or(b),
+        };
+    }
+
     pub fn len(&self) -> usize {
         self.genes.len()
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &f32> {
+    pub fn is_empty(&self) -> bool {
+        self.genes.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &G> {
         self.genes.iter()
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut G> {
         self.genes.iter_mut()
     }
 }
-impl Index<usize> for Chromosome {
-    type Output = f32;
+impl<G> Chromosome<G>
+where
+    G: Gene,
+{
+    /// Euclidean distance between two chromosomes (see `Gene::value()`).
+    pub fn distance(&self, other: &Self) -> f32 {
+        assert_eq!(self.len(), other.len());
+
+        self.iter()
+            .zip(other.iter())
+            .map(|(a, b)| (a.value() - b.value()).powi(2))
+            .sum::<f32>()
+            .sqrt()
+    }
+}
+impl<G> Index<usize> for Chromosome<G> {
+    type Output = G;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.genes[index]
     }
 }
-impl FromIterator<f32> for Chromosome {
-    fn from_iter<T: IntoIterator<Item = f32>>(iter: T) -> Self {
+impl<G> IndexMut<usize> for Chromosome<G> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.genes[index]
+    }
+}
+impl<G> FromIterator<G> for Chromosome<G> {
+    fn from_iter<T: IntoIterator<Item = G>>(iter: T) -> Self {
         Self {
             genes: iter.into_iter().collect(),
+            sigma: None,
         }
     }
 }
-impl IntoIterator for Chromosome {
-    type Item = f32;
-    type IntoIter = std::vec::IntoIter<f32>;
+impl<G> IntoIterator for Chromosome<G> {
+    type Item = G;
+    type IntoIter = std::vec::IntoIter<G>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.genes.into_iter()
@@ -0,0 +1,423 @@
+use nalgebra::{DMatrix, DVector};
+use rand::{Rng, RngCore};
+use rand_distr::StandardNormal;
+
+use std::ops::Bound;
+
+use crate::{Chromosome, Error, Individual, Optimizer, Statistics};
+
+/// Covariance Matrix Adaptation Evolution Strategy (Hansen & Ostermeier,
+/// 2001), following "The CMA Evolution Strategy: A Tutorial" (Hansen, 2016).
+///
+/// Instead of breeding individuals, CMA-ES samples each generation from a
+/// multivariate normal distribution and moves that distribution towards
+/// the best samples, learning correlations between genes on the way.
+///
+/// The number of samples equals the size of the population we are given;
+/// the first population (e.g. random birds) only determines the starting
+/// point.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CmaEs {
+    /// Initial step size.
+    sigma0: f32,
+    separable: bool,
+    state: Option<State>,
+}
+
+impl CmaEs {
+    pub fn new(sigma: f32) -> Self {
+        Self::try_new(sigma).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(sigma: f32) -> Result<Self, Error> {
+        let positive = (Bound::Excluded(0.0), Bound::Unbounded);
+
+        Error::check("sigma", sigma, positive, "(0.0, inf)")?;
+
+        Ok(Self {
+            sigma0: sigma,
+            separable: false,
+            state: None,
+        })
+    }
+
+    /// sep-CMA-ES (Ros & Hansen, 2008) - learns only the diagonal of the
+    /// covariance matrix, which costs O(n) instead of O(n²) memory and
+    /// O(n³) time, and adapts faster on large genomes.
+    pub fn separable(sigma: f32) -> Self {
+        Self::try_separable(sigma).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_separable(sigma: f32) -> Result<Self, Error> {
+        Ok(Self {
+            separable: true,
+            ..Self::try_new(sigma)?
+        })
+    }
+
+    /// Current step size, if the optimizer has been started already.
+    pub fn sigma(&self) -> Option<f32> {
+        self.state.as_ref().map(|state| state.sigma)
+    }
+
+    /// Current mean of the search distribution.
+    pub fn mean(&self) -> Option<Chromosome> {
+        self.state
+            .as_ref()
+            .map(|state| state.mean.iter().copied().collect())
+    }
+}
+
+impl<I> Optimizer<I> for CmaEs
+where
+    I: Individual,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics) {
+        assert!(!population.is_empty());
+
+        let mut ranked: Vec<_> = population.iter().collect();
+        ranked.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+
+        let samples: Vec<_> = ranked
+            .iter()
+            .map(|individual| {
+                DVector::from_iterator(
+                    individual.chromosome().len(),
+                    individual.chromosome().iter().copied(),
+                )
+            })
+            .collect();
+
+        let (sigma0, separable) = (self.sigma0, self.separable);
+
+        let state = self
+            .state
+            .get_or_insert_with(|| State::new(&samples, population.len(), sigma0, separable));
+
+        state.update(&samples);
+
+        let new_population = (0..population.len())
+            .map(|_| I::create(state.sample(rng)))
+            .collect();
+
+        (new_population, Statistics::new(population))
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct State {
+    params: Parameters,
+    mean: DVector<f32>,
+    sigma: f32,
+    covariance: Covariance,
+
+    /// Evolution path of the covariance matrix.
+    pc: DVector<f32>,
+
+    /// Evolution path of the step size.
+    ps: DVector<f32>,
+
+    generation: usize,
+}
+
+impl State {
+    fn new(samples: &[DVector<f32>], lambda: usize, sigma: f32, separable: bool) -> Self {
+        let n = samples[0].len();
+        let params = Parameters::new(n, lambda, separable);
+
+        // We don't know the distribution the first population comes from,
+        // so we simply start at the recombination of its best members
+        let mean = params.recombine(samples.iter().cloned());
+
+        let covariance = if separable {
+            Covariance::Diagonal(DVector::from_element(n, 1.0))
+        } else {
+            Covariance::full(n)
+        };
+
+        Self {
+            params,
+            mean,
+            sigma,
+            covariance,
+            pc: DVector::zeros(n),
+            ps: DVector::zeros(n),
+            generation: 0,
+        }
+    }
+
+    /// Moves the distribution towards given samples, sorted from the best.
+    fn update(&mut self, samples: &[DVector<f32>]) {
+        self.generation += 1;
+
+        // The first population hasn't been sampled from our distribution,
+        // so there's nothing to learn from it yet
+        if self.generation == 1 {
+            return;
+        }
+
+        let p = &self.params;
+        let n = self.mean.len() as f32;
+
+        let steps: Vec<_> = samples
+            .iter()
+            .map(|x| (x - &self.mean) / self.sigma)
+            .collect();
+
+        let step = p.recombine(steps.iter().cloned());
+        self.mean += &step * self.sigma;
+
+        self.ps = &self.ps * (1.0 - p.cs)
+            + self.covariance.inv_sqrt_mul(&step) * (p.cs * (2.0 - p.cs) * p.mu_eff).sqrt();
+
+        let ps_norm = self.ps.norm() / (1.0 - (1.0 - p.cs).powi(2 * self.generation as i32)).sqrt();
+
+        let hsig = ps_norm < (1.4 + 2.0 / (n + 1.0)) * p.chi_n;
+        let hsig = if hsig { 1.0 } else { 0.0 };
+
+        self.pc = &self.pc * (1.0 - p.cc) + &step * hsig * (p.cc * (2.0 - p.cc) * p.mu_eff).sqrt();
+
+        let decay = 1.0 - p.c1 - p.cmu + (1.0 - hsig) * p.c1 * p.cc * (2.0 - p.cc);
+
+        let weighted_steps = steps.iter().take(p.weights.len()).zip(&p.weights);
+
+        match &mut self.covariance {
+            Covariance::Full { c, .. } => {
+                let mut rank_mu = DMatrix::zeros(c.nrows(), c.ncols());
+
+                for (y, w) in weighted_steps {
+                    rank_mu += y * y.transpose() * *w;
+                }
+
+                *c = &*c * decay + &self.pc * self.pc.transpose() * p.c1 + rank_mu * p.cmu;
+            }
+
+            Covariance::Diagonal(c) => {
+                let mut rank_mu = DVector::zeros(c.len());
+
+                for (y, w) in weighted_steps {
+                    rank_mu += y.component_mul(y) * *w;
+                }
+
+                *c = &*c * decay + self.pc.component_mul(&self.pc) * p.c1 + rank_mu * p.cmu;
+            }
+        }
+
+        self.covariance.decompose();
+
+        self.sigma *= ((p.cs / p.ds) * (self.ps.norm() / p.chi_n - 1.0)).exp();
+    }
+
+    fn sample(&self, rng: &mut dyn RngCore) -> Chromosome {
+        let z = DVector::from_fn(self.mean.len(), |_, _| rng.sample::<f32, _>(StandardNormal));
+        let x = &self.mean + self.covariance.sqrt_mul(&z) * self.sigma;
+
+        x.iter().copied().collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Covariance {
+    Full {
+        c: DMatrix<f32>,
+
+        /// Eigenvectors of `c` (as columns).
+        b: DMatrix<f32>,
+
+        /// Square roots of eigenvalues of `c`.
+        d: DVector<f32>,
+    },
+
+    /// Just the diagonal of the covariance matrix.
+    Diagonal(DVector<f32>),
+}
+
+impl Covariance {
+    fn full(n: usize) -> Self {
+        Self::Full {
+            c: DMatrix::identity(n, n),
+            b: DMatrix::identity(n, n),
+            d: DVector::from_element(n, 1.0),
+        }
+    }
+
+    fn decompose(&mut self) {
+        if let Self::Full { c, b, d } = self {
+            // Enforces symmetry, which floating-point errors slowly erode
+            *c = (&*c + c.transpose()) * 0.5;
+
+            let eigen = c.clone().symmetric_eigen();
+
+            *b = eigen.eigenvectors;
+            *d = eigen.eigenvalues.map(|value| value.max(1e-20).sqrt());
+        }
+    }
+
+    /// C^(1/2) * z
+    fn sqrt_mul(&self, z: &DVector<f32>) -> DVector<f32> {
+        match self {
+            Self::Full { b, d, .. } => b * z.component_mul(d),
+            Self::Diagonal(c) => z.component_mul(&c.map(f32::sqrt)),
+        }
+    }
+
+    /// C^(-1/2) * y
+    fn inv_sqrt_mul(&self, y: &DVector<f32>) -> DVector<f32> {
+        match self {
+            Self::Full { b, d, .. } => b * (b.transpose() * y).component_div(d),
+            Self::Diagonal(c) => y.component_div(&c.map(f32::sqrt)),
+        }
+    }
+}
+
+/// Strategy parameters, all set to their default values from the tutorial.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Parameters {
+    /// Recombination weights of the best `mu` samples.
+    weights: Vec<f32>,
+    mu_eff: f32,
+    cc: f32,
+    cs: f32,
+    c1: f32,
+    cmu: f32,
+    ds: f32,
+
+    /// Expected length of a N(0, I) distributed vector.
+    chi_n: f32,
+}
+
+impl Parameters {
+    fn new(n: usize, lambda: usize, separable: bool) -> Self {
+        let n = n as f32;
+        let mu = (lambda / 2).max(1);
+
+        let weights: Vec<f32> = (1..=mu)
+            .map(|i| ((mu as f32) + 0.5).ln() - (i as f32).ln())
+            .collect();
+
+        let sum: f32 = weights.iter().sum();
+        let weights: Vec<f32> = weights.iter().map(|w| w / sum).collect();
+        let mu_eff = 1.0 / weights.iter().map(|w| w * w).sum::<f32>();
+
+        let cc = (4.0 + mu_eff / n) / (n + 4.0 + 2.0 * mu_eff / n);
+        let cs = (mu_eff + 2.0) / (n + mu_eff + 5.0);
+        let mut c1 = 2.0 / ((n + 1.3).powi(2) + mu_eff);
+        let mut cmu =
+            (2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((n + 2.0).powi(2) + mu_eff)).min(1.0 - c1);
+
+        if separable {
+            // Diagonal learns n instead of n² values, so it can learn faster
+            c1 = (c1 * (n + 2.0) / 3.0).min(1.0);
+            cmu = (cmu * (n + 2.0) / 3.0).min(1.0 - c1);
+        }
+
+        let ds = 1.0 + 2.0 * (((mu_eff - 1.0) / (n + 1.0)).sqrt() - 1.0).max(0.0) + cs;
+        let chi_n = n.sqrt() * (1.0 - 1.0 / (4.0 * n) + 1.0 / (21.0 * n * n));
+
+        Self {
+            weights,
+            mu_eff,
+            cc,
+            cs,
+            c1,
+            cmu,
+            ds,
+            chi_n,
+        }
+    }
+
+    /// Weighted average of the best `mu` vectors (sorted from the best).
+    fn recombine(&self, vectors: impl Iterator<Item = DVector<f32>>) -> DVector<f32> {
+        vectors
+            .zip(&self.weights)
+            .map(|(v, w)| v * *w)
+            .reduce(|a, b| a + b)
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Rotated ellipsoid - genes are strongly correlated, which is where
+    /// the full covariance matrix shines.
+    #[derive(Debug)]
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn fitness(&self) -> f32 {
+            let genes = &self.chromosome.genes;
+
+            let cost: f32 = (0..genes.len())
+                .map(|i| {
+                    let partial: f32 = genes[..=i].iter().map(|g| g - 1.0).sum();
+                    partial * partial
+                })
+                .sum();
+
+            -cost
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+    }
+
+    fn run(mut optimizer: CmaEs) -> f32 {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut population: Vec<_> = (0..12)
+            .map(|_| TestIndividual::create((0..6).map(|_| rng.gen_range(-1.0..1.0)).collect()))
+            .collect();
+
+        let mut best = f32::MIN;
+
+        for _ in 0..200 {
+            let (new_population, stats) = optimizer.evolve(&mut rng, &population);
+            best = best.max(stats.max_fitness);
+            population = new_population;
+        }
+
+        best
+    }
+
+    #[test]
+    fn cma_es() {
+        assert!(run(CmaEs::new(0.5)) > -1e-6);
+    }
+
+    #[test]
+    fn separable_cma_es() {
+        assert!(run(CmaEs::separable(0.5)) > -1e-6);
+    }
+
+    #[test]
+    fn first_population_sets_the_mean() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut optimizer = CmaEs::new(0.1);
+
+        let population = vec![
+            TestIndividual::create(vec![1.0, 1.0].into_iter().collect()),
+            TestIndividual::create(vec![5.0, 5.0].into_iter().collect()),
+        ];
+
+        let (new_population, _) = optimizer.evolve(&mut rng, &population);
+
+        // The best one (the closest to 1.0) wins the whole weight
+        assert_eq!(optimizer.mean().unwrap().genes, vec![1.0, 1.0]);
+        assert_eq!(new_population.len(), 2);
+    }
+}
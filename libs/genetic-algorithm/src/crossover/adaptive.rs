@@ -0,0 +1,60 @@
+use rand::RngCore;
+
+use crate::operator_selection::OperatorSelection;
+use crate::{Chromosome, CrossoverMethod, Error, Statistics};
+
+/// Picks one of several crossover methods each generation, preferring the
+/// ones whose offspring have recently improved the population the most.
+///
+/// Different kinds of methods can be mixed by boxing them, i.e. by using
+/// `AdaptiveCrossover<Box<dyn CrossoverMethod>>`.
+#[derive(Clone, Debug)]
+pub struct AdaptiveCrossover<C> {
+    methods: Vec<C>,
+    selection: OperatorSelection,
+}
+
+impl<C> AdaptiveCrossover<C> {
+    /// See `AdaptiveMutation::new()` for the meaning of `exploration`.
+    pub fn new(methods: Vec<C>, exploration: f32) -> Self {
+        Self::try_new(methods, exploration).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(methods: Vec<C>, exploration: f32) -> Result<Self, Error> {
+        let selection = OperatorSelection::try_new(methods.len(), exploration)?;
+
+        Ok(Self { methods, selection })
+    }
+
+    /// Index of the method currently used.
+    pub fn current(&self) -> usize {
+        self.selection.current()
+    }
+
+    /// Average fitness gain of offspring produced by each method.
+    pub fn scores(&self) -> Vec<f32> {
+        self.selection.scores()
+    }
+}
+
+impl<G, C> CrossoverMethod<G> for AdaptiveCrossover<C>
+where
+    C: CrossoverMethod<G>,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        self.methods[self.current()].crossover(rng, parent_a, parent_b)
+    }
+
+    fn adapt(&mut self, stats: &Statistics) {
+        for method in &mut self.methods {
+            method.adapt(stats);
+        }
+
+        self.selection.adapt(stats);
+    }
+}
@@ -0,0 +1,65 @@
+use rand::RngCore;
+
+use crate::{Chromosome, CrossoverMethod, Error};
+
+/// Weighted average of both parents:
+///
+/// child = alpha * parent_a + (1 - alpha) * parent_b
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArithmeticCrossover {
+    /// Weight of the first parent:
+    /// - 0.0 = child is a copy of the second parent
+    /// - 0.5 = child lies right in the middle
+    /// - 1.0 = child is a copy of the first parent
+    alpha: f32,
+}
+
+impl ArithmeticCrossover {
+    pub fn new(alpha: f32) -> Self {
+        Self::try_new(alpha).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(alpha: f32) -> Result<Self, Error> {
+        Error::check("alpha", alpha, 0.0..=1.0, "0.0..=1.0")?;
+
+        Ok(Self { alpha })
+    }
+}
+
+impl CrossoverMethod for ArithmeticCrossover {
+    fn crossover(
+        &self,
+        _: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(a, b)| self.alpha * a + (1.0 - self.alpha) * b)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn arithmetic_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+        let parent_b: Chromosome = vec![-1.0, 0.0, 5.0].into_iter().collect();
+
+        let child = ArithmeticCrossover::new(0.25).crossover(&mut rng, &parent_a, &parent_b);
+        let actual: Vec<_> = child.into_iter().collect();
+
+        assert_relative_eq!(actual.as_slice(), [-0.5, 0.5, 4.5].as_slice());
+    }
+}
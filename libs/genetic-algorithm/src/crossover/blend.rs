@@ -0,0 +1,89 @@
+use rand::{Rng, RngCore};
+
+use crate::{Chromosome, CrossoverMethod, Error};
+
+/// BLX-α (Eshelman & Schaffer, 1993): each gene is drawn uniformly from
+/// the range spanned by both parents, extended on each side by `alpha`
+/// times its width.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlendCrossover {
+    /// - 0.0 = children always lie between their parents
+    /// - 0.5 = the usual choice, keeps the population's variance stable
+    alpha: f32,
+}
+
+impl BlendCrossover {
+    pub fn new(alpha: f32) -> Self {
+        Self::try_new(alpha).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(alpha: f32) -> Result<Self, Error> {
+        Error::check("alpha", alpha, 0.0.., "0.0..")?;
+
+        Ok(Self { alpha })
+    }
+}
+
+impl CrossoverMethod for BlendCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                let extent = self.alpha * (a - b).abs();
+                let min = a.min(b) - extent;
+                let max = a.max(b) + extent;
+
+                if min < max {
+                    rng.gen_range(min..=max)
+                } else {
+                    a
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn blend_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+        let child = BlendCrossover::new(0.5).crossover(&mut rng, &parent_a, &parent_b);
+
+        // Every gene lies within the extended range of its parents
+        assert!(child
+            .iter()
+            .zip(parent_a.iter())
+            .all(|(c, p)| c.abs() <= 2.0 * p));
+
+        let outside = child
+            .iter()
+            .zip(parent_a.iter())
+            .filter(|(c, p)| c.abs() > **p)
+            .count();
+
+        assert_eq!(outside, 40);
+
+        let first: Vec<_> = child.iter().take(3).copied().collect();
+        assert_relative_eq!(
+            first.as_slice(),
+            [-1.2510376, 2.6953583, 4.908757].as_slice()
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use rand::RngCore;
+
+use crate::{Chromosome, CrossoverMethod, Gene};
+
+/// Cycle crossover (CX; Oliver et al., 1987) for permutations: positions
+/// are split into cycles (following parent A's gene to its position in
+/// parent B, and so on) and the cycles are taken alternately from both
+/// parents, so every gene keeps the position it had in one of them.
+///
+/// Deterministic - the random number generator isn't used at all.
+#[derive(Clone, Debug)]
+pub struct CycleCrossover;
+
+impl<G> CrossoverMethod<G> for CycleCrossover
+where
+    G: Gene + Eq + Hash,
+{
+    fn crossover(
+        &self,
+        _rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let positions_a: HashMap<&G, usize> = parent_a.iter().zip(0..).collect();
+        let mut genes: Vec<Option<G>> = vec![None; parent_a.len()];
+        let mut from_a = true;
+
+        for start in 0..parent_a.len() {
+            if genes[start].is_some() {
+                continue;
+            }
+
+            let parent = if from_a { parent_a } else { parent_b };
+            let mut idx = start;
+
+            loop {
+                genes[idx] = Some(parent[idx].clone());
+                idx = positions_a[&parent_b[idx]];
+
+                if idx == start {
+                    break;
+                }
+            }
+
+            from_a = !from_a;
+        }
+
+        genes.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn cycle_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome<usize> = vec![1, 2, 3, 4, 5, 6, 7, 8].into_iter().collect();
+        let parent_b: Chromosome<usize> = vec![8, 5, 2, 1, 3, 6, 4, 7].into_iter().collect();
+
+        let child = CycleCrossover.crossover(&mut rng, &parent_a, &parent_b);
+
+        // Cycles: {0, 3, 6, 7}, {1, 2, 4}, {5}
+        assert_eq!(child.genes, vec![1, 5, 2, 4, 3, 6, 7, 8]);
+    }
+}
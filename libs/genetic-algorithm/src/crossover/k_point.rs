@@ -0,0 +1,109 @@
+use rand::seq::index;
+use rand::RngCore;
+
+use crate::{Chromosome, CrossoverMethod, Error, Gene};
+
+/// Generalisation of `SinglePointCrossover`: chooses `k` random points and
+/// switches between parents at each one of them.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KPointCrossover {
+    k: usize,
+}
+
+impl KPointCrossover {
+    pub fn new(k: usize) -> Self {
+        Self::try_new(k).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(k: usize) -> Result<Self, Error> {
+        Error::check("number of crossover points", k as f32, 1.0.., "1..")?;
+
+        Ok(Self { k })
+    }
+}
+
+impl<G> CrossoverMethod<G> for KPointCrossover
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        if parent_a.len() < 2 {
+            return parent_a.clone();
+        }
+
+        let k = self.k.min(parent_a.len() - 1);
+
+        let mut points: Vec<_> = index::sample(rng, parent_a.len() - 1, k)
+            .into_iter()
+            .map(|point| point + 1)
+            .collect();
+
+        points.sort_unstable();
+
+        let mut points = points.into_iter().peekable();
+        let mut from_a = true;
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .enumerate()
+            .map(|(idx, (a, b))| {
+                if points.next_if_eq(&idx).is_some() {
+                    from_a = !from_a;
+                }
+
+                if from_a {
+                    a.clone()
+                } else {
+                    b.clone()
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn k_point_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+        let child = KPointCrossover::new(3).crossover(&mut rng, &parent_a, &parent_b);
+
+        let diff_a = child
+            .iter()
+            .zip(parent_a.iter())
+            .filter(|(c, p)| c != p)
+            .count();
+        let diff_b = child
+            .iter()
+            .zip(parent_b.iter())
+            .filter(|(c, p)| c != p)
+            .count();
+
+        assert_eq!(diff_a, 61);
+        assert_eq!(diff_b, 39);
+
+        // Three points = four runs of genes
+        let switches = child
+            .iter()
+            .zip(child.iter().skip(1))
+            .filter(|(a, b)| a.signum() != b.signum())
+            .count();
+
+        assert_eq!(switches, 3);
+    }
+}
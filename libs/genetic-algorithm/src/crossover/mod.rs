@@ -1,19 +1,65 @@
 use rand::Rng;
 use rand::RngCore;
 
-use crate::{Chromosome, CrossoverMethod};
+use crate::{Chromosome, CrossoverMethod, Gene};
+
+mod adaptive;
+mod arithmetic;
+mod blend;
+mod cycle;
+mod k_point;
+mod neuron;
+mod order;
+mod partially_mapped;
+mod simulated_binary;
+mod single_point;
+
+pub use adaptive::AdaptiveCrossover;
+pub use arithmetic::ArithmeticCrossover;
+pub use blend::BlendCrossover;
+pub use cycle::CycleCrossover;
+pub use k_point::KPointCrossover;
+pub use neuron::NeuronCrossover;
+pub use order::OrderCrossover;
+pub use partially_mapped::PartiallyMappedCrossover;
+pub use simulated_binary::SimulatedBinaryCrossover;
+pub use single_point::SinglePointCrossover;
+
+/// Picks a random segment `from..to` spanning at least two genes of a
+/// chromosome of given length - shared by operators working on
+/// permutations.
+pub(crate) fn random_segment(rng: &mut dyn RngCore, len: usize) -> (usize, usize) {
+    let from = rng.gen_range(0..len - 1);
+    let to = rng.gen_range(from + 2..=len);
+
+    (from, to)
+}
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UniformCrossover;
 
-impl CrossoverMethod for UniformCrossover {
-    fn crossover(&self, rng: &mut dyn RngCore, parent_a: &Chromosome, parent_b: &Chromosome) -> Chromosome
-    {
+impl<G> CrossoverMethod<G> for UniformCrossover
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
         assert_eq!(parent_a.len(), parent_b.len());
         parent_a
             .iter()
             .zip(parent_b.iter())
-            .map(|(&a, &b)| if rng.gen_bool(0.5) { a } else { b })
+            .map(|(a, b)| {
+                if rng.gen_bool(0.5) {
+                    a.clone()
+                } else {
+                    b.clone()
+                }
+            })
             .collect()
     }
 }
@@ -0,0 +1,95 @@
+use rand::{Rng, RngCore};
+
+use crate::{Chromosome, CrossoverMethod, Error, Gene};
+
+/// Layer-aware crossover for chromosomes encoding neural networks: genes
+/// are grouped into neurons (bias followed by weights, as given by
+/// `lib_neural_network::Network::neuron_sizes()`) and each neuron is
+/// inherited as a whole from one of the parents.
+///
+/// Mixing genes across neurons tends to break whatever the parents'
+/// neurons have learned to detect - swapping whole neurons doesn't.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NeuronCrossover {
+    /// Number of genes of each consecutive neuron.
+    neuron_sizes: Vec<usize>,
+}
+
+impl NeuronCrossover {
+    pub fn new(neuron_sizes: Vec<usize>) -> Self {
+        Self::try_new(neuron_sizes).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(neuron_sizes: Vec<usize>) -> Result<Self, Error> {
+        for &size in &neuron_sizes {
+            Error::check("neuron size", size as f32, 1.0.., "1..")?;
+        }
+
+        Ok(Self { neuron_sizes })
+    }
+}
+
+impl<G> CrossoverMethod<G> for NeuronCrossover
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+        assert_eq!(parent_a.len(), self.neuron_sizes.iter().sum::<usize>());
+
+        let mut genes = Vec::with_capacity(parent_a.len());
+
+        for &size in &self.neuron_sizes {
+            let from = genes.len();
+            let parent = if rng.gen_bool(0.5) {
+                parent_a
+            } else {
+                parent_b
+            };
+
+            genes.extend(parent.iter().skip(from).take(size).cloned());
+        }
+
+        genes.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn neuron_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        // Three inputs, two hidden neurons, one output:
+        // 2 * (1 + 3) + 1 * (1 + 2) = 11 genes
+        let neuron_sizes = vec![4, 4, 3];
+        let parent_a: Chromosome = (1..=11).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=11).map(|n| -n as f32).collect();
+
+        for _ in 0..10 {
+            let child = NeuronCrossover::new(neuron_sizes.clone())
+                .crossover(&mut rng, &parent_a, &parent_b);
+
+            // Every neuron comes as a whole from exactly one parent
+            let mut from = 0;
+            for &size in &neuron_sizes {
+                let neuron: Vec<_> = child.iter().skip(from).take(size).collect();
+                assert!(
+                    neuron.iter().all(|gene| **gene > 0.0)
+                        || neuron.iter().all(|gene| **gene < 0.0)
+                );
+                from += size;
+            }
+        }
+    }
+}
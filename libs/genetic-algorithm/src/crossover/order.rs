@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use rand::RngCore;
+
+use crate::crossover::random_segment;
+use crate::{Chromosome, CrossoverMethod, Gene};
+
+/// Order crossover (OX; Davis, 1985) for permutations: a random segment is
+/// copied from the first parent and the remaining genes are filled in the
+/// order they appear in the second one, starting right after the segment.
+///
+/// Preserves relative order of genes, which suits travelling salesman
+/// problem.
+#[derive(Clone, Debug)]
+pub struct OrderCrossover;
+
+impl<G> CrossoverMethod<G> for OrderCrossover
+where
+    G: Gene + Eq + Hash,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let len = parent_a.len();
+
+        if len < 2 {
+            return parent_a.clone();
+        }
+
+        let (from, to) = random_segment(rng, len);
+        let segment: HashSet<&G> = parent_a.genes[from..to].iter().collect();
+
+        let mut rest = (0..len)
+            .map(|idx| &parent_b[(to + idx) % len])
+            .filter(|gene| !segment.contains(gene));
+
+        let mut genes: Vec<Option<G>> = vec![None; len];
+
+        for idx in from..to {
+            genes[idx] = Some(parent_a[idx].clone());
+        }
+
+        for idx in (0..len - (to - from)).map(|idx| (to + idx) % len) {
+            genes[idx] = rest.next().cloned();
+        }
+
+        genes
+            .into_iter()
+            .map(|gene| gene.expect("parents aren't permutations of each other"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneticAlgorithm, Individual, InversionMutation, RouletteWheelSelection};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn order_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome<usize> = (0..10).collect();
+        let parent_b: Chromosome<usize> = (0..10).rev().collect();
+
+        for _ in 0..10 {
+            let child = OrderCrossover.crossover(&mut rng, &parent_a, &parent_b);
+
+            let mut genes: Vec<_> = child.iter().copied().collect();
+            genes.sort_unstable();
+            assert_eq!(genes, (0..10).collect::<Vec<_>>());
+        }
+
+        let child = OrderCrossover.crossover(&mut rng, &parent_a, &parent_b);
+        assert_eq!(child.genes, vec![9, 8, 2, 3, 4, 5, 6, 7, 1, 0]);
+    }
+
+    /// Tour through cities placed evenly on a circle; the shortest one
+    /// visits them in order (either way round).
+    struct Tour {
+        chromosome: Chromosome<usize>,
+    }
+
+    const CITIES: usize = 8;
+
+    impl Tour {
+        fn length(&self) -> f32 {
+            let city = |idx: usize| {
+                let angle = std::f32::consts::TAU * (self.chromosome[idx % CITIES] as f32)
+                    / (CITIES as f32);
+
+                (angle.cos(), angle.sin())
+            };
+
+            (0..CITIES)
+                .map(|idx| {
+                    let (ax, ay) = city(idx);
+                    let (bx, by) = city(idx + 1);
+
+                    ((ax - bx).powi(2) + (ay - by).powi(2)).sqrt()
+                })
+                .sum()
+        }
+    }
+
+    impl Individual<usize> for Tour {
+        fn create(chromosome: Chromosome<usize>) -> Self {
+            Self { chromosome }
+        }
+
+        fn fitness(&self) -> f32 {
+            // No tour can be longer than 2 * CITIES
+            (2.0 * CITIES as f32 - self.length()).powi(2)
+        }
+
+        fn chromosome(&self) -> &Chromosome<usize> {
+            &self.chromosome
+        }
+    }
+
+    #[test]
+    fn solves_travelling_salesman_problem() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            OrderCrossover,
+            InversionMutation::new(0.3),
+        );
+
+        let mut population: Vec<_> = (0..30)
+            .map(|_| {
+                let mut genes: Vec<usize> = (0..CITIES).collect();
+                rand::seq::SliceRandom::shuffle(genes.as_mut_slice(), &mut rng);
+                Tour::create(genes.into_iter().collect())
+            })
+            .collect();
+
+        for _ in 0..50 {
+            (population, _) = ga.evolve(&mut rng, &population);
+        }
+
+        let shortest = population
+            .iter()
+            .map(Tour::length)
+            .min_by(f32::total_cmp)
+            .unwrap();
+
+        // Perimeter of a regular octagon inscribed in the unit circle
+        let optimum = 16.0 * (std::f32::consts::PI / 8.0).sin();
+
+        approx::assert_relative_eq!(shortest, optimum, epsilon = 1e-4);
+    }
+}
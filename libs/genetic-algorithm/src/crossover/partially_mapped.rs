@@ -0,0 +1,93 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use rand::RngCore;
+
+use crate::crossover::random_segment;
+use crate::{Chromosome, CrossoverMethod, Gene};
+
+/// Partially mapped crossover (PMX; Goldberg & Lingle, 1985) for
+/// permutations: a random segment is copied from the first parent and the
+/// rest is filled from the second one, with conflicting genes resolved
+/// through the mapping defined by the segment.
+///
+/// Preserves absolute positions of genes, which suits scheduling problems.
+#[derive(Clone, Debug)]
+pub struct PartiallyMappedCrossover;
+
+impl<G> CrossoverMethod<G> for PartiallyMappedCrossover
+where
+    G: Gene + Eq + Hash,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        if parent_a.len() < 2 {
+            return parent_a.clone();
+        }
+
+        let (from, to) = random_segment(rng, parent_a.len());
+        let positions_b: HashMap<&G, usize> = parent_b.iter().zip(0..).collect();
+        let segment: HashSet<&G> = parent_a.genes[from..to].iter().collect();
+
+        let mut genes: Vec<Option<G>> = vec![None; parent_a.len()];
+
+        for idx in from..to {
+            genes[idx] = Some(parent_a[idx].clone());
+        }
+
+        // Genes of the second parent that got displaced by the segment
+        // travel along the mapping until they find a free spot
+        for idx in from..to {
+            let gene = &parent_b[idx];
+
+            if segment.contains(gene) {
+                continue;
+            }
+
+            let mut target = idx;
+
+            while (from..to).contains(&target) {
+                target = positions_b[&parent_a[target]];
+            }
+
+            genes[target] = Some(gene.clone());
+        }
+
+        genes
+            .into_iter()
+            .zip(parent_b.iter())
+            .map(|(gene, fallback)| gene.unwrap_or_else(|| fallback.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn partially_mapped_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome<usize> = (0..10).collect();
+        let parent_b: Chromosome<usize> = (0..10).rev().collect();
+
+        for _ in 0..10 {
+            let child = PartiallyMappedCrossover.crossover(&mut rng, &parent_a, &parent_b);
+
+            let mut genes: Vec<_> = child.iter().copied().collect();
+            genes.sort_unstable();
+            assert_eq!(genes, (0..10).collect::<Vec<_>>());
+        }
+
+        let child = PartiallyMappedCrossover.crossover(&mut rng, &parent_a, &parent_b);
+        assert_eq!(child.genes, vec![9, 8, 2, 3, 4, 5, 6, 7, 1, 0]);
+    }
+}
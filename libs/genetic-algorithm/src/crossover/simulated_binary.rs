@@ -0,0 +1,98 @@
+use rand::{Rng, RngCore};
+
+use crate::{Chromosome, CrossoverMethod, Error};
+
+/// SBX (Deb & Agrawal, 1995): mimics the spread of children that
+/// single-point crossover yields on binary strings, but for real genes.
+///
+/// Of the two children SBX produces, we randomly keep one per gene.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulatedBinaryCrossover {
+    /// Distribution index:
+    /// - small values (e.g. 2.0) = children can land far from parents
+    /// - large values (e.g. 20.0) = children stay close to their parents
+    eta: f32,
+}
+
+impl SimulatedBinaryCrossover {
+    pub fn new(eta: f32) -> Self {
+        Self::try_new(eta).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(eta: f32) -> Result<Self, Error> {
+        Error::check("eta", eta, 0.0.., "0.0..")?;
+
+        Ok(Self { eta })
+    }
+}
+
+impl CrossoverMethod for SimulatedBinaryCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                let u: f32 = rng.gen();
+
+                let beta = if u <= 0.5 {
+                    (2.0 * u).powf(1.0 / (self.eta + 1.0))
+                } else {
+                    (1.0 / (2.0 * (1.0 - u))).powf(1.0 / (self.eta + 1.0))
+                };
+
+                let sign = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+
+                0.5 * ((a + b) + sign * beta * (a - b))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn simulated_binary_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+        let child = SimulatedBinaryCrossover::new(2.0).crossover(&mut rng, &parent_a, &parent_b);
+
+        // Children are centered around parents' mean (zero here)
+        let mean: f32 = child
+            .iter()
+            .zip(parent_a.iter())
+            .map(|(c, p)| c / p)
+            .sum::<f32>()
+            / 100.0;
+        assert_relative_eq!(mean, -0.02947314);
+
+        let first: Vec<_> = child.iter().take(3).copied().collect();
+        assert_relative_eq!(
+            first.as_slice(),
+            [-0.72079206, 2.2140012, 3.0727615].as_slice()
+        );
+    }
+
+    #[test]
+    fn identical_parents() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+        let child = SimulatedBinaryCrossover::new(2.0).crossover(&mut rng, &parent, &parent);
+        let actual: Vec<_> = child.into_iter().collect();
+
+        assert_relative_eq!(actual.as_slice(), [1.0, 2.0, 3.0].as_slice());
+    }
+}
@@ -0,0 +1,70 @@
+use rand::{Rng, RngCore};
+
+use crate::{Chromosome, CrossoverMethod, Gene};
+
+/// Takes genes up to a random point from the first parent and the rest
+/// from the second one.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SinglePointCrossover;
+
+impl<G> CrossoverMethod<G> for SinglePointCrossover
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        if parent_a.len() < 2 {
+            return parent_a.clone();
+        }
+
+        // At least one gene comes from each parent
+        let point = rng.gen_range(1..parent_a.len());
+
+        parent_a
+            .iter()
+            .take(point)
+            .chain(parent_b.iter().skip(point))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn single_point_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+        let child = SinglePointCrossover.crossover(&mut rng, &parent_a, &parent_b);
+
+        let diff_a = child
+            .iter()
+            .zip(parent_a.iter())
+            .filter(|(c, p)| c != p)
+            .count();
+        let diff_b = child
+            .iter()
+            .zip(parent_b.iter())
+            .filter(|(c, p)| c != p)
+            .count();
+
+        assert_eq!(diff_a, 37);
+        assert_eq!(diff_b, 63);
+
+        // Genes from the first parent form a prefix
+        assert!(child.iter().take(100 - diff_a).all(|&gene| gene > 0.0));
+        assert!(child.iter().skip(100 - diff_a).all(|&gene| gene < 0.0));
+    }
+}
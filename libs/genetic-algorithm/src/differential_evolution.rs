@@ -0,0 +1,241 @@
+use rand::seq::index;
+use rand::{Rng, RngCore};
+
+use crate::{Chromosome, Error, Individual, Optimizer, Statistics};
+
+/// How `DifferentialEvolution` builds mutant vectors; every strategy uses
+/// binomial crossover afterwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DifferentialEvolutionStrategy {
+    /// `r1 + F * (r2 - r3)` - explores the most, converges the slowest.
+    Rand1Bin,
+
+    /// `best + F * (r1 - r2)` - converges fast, but can get stuck.
+    Best1Bin,
+
+    /// `current + F * (best - current) + F * (r1 - r2)` - a bit of both.
+    CurrentToBest1Bin,
+}
+
+/// Differential evolution (Storn & Price, 1997).
+///
+/// Each individual of the population we return is a *trial* competing
+/// against one *target* individual kept inside the optimizer - once the
+/// trials have been evaluated and passed back to `evolve()`, every trial
+/// replaces its target if it's at least as fit.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DifferentialEvolution {
+    strategy: DifferentialEvolutionStrategy,
+
+    /// Differential weight, usually within `<0.4, 1.0>`.
+    f: f32,
+
+    /// Crossover probability - how many genes come from the mutant.
+    cr: f32,
+
+    /// Chromosomes and fitnesses of target individuals.
+    targets: Vec<(Chromosome, f32)>,
+}
+
+impl DifferentialEvolution {
+    pub fn new(strategy: DifferentialEvolutionStrategy, f: f32, cr: f32) -> Self {
+        Self::try_new(strategy, f, cr).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(
+        strategy: DifferentialEvolutionStrategy,
+        f: f32,
+        cr: f32,
+    ) -> Result<Self, Error> {
+        Error::check("differential weight", f, 0.0.., "0.0..")?;
+        Error::check("crossover probability", cr, 0.0..=1.0, "0.0..=1.0")?;
+
+        Ok(Self {
+            strategy,
+            f,
+            cr,
+            targets: Vec::new(),
+        })
+    }
+
+    fn select_targets<I>(&mut self, population: &[I])
+    where
+        I: Individual,
+    {
+        // The first generation (or a population of a different size)
+        // simply becomes our targets
+        if self.targets.len() != population.len() {
+            self.targets = population
+                .iter()
+                .map(|trial| (trial.chromosome().clone(), trial.fitness()))
+                .collect();
+
+            return;
+        }
+
+        for (target, trial) in self.targets.iter_mut().zip(population) {
+            if trial.fitness() >= target.1 {
+                *target = (trial.chromosome().clone(), trial.fitness());
+            }
+        }
+    }
+
+    fn trial(&self, rng: &mut dyn RngCore, current: usize, best: usize) -> Chromosome {
+        let target = &self.targets[current].0;
+        let [r1, r2, r3] = self.random_others(rng, current);
+        let x = |idx: usize, gene: usize| self.targets[idx].0[gene];
+
+        // At least one gene always comes from the mutant
+        let forced = rng.gen_range(0..target.len());
+
+        (0..target.len())
+            .map(|j| {
+                if j != forced && !rng.gen_bool(self.cr as f64) {
+                    return target[j];
+                }
+
+                match self.strategy {
+                    DifferentialEvolutionStrategy::Rand1Bin => {
+                        x(r1, j) + self.f * (x(r2, j) - x(r3, j))
+                    }
+
+                    DifferentialEvolutionStrategy::Best1Bin => {
+                        x(best, j) + self.f * (x(r1, j) - x(r2, j))
+                    }
+
+                    DifferentialEvolutionStrategy::CurrentToBest1Bin => {
+                        target[j]
+                            + self.f * (x(best, j) - target[j])
+                            + self.f * (x(r1, j) - x(r2, j))
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Three distinct individuals other than `current` (as long as the
+    /// population is large enough to have them).
+    fn random_others(&self, rng: &mut dyn RngCore, current: usize) -> [usize; 3] {
+        let len = self.targets.len();
+
+        if len < 4 {
+            return [(); 3].map(|_| rng.gen_range(0..len));
+        }
+
+        let picked = index::sample(rng, len - 1, 3);
+        let skip = |idx: usize| if idx >= current { idx + 1 } else { idx };
+
+        [
+            skip(picked.index(0)),
+            skip(picked.index(1)),
+            skip(picked.index(2)),
+        ]
+    }
+}
+
+impl<I> Optimizer<I> for DifferentialEvolution
+where
+    I: Individual,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics) {
+        assert!(!population.is_empty());
+
+        self.select_targets(population);
+
+        let best = (0..self.targets.len())
+            .max_by(|&a, &b| self.targets[a].1.total_cmp(&self.targets[b].1))
+            .unwrap();
+
+        let trials = (0..self.targets.len())
+            .map(|current| I::create(self.trial(rng, current, best)))
+            .collect();
+
+        (trials, Statistics::new(population))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use test_case::test_case;
+
+    /// Negated sphere function, with the optimum at (1, 1, ...)
+    #[derive(Debug)]
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn fitness(&self) -> f32 {
+            -self
+                .chromosome
+                .iter()
+                .map(|g| (g - 1.0).powi(2))
+                .sum::<f32>()
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+    }
+
+    fn run(strategy: DifferentialEvolutionStrategy) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut de = DifferentialEvolution::new(strategy, 0.6, 0.9);
+
+        let mut population: Vec<_> = (0..20)
+            .map(|_| TestIndividual::create((0..5).map(|_| rng.gen_range(-5.0..5.0)).collect()))
+            .collect();
+
+        let mut history = Vec::new();
+
+        for _ in 0..150 {
+            let (new_population, stats) = de.evolve(&mut rng, &population);
+            history.push(stats.max_fitness);
+            population = new_population;
+        }
+
+        history
+    }
+
+    #[test_case(DifferentialEvolutionStrategy::Rand1Bin)]
+    #[test_case(DifferentialEvolutionStrategy::Best1Bin)]
+    #[test_case(DifferentialEvolutionStrategy::CurrentToBest1Bin)]
+    fn converges(strategy: DifferentialEvolutionStrategy) {
+        let history = run(strategy);
+
+        assert!(history.last().unwrap() > &-1e-3);
+    }
+
+    #[test]
+    fn is_reproducible() {
+        let a = run(DifferentialEvolutionStrategy::Rand1Bin);
+        let b = run(DifferentialEvolutionStrategy::Rand1Bin);
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn keeps_better_targets() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut de = DifferentialEvolution::new(DifferentialEvolutionStrategy::Rand1Bin, 0.5, 0.5);
+
+        let individual = |gene: f32| TestIndividual::create(vec![gene].into_iter().collect());
+
+        de.evolve(&mut rng, &[individual(1.0), individual(5.0)]);
+
+        // The first trial is worse than its target, the second one is better
+        de.evolve(&mut rng, &[individual(3.0), individual(2.0)]);
+
+        let targets: Vec<_> = de.targets.iter().map(|(c, _)| c[0]).collect();
+        assert_eq!(targets, vec![1.0, 2.0]);
+    }
+}
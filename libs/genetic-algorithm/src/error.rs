@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// Error returned by fallible (`try_*`) constructors & evolutions.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// There's nobody to evolve.
+    EmptyPopulation,
+
+    /// Fitnesses can't be used as selection weights - e.g. all of them are
+    /// zero, or some are negative.
+    InvalidFitness,
+
+    /// Operator's parameter is out of its range.
+    InvalidParameter {
+        name: &'static str,
+        value: f32,
+
+        /// Human-readable range, e.g. `0.0..=1.0`.
+        expected: &'static str,
+    },
+
+    /// Something comes in a different number than it should - e.g. there
+    /// are more populations than islands.
+    LengthMismatch {
+        what: &'static str,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Error {
+    /// Checks that `value` lies within `range`.
+    pub(crate) fn check<R>(
+        name: &'static str,
+        value: f32,
+        range: R,
+        expected: &'static str,
+    ) -> Result<(), Self>
+    where
+        R: std::ops::RangeBounds<f32>,
+    {
+        if range.contains(&value) {
+            Ok(())
+        } else {
+            Err(Self::InvalidParameter {
+                name,
+                value,
+                expected,
+            })
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyPopulation => write!(f, "population is empty"),
+
+            Self::InvalidFitness => write!(
+                f,
+                "fitnesses can't be used as selection weights (are they all zero?)"
+            ),
+
+            Self::InvalidParameter {
+                name,
+                value,
+                expected,
+            } => write!(f, "{} must be within {}, but got {}", name, expected, value),
+
+            Self::LengthMismatch {
+                what,
+                expected,
+                found,
+            } => write!(f, "expected {} {}, but got {}", expected, what, found),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,345 @@
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use rand::RngCore;
+
+use crate::{Individual, Optimizer, Statistics, TerminationCriterion};
+
+/// Gets notified about every generation evolved by `Evolution` (or
+/// recorded by a `Monitor`) - e.g. to log progress, draw charts or save
+/// checkpoints.
+pub trait Observer {
+    /// `generation` is the number of generations evolved so far (so it's
+    /// 1 for the first one).
+    fn observe(&mut self, generation: usize, stats: &Statistics);
+}
+
+impl<F> Observer for F
+where
+    F: FnMut(usize, &Statistics),
+{
+    fn observe(&mut self, generation: usize, stats: &Statistics) {
+        self(generation, stats)
+    }
+}
+
+/// Handle allowing to stop a running `Evolution` from the outside (e.g.
+/// from another thread or from an observer); cheap to clone.
+#[derive(Clone, Debug, Default)]
+pub struct Cancellation {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Cancellation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes `Evolution` stop before evolving the next generation.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Why `Evolution::run()` has stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    /// Termination criterion of given index (in the order they have been
+    /// added) has been met.
+    Criterion(usize),
+
+    /// Run has been cancelled through `Cancellation`.
+    Cancelled,
+}
+
+/// Result of `Evolution::run()`.
+#[derive(Debug)]
+pub struct Outcome<I> {
+    /// The last evaluated population - i.e. the one `stats` describe and
+    /// the one that has satisfied the termination criterion.
+    pub population: Vec<I>,
+
+    /// Statistics of the last evaluated population; `None` if the run has
+    /// been cancelled before evolving even a single generation.
+    pub stats: Option<Statistics>,
+
+    /// Number of generations evolved during this run.
+    pub generations: usize,
+
+    pub reason: StopReason,
+}
+
+/// Termination criteria, observers and cancellation of a run - i.e. the
+/// part of `Evolution` that doesn't care how the generations get evolved,
+/// so that custom run loops (e.g. ones whose individuals have to be
+/// evaluated by a simulation) can use it as well.
+#[derive(Default)]
+pub struct Monitor {
+    criteria: Vec<Box<dyn TerminationCriterion>>,
+    observers: Vec<Box<dyn Observer>>,
+    cancellation: Cancellation,
+}
+
+impl Monitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_criterion(mut self, criterion: impl TerminationCriterion + 'static) -> Self {
+        self.criteria.push(Box::new(criterion));
+        self
+    }
+
+    pub fn with_observer(mut self, observer: impl Observer + 'static) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    pub fn with_cancellation(mut self, cancellation: Cancellation) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    pub fn cancellation(&self) -> Cancellation {
+        self.cancellation.clone()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Lets the termination criteria know that a run begins (e.g. so that
+    /// `WallClock` starts counting from here); run loops should call this
+    /// right before evolving the first generation.
+    pub fn start(&mut self) {
+        for criterion in &mut self.criteria {
+            criterion.start();
+        }
+    }
+
+    /// Notifies observers about a freshly evolved generation and returns
+    /// why the run should stop (if it should).
+    ///
+    /// `generation` is the number of generations evolved so far during
+    /// this run (so it's 1 for the first one).
+    pub fn record(&mut self, generation: usize, stats: &Statistics) -> Option<StopReason> {
+        for observer in &mut self.observers {
+            observer.observe(generation, stats);
+        }
+
+        // All criteria get to see every generation, so that the stateful
+        // ones stay up to date
+        let mut reason = None;
+
+        for (idx, criterion) in self.criteria.iter_mut().enumerate() {
+            if criterion.should_stop(generation, stats) {
+                reason.get_or_insert(StopReason::Criterion(idx));
+            }
+        }
+
+        if self.is_cancelled() {
+            reason.get_or_insert(StopReason::Cancelled);
+        }
+
+        reason
+    }
+}
+
+/// Run loop driving an `Optimizer` until one of the termination criteria
+/// is met (or until the run gets cancelled).
+///
+/// Individuals are expected to evaluate themselves in `Individual::create()`
+/// - if fitness depends on something else (say, on how well a bird flies
+/// through the world), call `Optimizer::evolve()` directly instead, with a
+/// `Monitor` deciding when to stop.
+pub struct Evolution<O, I, G = f32> {
+    optimizer: O,
+    monitor: Monitor,
+    _individual: PhantomData<fn() -> (I, G)>,
+}
+
+impl<O, I, G> Evolution<O, I, G>
+where
+    O: Optimizer<I, G>,
+    I: Individual<G>,
+{
+    pub fn new(optimizer: O) -> Self {
+        Self {
+            optimizer,
+            monitor: Monitor::new(),
+            _individual: PhantomData,
+        }
+    }
+
+    pub fn with_criterion(mut self, criterion: impl TerminationCriterion + 'static) -> Self {
+        self.monitor = self.monitor.with_criterion(criterion);
+        self
+    }
+
+    pub fn with_observer(mut self, observer: impl Observer + 'static) -> Self {
+        self.monitor = self.monitor.with_observer(observer);
+        self
+    }
+
+    pub fn with_cancellation(mut self, cancellation: Cancellation) -> Self {
+        self.monitor = self.monitor.with_cancellation(cancellation);
+        self
+    }
+
+    pub fn cancellation(&self) -> Cancellation {
+        self.monitor.cancellation()
+    }
+
+    pub fn optimizer(&self) -> &O {
+        &self.optimizer
+    }
+
+    pub fn into_optimizer(self) -> O {
+        self.optimizer
+    }
+
+    /// Evolves `population` until one of the termination criteria is met.
+    ///
+    /// Without any criteria, this runs until cancelled.
+    pub fn run(&mut self, rng: &mut dyn RngCore, mut population: Vec<I>) -> Outcome<I> {
+        if self.monitor.is_cancelled() {
+            return Outcome {
+                population,
+                stats: None,
+                generations: 0,
+                reason: StopReason::Cancelled,
+            };
+        }
+
+        self.monitor.start();
+
+        let mut generation = 0;
+
+        loop {
+            let (new_population, stats) = self.optimizer.evolve(rng, &population);
+            generation += 1;
+
+            if let Some(reason) = self.monitor.record(generation, &stats) {
+                return Outcome {
+                    population,
+                    stats: Some(stats),
+                    generations: generation,
+                    reason,
+                };
+            }
+
+            population = new_population;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::{Candidate, Sphere};
+    use crate::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn population(rng: &mut dyn RngCore) -> Vec<Candidate<Sphere>> {
+        (0..30)
+            .map(|_| Candidate::create((0..3).map(|_| rng.gen_range(-2.0..2.0)).collect()))
+            .collect()
+    }
+
+    fn evolution() -> Evolution<CmaEs, Candidate<Sphere>> {
+        Evolution::new(CmaEs::new(0.5))
+    }
+
+    #[test]
+    fn stops_at_the_first_met_criterion() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+
+        let outcome = evolution()
+            .with_criterion(MaxGenerations::new(100))
+            .with_criterion(TargetFitness::new(0.9999))
+            .run(&mut rng, population);
+
+        assert_eq!(outcome.reason, StopReason::Criterion(1));
+        assert_eq!(outcome.generations, 10);
+        assert!(outcome.stats.unwrap().max_fitness >= 0.9999);
+
+        // The returned population is the one that has reached the target
+        assert!(outcome
+            .population
+            .iter()
+            .any(|candidate| candidate.fitness() >= 0.9999));
+    }
+
+    #[test]
+    fn notifies_observers() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+
+        let outcome = evolution()
+            .with_criterion(MaxGenerations::new(5))
+            .with_observer({
+                let seen = Rc::clone(&seen);
+                move |generation, stats: &Statistics| {
+                    seen.borrow_mut().push((generation, stats.max_fitness))
+                }
+            })
+            .run(&mut rng, population);
+
+        let seen = seen.borrow();
+
+        assert_eq!(outcome.generations, 5);
+        assert_eq!(
+            seen.iter()
+                .map(|(generation, _)| *generation)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(seen[4].1, outcome.stats.unwrap().max_fitness);
+    }
+
+    #[test]
+    fn can_be_cancelled() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+        let cancellation = Cancellation::new();
+
+        // No criteria - only cancellation can stop this run
+        let outcome = evolution()
+            .with_cancellation(cancellation.clone())
+            .with_observer(move |generation, _: &Statistics| {
+                if generation == 3 {
+                    cancellation.cancel();
+                }
+            })
+            .run(&mut rng, population);
+
+        assert_eq!(outcome.reason, StopReason::Cancelled);
+        assert_eq!(outcome.generations, 3);
+    }
+
+    #[test]
+    fn cancelled_before_start() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+        let mut evolution = evolution();
+
+        evolution.cancellation().cancel();
+
+        let outcome = evolution.run(&mut rng, population);
+
+        assert_eq!(outcome.reason, StopReason::Cancelled);
+        assert_eq!(outcome.generations, 0);
+        assert!(outcome.stats.is_none());
+        assert_eq!(outcome.population.len(), 30);
+    }
+}
@@ -0,0 +1,40 @@
+use std::fmt::Debug;
+
+/// Anything a `Chromosome` can be made of.
+///
+/// Besides the real-valued genes that neural networks use, the crate
+/// supports binary (`bool`), integer (`i32`) and permutation (`usize`)
+/// genes, plus `MixedGene` for chromosomes built out of typed segments.
+pub trait Gene: Clone + Debug + PartialEq {
+    /// Numeric view of the gene, used to measure distances between
+    /// chromosomes (and thus population's diversity).
+    fn value(&self) -> f32;
+}
+
+impl Gene for f32 {
+    fn value(&self) -> f32 {
+        *self
+    }
+}
+
+impl Gene for i32 {
+    fn value(&self) -> f32 {
+        *self as f32
+    }
+}
+
+impl Gene for bool {
+    fn value(&self) -> f32 {
+        if *self {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Gene for usize {
+    fn value(&self) -> f32 {
+        *self as f32
+    }
+}
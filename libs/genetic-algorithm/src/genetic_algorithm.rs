@@ -1,8 +1,14 @@
+use std::ptr;
+
 use rand::RngCore;
 
-use crate::{CrossoverMethod, Individual, MutationMethod, SelectionMethod, Statistics};
+use crate::{
+    Birth, Chromosome, CrossoverMethod, Error, Gene, Individual, MutationMethod, Optimizer,
+    SelectionMethod, Statistics,
+};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GeneticAlgorithm<S, C, M> {
     selection_method: S,
     crossover_method: C,
@@ -12,8 +18,6 @@ pub struct GeneticAlgorithm<S, C, M> {
 impl<S, C, M> GeneticAlgorithm<S, C, M>
 where
     S: SelectionMethod,
-    C: CrossoverMethod,
-    M: MutationMethod,
 {
     pub fn new(selection_method: S, crossover_method: C, mutation_method: M) -> Self {
         Self {
@@ -23,28 +27,218 @@ where
         }
     }
 
-    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+    pub fn mutation_method(&self) -> &M {
+        &self.mutation_method
+    }
+
+    pub fn crossover_method(&self) -> &C {
+        &self.crossover_method
+    }
+
+    pub fn evolve<I, G>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
     where
-        I: Individual,
+        I: Individual<G>,
+        G: Gene,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
     {
-        assert!(!population.is_empty());
+        self.try_evolve(rng, population)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Same as `evolve()`, but returns an error instead of panicking - e.g.
+    /// when the population is empty.
+    pub fn try_evolve<I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Statistics), Error>
+    where
+        I: Individual<G>,
+        G: Gene,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+    {
+        if population.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
 
         let new_population = (0..population.len())
             .map(|_| {
-                let parent_a = self.selection_method.select(rng, population);
-                let parent_b = self.selection_method.select(rng, population);
+                let parent_a = self.selection_method.try_select::<I, G>(rng, population)?;
+                let parent_b = self.selection_method.try_select::<I, G>(rng, population)?;
+
+                let child = breed(
+                    rng,
+                    &self.crossover_method,
+                    &self.mutation_method,
+                    parent_a.chromosome(),
+                    parent_b.chromosome(),
+                );
+
+                Ok(I::create(child))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok((new_population, Statistics::new(population)))
+    }
+
+    /// Same as `evolve()`, but also tells who are the parents of each new
+    /// individual and what has happened to its genes - which costs an
+    /// extra copy of every child, so prefer `evolve()` when you don't need
+    /// that.
+    pub fn evolve_traced<I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, Vec<Birth>, Statistics)
+    where
+        I: Individual<G>,
+        G: Gene,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+    {
+        self.try_evolve_traced(rng, population)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Same as `evolve_traced()`, but returns an error instead of
+    /// panicking.
+    pub fn try_evolve_traced<I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Vec<Birth>, Statistics), Error>
+    where
+        I: Individual<G>,
+        G: Gene,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+    {
+        if population.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
+
+        let (new_population, births) = (0..population.len())
+            .map(|_| {
+                let parent_a = self.selection_method.try_select::<I, G>(rng, population)?;
+                let parent_b = self.selection_method.try_select::<I, G>(rng, population)?;
+
                 let mut child = self.crossover_method.crossover(
                     rng,
                     parent_a.chromosome(),
                     parent_b.chromosome(),
                 );
+
+                child.inherit_sigma(parent_a.chromosome(), parent_b.chromosome());
+
+                let crossed = child.clone();
                 self.mutation_method.mutate(rng, &mut child);
-                I::create(child)
+
+                let birth = Birth::bred(
+                    [
+                        index_of(population, parent_a),
+                        index_of(population, parent_b),
+                    ],
+                    parent_a.chromosome(),
+                    parent_b.chromosome(),
+                    &crossed,
+                    &child,
+                );
+
+                Ok((I::create(child), birth))
             })
-            .collect();
+            .collect::<Result<Vec<_>, Error>>()?
+            .into_iter()
+            .unzip();
 
-            (new_population, Statistics::new(population))
+        Ok((new_population, births, Statistics::new(population)))
+    }
 
+    /// Lets the crossover & mutation methods adapt to the statistics of
+    /// the last generation (see `CrossoverMethod::adapt()`).
+    pub fn adapt<G>(&mut self, stats: &Statistics)
+    where
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+    {
+        self.crossover_method.adapt(stats);
+        self.mutation_method.adapt(stats);
+    }
+}
+
+/// Crossover followed by mutation - the part of reproduction shared by all
+/// our genetic algorithms.
+pub(crate) fn breed<G, C, M>(
+    rng: &mut dyn RngCore,
+    crossover_method: &C,
+    mutation_method: &M,
+    parent_a: &Chromosome<G>,
+    parent_b: &Chromosome<G>,
+) -> Chromosome<G>
+where
+    C: CrossoverMethod<G> + ?Sized,
+    M: MutationMethod<G> + ?Sized,
+{
+    let mut child = crossover_method.crossover(rng, parent_a, parent_b);
+    child.inherit_sigma(parent_a, parent_b);
+    mutation_method.mutate(rng, &mut child);
+    child
+}
+
+/// Finds `individual` (as returned by a `SelectionMethod`) within
+/// `population`.
+fn index_of<T>(population: &[T], individual: &T) -> usize {
+    population
+        .iter()
+        .position(|candidate| ptr::eq(candidate, individual))
+        .expect("selected individual doesn't come from the population")
+}
+
+impl<S, C, M, I, G> Optimizer<I, G> for GeneticAlgorithm<S, C, M>
+where
+    S: SelectionMethod,
+    C: CrossoverMethod<G>,
+    M: MutationMethod<G>,
+    I: Individual<G>,
+    G: Gene,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics) {
+        let (new_population, stats) = GeneticAlgorithm::evolve(self, rng, population);
+        self.adapt::<G>(&stats);
+        (new_population, stats)
+    }
+
+    fn evolve_traced(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, Vec<Birth>, Statistics) {
+        let (new_population, births, stats) =
+            GeneticAlgorithm::evolve_traced(self, rng, population);
+        self.adapt::<G>(&stats);
+        (new_population, births, stats)
+    }
+
+    fn try_evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Statistics), Error> {
+        let (new_population, stats) = GeneticAlgorithm::try_evolve(self, rng, population)?;
+        self.adapt::<G>(&stats);
+        Ok((new_population, stats))
+    }
+
+    fn try_evolve_traced(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Vec<Birth>, Statistics), Error> {
+        let (new_population, births, stats) =
+            GeneticAlgorithm::try_evolve_traced(self, rng, population)?;
+        self.adapt::<G>(&stats);
+        Ok((new_population, births, stats))
     }
 }
 
@@ -52,7 +246,8 @@ where
 mod tests {
     use super::*;
     use crate::{
-        crossover::UniformCrossover, mutation::GaussianMutation, Chromosome, RouletteWheelSelection,
+        crossover::UniformCrossover, mutation::UniformPerturbationMutation, Chromosome,
+        RouletteWheelSelection,
     };
     use rand::SeedableRng;
     use rand_chacha::ChaCha8Rng;
@@ -112,7 +307,7 @@ mod tests {
         let ga = GeneticAlgorithm::new(
             RouletteWheelSelection,
             UniformCrossover,
-            GaussianMutation::new(0.5, 0.5),
+            UniformPerturbationMutation::new(0.5, 0.5),
         );
 
         let mut population = vec![
@@ -129,16 +324,105 @@ mod tests {
         // 1000 поколений: единственное, что будет меняться - магнитуда
         // разницы между популяциями.
         for _ in 0..10 {
-            population = ga.evolve(&mut rng, &population);
+            (population, _) = ga.evolve(&mut rng, &population);
         }
 
         let expected_population = vec![
-            individual(&[0.44769490, 2.0648358, 4.3058133]),
-            individual(&[1.21268670, 1.5538777, 2.8869110]),
-            individual(&[1.06176780, 2.2657390, 4.4287640]),
-            individual(&[0.95909685, 2.4618788, 4.0247330]),
+            individual(&[0.4476949, 2.0648358, 4.3058133]),
+            individual(&[1.2126867, 1.5538777, 2.886911]),
+            individual(&[1.0617678, 2.265739, 4.428764]),
+            individual(&[0.95909685, 2.4618788, 4.024733]),
         ];
 
         assert_eq!(population, expected_population);
     }
+
+    #[test]
+    fn evolve_traced() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            UniformPerturbationMutation::new(0.3, 0.5),
+        );
+
+        // Every gene is unique, so it's easy to tell where it comes from
+        let population: Vec<_> = (0..4)
+            .map(|idx| {
+                TestIndividual::create((0..5).map(|gene| (10 * idx + gene + 1) as f32).collect())
+            })
+            .collect();
+
+        let (children, births, _) = ga.evolve_traced(&mut rng, &population);
+
+        assert_eq!(births.len(), children.len());
+
+        for (child, birth) in children.iter().zip(&births) {
+            let [a, b] = birth.parents.unwrap();
+            let parent_a = population[a].chromosome();
+            let parent_b = population[b].chromosome();
+
+            for (idx, gene) in child.chromosome().iter().enumerate() {
+                if birth.mutated.contains(&idx) {
+                    assert_ne!(*gene, parent_a[idx]);
+                    assert_ne!(*gene, parent_b[idx]);
+                } else {
+                    assert!(*gene == parent_a[idx] || *gene == parent_b[idx]);
+                }
+            }
+
+            assert_eq!(
+                birth.inherited[0] + birth.inherited[1] + birth.mutated.len(),
+                5
+            );
+        }
+
+        // Tracing doesn't change the outcome
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        assert_eq!(ga.evolve(&mut rng, &population).0, children);
+    }
+
+    #[test]
+    fn errors() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            UniformPerturbationMutation::new(0.5, 0.5),
+        );
+
+        let empty: Vec<TestIndividual> = Vec::new();
+
+        let starving: Vec<_> = (0..4)
+            .map(|_| TestIndividual::create(vec![0.0, 0.0].into_iter().collect()))
+            .collect();
+
+        assert_eq!(
+            ga.try_evolve(&mut rng, &empty).err(),
+            Some(Error::EmptyPopulation)
+        );
+
+        assert_eq!(
+            Optimizer::try_evolve_traced(&mut ga, &mut rng, &starving).err(),
+            Some(Error::InvalidFitness)
+        );
+
+        assert_eq!(
+            UniformPerturbationMutation::try_new(1.5, 0.5).err(),
+            Some(Error::InvalidParameter {
+                name: "mutation chance",
+                value: 1.5,
+                expected: "0.0..=1.0",
+            })
+        );
+
+        assert_eq!(
+            UniformPerturbationMutation::try_new(1.5, 0.5)
+                .unwrap_err()
+                .to_string(),
+            "mutation chance must be within 0.0..=1.0, but got 1.5"
+        );
+    }
 }
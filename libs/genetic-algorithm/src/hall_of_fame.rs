@@ -0,0 +1,190 @@
+use crate::{Chromosome, Gene, Individual};
+
+/// Individual that has made it into a `HallOfFame`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HallOfFameEntry<G = f32> {
+    pub chromosome: Chromosome<G>,
+    pub fitness: f32,
+
+    /// Generation the individual has been evaluated in.
+    pub generation: usize,
+}
+
+/// Bounded archive of the best individuals ever seen, sorted from the
+/// fittest one.
+///
+/// Populations get replaced wholesale every generation, so without such
+/// archive the best solution found might be lost right after it has been
+/// found.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HallOfFame<G = f32> {
+    capacity: usize,
+    entries: Vec<HallOfFameEntry<G>>,
+}
+
+impl<G> HallOfFame<G>
+where
+    G: Gene,
+{
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0);
+
+        Self {
+            capacity,
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Offers individuals of an evaluated population to the archive.
+    ///
+    /// Individuals already present in the archive (with identical genes)
+    /// are skipped, so that a single solution cannot take over all the
+    /// places; on ties, individuals seen earlier stay ahead.
+    pub fn update<I>(&mut self, population: &[I], generation: usize)
+    where
+        I: Individual<G>,
+    {
+        for individual in population {
+            let fitness = individual.fitness();
+
+            if self.entries.len() == self.capacity
+                && self
+                    .entries
+                    .last()
+                    .map_or(false, |worst| fitness <= worst.fitness)
+            {
+                continue;
+            }
+
+            if self
+                .entries
+                .iter()
+                .any(|entry| entry.chromosome.genes == individual.chromosome().genes)
+            {
+                continue;
+            }
+
+            let idx = self
+                .entries
+                .partition_point(|entry| entry.fitness >= fitness);
+
+            self.entries.insert(
+                idx,
+                HallOfFameEntry {
+                    chromosome: individual.chromosome().clone(),
+                    fitness,
+                    generation,
+                },
+            );
+
+            self.entries.truncate(self.capacity);
+        }
+    }
+
+    pub fn best(&self) -> Option<&HallOfFameEntry<G>> {
+        self.entries.first()
+    }
+
+    pub fn entries(&self) -> &[HallOfFameEntry<G>] {
+        &self.entries
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn fitness(&self) -> f32 {
+            self.chromosome.iter().sum()
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+    }
+
+    fn population(fitnesses: &[f32]) -> Vec<TestIndividual> {
+        fitnesses
+            .iter()
+            .map(|&fitness| TestIndividual::create(vec![fitness].into_iter().collect()))
+            .collect()
+    }
+
+    fn summary(hof: &HallOfFame) -> Vec<(f32, usize)> {
+        hof.entries()
+            .iter()
+            .map(|entry| (entry.fitness, entry.generation))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_the_best_individuals_ever_seen() {
+        let mut hof = HallOfFame::new(3);
+
+        hof.update(&population(&[1.0, 5.0, 3.0, 2.0]), 0);
+        assert_eq!(summary(&hof), vec![(5.0, 0), (3.0, 0), (2.0, 0)]);
+
+        hof.update(&population(&[4.0, 1.0, 0.5]), 1);
+        assert_eq!(summary(&hof), vec![(5.0, 0), (4.0, 1), (3.0, 0)]);
+
+        // A worse generation doesn't change anything
+        hof.update(&population(&[0.1, 0.2]), 2);
+        assert_eq!(summary(&hof), vec![(5.0, 0), (4.0, 1), (3.0, 0)]);
+
+        assert_eq!(hof.best().unwrap().chromosome.genes, vec![5.0]);
+    }
+
+    #[test]
+    fn skips_duplicates() {
+        let mut hof = HallOfFame::new(3);
+
+        hof.update(&population(&[2.0, 2.0]), 0);
+        hof.update(&population(&[2.0, 1.0]), 1);
+
+        assert_eq!(summary(&hof), vec![(2.0, 0), (1.0, 1)]);
+    }
+
+    #[test]
+    fn keeps_older_individuals_on_ties() {
+        let mut hof = HallOfFame::new(2);
+
+        hof.update(&population(&[1.0]), 0);
+
+        // Same fitness, different genes
+        hof.update(
+            &[TestIndividual::create(vec![0.5, 0.5].into_iter().collect())],
+            1,
+        );
+        hof.update(
+            &[TestIndividual::create(
+                vec![0.25, 0.75].into_iter().collect(),
+            )],
+            2,
+        );
+
+        assert_eq!(summary(&hof), vec![(1.0, 0), (1.0, 1)]);
+    }
+}
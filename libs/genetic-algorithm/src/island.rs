@@ -0,0 +1,343 @@
+use rand::RngCore;
+
+use crate::{Error, Individual, Optimizer, Statistics};
+
+/// Which islands exchange migrants with each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Topology {
+    /// Island `n` sends its migrants to island `n + 1` (the last one sends
+    /// them back to the first one).
+    Ring,
+
+    /// Every island sends its migrants to every other island.
+    FullyConnected,
+}
+
+#[derive(Clone, Debug)]
+pub struct MigrationPolicy {
+    topology: Topology,
+
+    /// Migration happens every `interval` generations.
+    interval: usize,
+
+    /// How many of the best individuals leave each island per migration.
+    migrants: usize,
+}
+
+impl MigrationPolicy {
+    pub fn new(topology: Topology, interval: usize, migrants: usize) -> Self {
+        Self::try_new(topology, interval, migrants).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(topology: Topology, interval: usize, migrants: usize) -> Result<Self, Error> {
+        Error::check("migration interval", interval as f32, 1.0.., "1..")?;
+
+        Ok(Self {
+            topology,
+            interval,
+            migrants,
+        })
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn interval(&self) -> usize {
+        self.interval
+    }
+
+    pub fn migrants(&self) -> usize {
+        self.migrants
+    }
+}
+
+/// Several subpopulations evolving independently, each one with its own
+/// optimizer, occasionally exchanging their best individuals.
+///
+/// Keeping populations apart slows down the convergence - each island
+/// tends to explore a different part of the search space, while
+/// migration still lets good solutions spread.
+pub struct IslandModel<I> {
+    islands: Vec<Box<dyn Optimizer<I>>>,
+    policy: MigrationPolicy,
+    generation: usize,
+}
+
+impl<I> IslandModel<I>
+where
+    I: Individual,
+{
+    pub fn new(islands: Vec<Box<dyn Optimizer<I>>>, policy: MigrationPolicy) -> Self {
+        Self::try_new(islands, policy).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(
+        islands: Vec<Box<dyn Optimizer<I>>>,
+        policy: MigrationPolicy,
+    ) -> Result<Self, Error> {
+        Error::check("number of islands", islands.len() as f32, 1.0.., "1..")?;
+
+        Ok(Self {
+            islands,
+            policy,
+            generation: 0,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.islands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.islands.is_empty()
+    }
+
+    /// Evolves every island's population, returning the new populations
+    /// together with per-island statistics (both in the order of islands).
+    pub fn evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        populations: &[Vec<I>],
+    ) -> (Vec<Vec<I>>, Vec<Statistics>) {
+        self.try_evolve(rng, populations)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Same as `evolve()`, but returns an error instead of panicking - e.g.
+    /// when there are more populations than islands, or when one of the
+    /// islands fails to evolve.
+    pub fn try_evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        populations: &[Vec<I>],
+    ) -> Result<(Vec<Vec<I>>, Vec<Statistics>), Error> {
+        if populations.len() != self.islands.len() {
+            return Err(Error::LengthMismatch {
+                what: "populations",
+                expected: self.islands.len(),
+                found: populations.len(),
+            });
+        }
+
+        let (mut new_populations, stats): (Vec<_>, Vec<_>) = self
+            .islands
+            .iter_mut()
+            .zip(populations)
+            .map(|(island, population)| island.try_evolve(rng, population))
+            .collect::<Result<Vec<_>, Error>>()?
+            .into_iter()
+            .unzip();
+
+        self.generation += 1;
+
+        if self.generation % self.policy.interval == 0 {
+            self.migrate(populations, &mut new_populations);
+        }
+
+        Ok((new_populations, stats))
+    }
+
+    /// Copies the best individuals of each (already evaluated) population
+    /// into the freshly bred populations of the neighbouring islands,
+    /// replacing their last children.
+    fn migrate(&self, populations: &[Vec<I>], new_populations: &mut [Vec<I>]) {
+        let islands = populations.len();
+
+        if islands < 2 || self.policy.migrants == 0 {
+            return;
+        }
+
+        let mut replaced = vec![0; islands];
+
+        for (source, population) in populations.iter().enumerate() {
+            let targets: Vec<_> = match self.policy.topology {
+                Topology::Ring => vec![(source + 1) % islands],
+                Topology::FullyConnected => (0..islands).filter(|&t| t != source).collect(),
+            };
+
+            for migrant in best(population, self.policy.migrants) {
+                for &target in &targets {
+                    let target_population = &mut new_populations[target];
+
+                    if replaced[target] >= target_population.len() {
+                        continue;
+                    }
+
+                    replaced[target] += 1;
+
+                    let idx = target_population.len() - replaced[target];
+                    target_population[idx] = I::create(migrant.chromosome().clone());
+                }
+            }
+        }
+    }
+}
+
+fn best<I>(population: &[I], count: usize) -> impl Iterator<Item = &I>
+where
+    I: Individual,
+{
+    let mut ranked: Vec<_> = population.iter().collect();
+    ranked.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+    ranked.into_iter().take(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Chromosome, GeneticAlgorithm, RouletteWheelSelection, UniformCrossover,
+        UniformPerturbationMutation,
+    };
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[derive(Clone, Debug)]
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn fitness(&self) -> f32 {
+            self.chromosome.iter().sum()
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+    }
+
+    /// Optimizer that doesn't change anything, so that the only thing
+    /// happening to populations is migration.
+    struct Identity;
+
+    impl Optimizer<TestIndividual> for Identity {
+        fn evolve(
+            &mut self,
+            _: &mut dyn RngCore,
+            population: &[TestIndividual],
+        ) -> (Vec<TestIndividual>, Statistics) {
+            (population.to_vec(), Statistics::new(population))
+        }
+    }
+
+    fn population(genes: &[f32]) -> Vec<TestIndividual> {
+        genes
+            .iter()
+            .map(|&gene| TestIndividual::create(vec![gene].into_iter().collect()))
+            .collect()
+    }
+
+    fn genes(population: &[TestIndividual]) -> Vec<f32> {
+        population.iter().map(|i| i.chromosome[0]).collect()
+    }
+
+    fn identity_model(islands: usize, policy: MigrationPolicy) -> IslandModel<TestIndividual> {
+        IslandModel::new(
+            (0..islands)
+                .map(|_| Box::new(Identity) as Box<dyn Optimizer<_>>)
+                .collect(),
+            policy,
+        )
+    }
+
+    #[test]
+    fn ring_migration() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut model = identity_model(3, MigrationPolicy::new(Topology::Ring, 2, 1));
+
+        let populations = vec![
+            population(&[1.0, 2.0, 3.0]),
+            population(&[4.0, 5.0, 6.0]),
+            population(&[7.0, 8.0, 9.0]),
+        ];
+
+        // First generation - no migration yet
+        let (populations, stats) = model.evolve(&mut rng, &populations);
+        assert_eq!(stats.len(), 3);
+        assert_eq!(genes(&populations[0]), vec![1.0, 2.0, 3.0]);
+
+        // Second generation - the best of each island replaces the last
+        // individual of its neighbour
+        let (populations, _) = model.evolve(&mut rng, &populations);
+        assert_eq!(genes(&populations[0]), vec![1.0, 2.0, 9.0]);
+        assert_eq!(genes(&populations[1]), vec![4.0, 5.0, 3.0]);
+        assert_eq!(genes(&populations[2]), vec![7.0, 8.0, 6.0]);
+    }
+
+    #[test]
+    fn fully_connected_migration() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut model = identity_model(3, MigrationPolicy::new(Topology::FullyConnected, 1, 1));
+
+        let populations = vec![
+            population(&[1.0, 2.0, 3.0]),
+            population(&[4.0, 5.0, 6.0]),
+            population(&[7.0, 8.0, 9.0]),
+        ];
+
+        let (populations, _) = model.evolve(&mut rng, &populations);
+        assert_eq!(genes(&populations[0]), vec![1.0, 9.0, 6.0]);
+        assert_eq!(genes(&populations[1]), vec![4.0, 9.0, 3.0]);
+        assert_eq!(genes(&populations[2]), vec![7.0, 6.0, 3.0]);
+    }
+
+    #[test]
+    fn islands_with_different_settings() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut model = IslandModel::new(
+            vec![
+                Box::new(GeneticAlgorithm::new(
+                    RouletteWheelSelection,
+                    UniformCrossover,
+                    UniformPerturbationMutation::new(0.5, 0.5),
+                )),
+                Box::new(GeneticAlgorithm::new(
+                    RouletteWheelSelection,
+                    UniformCrossover,
+                    UniformPerturbationMutation::new(0.1, 2.0),
+                )),
+            ],
+            MigrationPolicy::new(Topology::Ring, 5, 1),
+        );
+
+        let mut populations = vec![population(&[1.0, 2.0, 3.0, 4.0]); 2];
+
+        for _ in 0..10 {
+            let (new_populations, stats) = model.evolve(&mut rng, &populations);
+            assert_eq!(stats.len(), 2);
+            populations = new_populations;
+        }
+
+        assert!(populations.iter().all(|population| population.len() == 4));
+    }
+
+    #[test]
+    fn mismatched_populations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut model = identity_model(3, MigrationPolicy::new(Topology::Ring, 1, 1));
+
+        let err = model
+            .try_evolve(&mut rng, &[population(&[1.0]), population(&[2.0])])
+            .map(drop)
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "expected 3 populations, but got 2");
+    }
+
+    #[test]
+    fn invalid_policy() {
+        assert_eq!(
+            MigrationPolicy::try_new(Topology::Ring, 0, 1)
+                .unwrap_err()
+                .to_string(),
+            "migration interval must be within 1.., but got 0"
+        );
+    }
+}
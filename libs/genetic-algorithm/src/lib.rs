@@ -1,40 +1,251 @@
+//! Every constructor that validates its parameters comes in two flavours:
+//! `new()`, which panics when they are out of range, and `try_new()`,
+//! which returns an `Error` instead; the same goes for `evolve()` and
+//! `try_evolve()`.
+//!
+//! Operators themselves (e.g. `CrossoverMethod::crossover()`) still panic
+//! when given chromosomes that can't come from the same population - say,
+//! of different lengths - since that's a bug, not a bad setting.
+
 use rand::RngCore;
 
+pub mod benchmark;
+mod birth;
 mod chromosome;
+mod cma_es;
 mod crossover;
+mod differential_evolution;
+mod error;
+mod evolution;
+mod gene;
 mod genetic_algorithm;
+mod hall_of_fame;
+mod island;
+mod mixed;
 mod mutation;
+mod nsga2;
+mod operator_selection;
+mod particle_swarm;
 mod selection;
+mod speciation;
 mod statistics;
+mod termination;
 
+pub use birth::Birth;
 pub use chromosome::Chromosome;
-pub use crossover::UniformCrossover;
+pub use cma_es::CmaEs;
+pub use crossover::{
+    AdaptiveCrossover, ArithmeticCrossover, BlendCrossover, CycleCrossover, KPointCrossover,
+    NeuronCrossover, OrderCrossover, PartiallyMappedCrossover, SimulatedBinaryCrossover,
+    SinglePointCrossover, UniformCrossover,
+};
+pub use differential_evolution::{DifferentialEvolution, DifferentialEvolutionStrategy};
+pub use error::Error;
+pub use evolution::{Cancellation, Evolution, Monitor, Observer, Outcome, StopReason};
+pub use gene::Gene;
 pub use genetic_algorithm::GeneticAlgorithm;
+pub use hall_of_fame::{HallOfFame, HallOfFameEntry};
+pub use island::{IslandModel, MigrationPolicy, Topology};
+pub use mixed::{MixedGene, SegmentGene, SegmentedCrossover, SegmentedMutation, Typed};
+#[allow(deprecated)]
 pub use mutation::GaussianMutation;
-pub use selection::RouletteWheelSelection;
+pub use mutation::{
+    AdaptiveMutation, BitFlipMutation, CauchyMutation, IntegerMutation, InversionMutation,
+    MutationSchedule, NormalMutation, PolynomialMutation, RandomResetMutation, ScheduledMutation,
+    SelfAdaptiveMutation, SwapMutation, UniformPerturbationMutation,
+};
+pub use nsga2::{crowding_distance, dominates, fast_non_dominated_sort, Nsga2};
+pub use particle_swarm::ParticleSwarm;
+pub use selection::{RouletteWheelSelection, TournamentSelection};
+pub use speciation::{FitnessSharing, SpeciatedGeneticAlgorithm};
 pub use statistics::Statistics;
+pub use termination::{
+    DiversityCollapse, MaxGenerations, Stagnation, TargetFitness, TerminationCriterion, WallClock,
+};
 
-pub trait Individual {
-    fn create(chromosome: Chromosome) -> Self;
+pub trait Individual<G = f32> {
+    fn create(chromosome: Chromosome<G>) -> Self;
     fn fitness(&self) -> f32;
-    fn chromosome(&self) -> &Chromosome;
+    fn chromosome(&self) -> &Chromosome<G>;
+}
+
+/// Individual judged by several competing objectives at once.
+///
+/// Scalar `fitness` is still used to summarise the population in
+/// `Statistics`; selection is driven solely by `objectives`.
+pub trait MultiObjectiveIndividual: Individual {
+    fn objectives(&self) -> Vec<f32>;
 }
 
 pub trait SelectionMethod {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
     where
-        I: Individual;
+        I: Individual<G>;
+
+    /// Same as `select()`, but returns an error instead of panicking; by
+    /// default, checks only whether the population is empty.
+    fn try_select<'a, I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+    ) -> Result<&'a I, Error>
+    where
+        I: Individual<G>,
+    {
+        if population.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
+
+        Ok(self.select(rng, population))
+    }
 }
 
-pub trait CrossoverMethod {
+pub trait CrossoverMethod<G = f32> {
     fn crossover(
         &self,
         rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-    ) -> Chromosome;
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G>;
+
+    /// Called once per generation with statistics of the population that
+    /// has just been evolved, so that adaptive operators can tune
+    /// themselves; does nothing by default.
+    fn adapt(&mut self, _stats: &Statistics) {}
+}
+
+impl<G, C> CrossoverMethod<G> for Box<C>
+where
+    C: CrossoverMethod<G> + ?Sized,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        (**self).crossover(rng, parent_a, parent_b)
+    }
+
+    fn adapt(&mut self, stats: &Statistics) {
+        (**self).adapt(stats)
+    }
+}
+
+pub trait MutationMethod<G = f32> {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>);
+
+    /// See `CrossoverMethod::adapt()`.
+    fn adapt(&mut self, _stats: &Statistics) {}
+}
+
+impl<G, M> MutationMethod<G> for Box<M>
+where
+    M: MutationMethod<G> + ?Sized,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        (**self).mutate(rng, child)
+    }
+
+    fn adapt(&mut self, stats: &Statistics) {
+        (**self).adapt(stats)
+    }
 }
 
-pub trait MutationMethod {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
+/// Mutation whose rate and strength can be tuned while evolution is
+/// running (see `ScheduledMutation`).
+pub trait TunableMutation: MutationMethod {
+    /// Returns the chance of changing a gene and the magnitude of that
+    /// change.
+    fn parameters(&self) -> (f32, f32);
+
+    /// Returns mutable references to the chance of changing a gene and to
+    /// the magnitude of that change.
+    fn parameters_mut(&mut self) -> (&mut f32, &mut f32);
+}
+
+/// Anything able to turn an evaluated population into the next one.
+///
+/// Unlike the inherent `GeneticAlgorithm::evolve()`, this takes `&mut self`
+/// so that optimizers can carry state between generations.
+pub trait Optimizer<I, G = f32>
+where
+    I: Individual<G>,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics);
+
+    /// Same as `evolve()`, but also tells how each new individual has been
+    /// created; by default, all of them are reported as sampled.
+    fn evolve_traced(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, Vec<Birth>, Statistics) {
+        let (new_population, stats) = self.evolve(rng, population);
+        let births = new_population.iter().map(|_| Birth::sampled()).collect();
+
+        (new_population, births, stats)
+    }
+
+    /// Same as `evolve()`, but returns an error instead of panicking; by
+    /// default, checks only whether the population is empty.
+    fn try_evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Statistics), Error> {
+        if population.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
+
+        Ok(self.evolve(rng, population))
+    }
+
+    /// Same as `evolve_traced()`, but returns an error instead of
+    /// panicking (see `try_evolve()`).
+    fn try_evolve_traced(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Vec<Birth>, Statistics), Error> {
+        if population.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
+
+        Ok(self.evolve_traced(rng, population))
+    }
+}
+
+impl<I, G, O> Optimizer<I, G> for Box<O>
+where
+    I: Individual<G>,
+    O: Optimizer<I, G> + ?Sized,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics) {
+        (**self).evolve(rng, population)
+    }
+
+    fn evolve_traced(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, Vec<Birth>, Statistics) {
+        (**self).evolve_traced(rng, population)
+    }
+
+    fn try_evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Statistics), Error> {
+        (**self).try_evolve(rng, population)
+    }
+
+    fn try_evolve_traced(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Vec<Birth>, Statistics), Error> {
+        (**self).try_evolve_traced(rng, population)
+    }
 }
@@ -0,0 +1,395 @@
+use std::marker::PhantomData;
+
+use rand::RngCore;
+
+use crate::{Chromosome, CrossoverMethod, Gene, MutationMethod, Statistics};
+
+/// Gene of a chromosome built out of typed segments - e.g. a few real
+/// parameters followed by a binary mask and a permutation.
+///
+/// Such chromosomes are evolved by `SegmentedCrossover` and
+/// `SegmentedMutation`, which apply a different (typed) operator to each
+/// segment.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MixedGene {
+    Real(f32),
+    Integer(i32),
+    Binary(bool),
+    Index(usize),
+}
+
+impl Gene for MixedGene {
+    fn value(&self) -> f32 {
+        match self {
+            Self::Real(gene) => gene.value(),
+            Self::Integer(gene) => gene.value(),
+            Self::Binary(gene) => gene.value(),
+            Self::Index(gene) => gene.value(),
+        }
+    }
+}
+
+/// Gene type a segment of `MixedGene`s can consist of.
+pub trait SegmentGene: Gene {
+    fn wrap(self) -> MixedGene;
+
+    /// Panics if the gene is of another type - which means the segments
+    /// don't match the chromosome.
+    fn unwrap(gene: &MixedGene) -> Self;
+}
+
+macro_rules! segment_gene {
+    ($type:ty, $variant:ident) => {
+        impl SegmentGene for $type {
+            fn wrap(self) -> MixedGene {
+                MixedGene::$variant(self)
+            }
+
+            fn unwrap(gene: &MixedGene) -> Self {
+                match gene {
+                    MixedGene::$variant(gene) => *gene,
+                    gene => panic!("expected {} gene, got {:?}", stringify!($variant), gene),
+                }
+            }
+        }
+    };
+}
+
+segment_gene!(f32, Real);
+segment_gene!(i32, Integer);
+segment_gene!(bool, Binary);
+segment_gene!(usize, Index);
+
+/// Adapts an operator working on genes of type `G` so that it can be used
+/// for a segment of `MixedGene`s.
+#[derive(Clone, Debug)]
+pub struct Typed<G, O> {
+    operator: O,
+    _gene: PhantomData<fn() -> G>,
+}
+
+impl<G, O> Typed<G, O> {
+    pub fn new(operator: O) -> Self {
+        Self {
+            operator,
+            _gene: PhantomData,
+        }
+    }
+}
+
+fn unwrap_all<G>(chromosome: &Chromosome<MixedGene>) -> Chromosome<G>
+where
+    G: SegmentGene,
+{
+    let mut typed: Chromosome<G> = chromosome.iter().map(G::unwrap).collect();
+    typed.set_sigma(chromosome.sigma());
+    typed
+}
+
+fn wrap_all<G>(chromosome: Chromosome<G>) -> Chromosome<MixedGene>
+where
+    G: SegmentGene,
+{
+    let sigma = chromosome.sigma();
+    let mut mixed: Chromosome<MixedGene> = chromosome.into_iter().map(G::wrap).collect();
+    mixed.set_sigma(sigma);
+    mixed
+}
+
+impl<G, C> CrossoverMethod<MixedGene> for Typed<G, C>
+where
+    G: SegmentGene,
+    C: CrossoverMethod<G>,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<MixedGene>,
+        parent_b: &Chromosome<MixedGene>,
+    ) -> Chromosome<MixedGene> {
+        wrap_all(
+            self.operator
+                .crossover(rng, &unwrap_all(parent_a), &unwrap_all(parent_b)),
+        )
+    }
+
+    fn adapt(&mut self, stats: &Statistics) {
+        self.operator.adapt(stats);
+    }
+}
+
+impl<G, M> MutationMethod<MixedGene> for Typed<G, M>
+where
+    G: SegmentGene,
+    M: MutationMethod<G>,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<MixedGene>) {
+        let mut typed = unwrap_all(child);
+        self.operator.mutate(rng, &mut typed);
+        *child = wrap_all(typed);
+    }
+
+    fn adapt(&mut self, stats: &Statistics) {
+        self.operator.adapt(stats);
+    }
+}
+
+/// Splits `chromosome` into chromosomes of consecutive segments of given
+/// lengths.
+fn split(chromosome: &Chromosome<MixedGene>, lengths: &[usize]) -> Vec<Chromosome<MixedGene>> {
+    assert_eq!(chromosome.len(), lengths.iter().sum::<usize>());
+
+    let mut from = 0;
+
+    lengths
+        .iter()
+        .map(|&len| {
+            let mut segment: Chromosome<MixedGene> =
+                chromosome.genes[from..from + len].iter().copied().collect();
+
+            segment.set_sigma(chromosome.sigma());
+            from += len;
+            segment
+        })
+        .collect()
+}
+
+/// Crossover for chromosomes of `MixedGene`s: each segment is crossed over
+/// with its own operator (usually a `Typed` one).
+pub struct SegmentedCrossover {
+    /// Length of each segment, along with its operator.
+    segments: Vec<(usize, Box<dyn CrossoverMethod<MixedGene>>)>,
+}
+
+impl SegmentedCrossover {
+    pub fn new(segments: Vec<(usize, Box<dyn CrossoverMethod<MixedGene>>)>) -> Self {
+        Self { segments }
+    }
+
+    fn lengths(&self) -> Vec<usize> {
+        self.segments.iter().map(|(len, _)| *len).collect()
+    }
+}
+
+impl std::fmt::Debug for SegmentedCrossover {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SegmentedCrossover")
+            .field("segments", &self.lengths())
+            .finish()
+    }
+}
+
+impl CrossoverMethod<MixedGene> for SegmentedCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<MixedGene>,
+        parent_b: &Chromosome<MixedGene>,
+    ) -> Chromosome<MixedGene> {
+        let lengths = self.lengths();
+
+        split(parent_a, &lengths)
+            .iter()
+            .zip(split(parent_b, &lengths).iter())
+            .zip(&self.segments)
+            .flat_map(|((a, b), (_, operator))| operator.crossover(rng, a, b))
+            .collect()
+    }
+
+    fn adapt(&mut self, stats: &Statistics) {
+        for (_, operator) in &mut self.segments {
+            operator.adapt(stats);
+        }
+    }
+}
+
+/// Mutation counterpart of `SegmentedCrossover`.
+pub struct SegmentedMutation {
+    /// Length of each segment, along with its operator.
+    segments: Vec<(usize, Box<dyn MutationMethod<MixedGene>>)>,
+}
+
+impl SegmentedMutation {
+    pub fn new(segments: Vec<(usize, Box<dyn MutationMethod<MixedGene>>)>) -> Self {
+        Self { segments }
+    }
+
+    fn lengths(&self) -> Vec<usize> {
+        self.segments.iter().map(|(len, _)| *len).collect()
+    }
+}
+
+impl std::fmt::Debug for SegmentedMutation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SegmentedMutation")
+            .field("segments", &self.lengths())
+            .finish()
+    }
+}
+
+impl MutationMethod<MixedGene> for SegmentedMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<MixedGene>) {
+        let sigma = child.sigma();
+        let mut segments = split(child, &self.lengths());
+
+        for (segment, (_, operator)) in segments.iter_mut().zip(&self.segments) {
+            operator.mutate(rng, segment);
+        }
+
+        // Only self-adaptive mutation touches sigma, so at most one segment
+        // is expected to have changed it
+        let sigma = segments
+            .iter()
+            .map(Chromosome::sigma)
+            .find(|segment_sigma| *segment_sigma != sigma)
+            .unwrap_or(sigma);
+
+        *child = segments.into_iter().flatten().collect();
+        child.set_sigma(sigma);
+    }
+
+    fn adapt(&mut self, stats: &Statistics) {
+        for (_, operator) in &mut self.segments {
+            operator.adapt(stats);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BitFlipMutation, GeneticAlgorithm, Individual, IntegerMutation, NormalMutation,
+        OrderCrossover, RouletteWheelSelection, SwapMutation, UniformCrossover,
+    };
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    /// Two reals (best at 1.0), four bits (best all set), two integers
+    /// (best at 7) and a permutation of four (best sorted).
+    const LENGTHS: [usize; 4] = [2, 4, 2, 4];
+
+    struct TestIndividual {
+        chromosome: Chromosome<MixedGene>,
+    }
+
+    impl Individual<MixedGene> for TestIndividual {
+        fn create(chromosome: Chromosome<MixedGene>) -> Self {
+            Self { chromosome }
+        }
+
+        fn fitness(&self) -> f32 {
+            let penalty: f32 = self
+                .chromosome
+                .iter()
+                .enumerate()
+                .map(|(idx, gene)| match *gene {
+                    MixedGene::Real(gene) => (gene - 1.0).abs(),
+                    MixedGene::Binary(gene) => (!gene as u8) as f32,
+                    MixedGene::Integer(gene) => (gene - 7).abs() as f32,
+                    MixedGene::Index(gene) => (gene != idx - 8) as u8 as f32,
+                })
+                .sum();
+
+            1.0 / (1.0 + penalty)
+        }
+
+        fn chromosome(&self) -> &Chromosome<MixedGene> {
+            &self.chromosome
+        }
+    }
+
+    fn random_individual(rng: &mut dyn RngCore) -> TestIndividual {
+        let mut permutation: Vec<usize> = (0..4).collect();
+        rand::seq::SliceRandom::shuffle(permutation.as_mut_slice(), rng);
+
+        let mut genes: Vec<_> = (0..2)
+            .map(|_| MixedGene::Real(rng.gen_range(-5.0..5.0)))
+            .collect();
+
+        genes.extend((0..4).map(|_| MixedGene::Binary(rng.gen_bool(0.5))));
+        genes.extend((0..2).map(|_| MixedGene::Integer(rng.gen_range(0..=20))));
+        genes.extend(permutation.into_iter().map(MixedGene::Index));
+
+        TestIndividual::create(genes.into_iter().collect())
+    }
+
+    fn ga() -> GeneticAlgorithm<RouletteWheelSelection, SegmentedCrossover, SegmentedMutation> {
+        let crossover = SegmentedCrossover::new(vec![
+            (LENGTHS[0], Box::new(Typed::<f32, _>::new(UniformCrossover))),
+            (
+                LENGTHS[1],
+                Box::new(Typed::<bool, _>::new(UniformCrossover)),
+            ),
+            (LENGTHS[2], Box::new(Typed::<i32, _>::new(UniformCrossover))),
+            (LENGTHS[3], Box::new(Typed::<usize, _>::new(OrderCrossover))),
+        ]);
+
+        let mutation = SegmentedMutation::new(vec![
+            (
+                LENGTHS[0],
+                Box::new(Typed::new(NormalMutation::new(0.3, 0.2))),
+            ),
+            (LENGTHS[1], Box::new(Typed::new(BitFlipMutation::new(0.05)))),
+            (
+                LENGTHS[2],
+                Box::new(Typed::new(IntegerMutation::new(0.2, 2, 0, 20))),
+            ),
+            (
+                LENGTHS[3],
+                Box::new(Typed::<usize, _>::new(SwapMutation::new(0.1))),
+            ),
+        ]);
+
+        GeneticAlgorithm::new(RouletteWheelSelection, crossover, mutation)
+    }
+
+    #[test]
+    fn keeps_segments_typed() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let ga = ga();
+        let mut population: Vec<_> = (0..20).map(|_| random_individual(&mut rng)).collect();
+
+        for _ in 0..5 {
+            (population, _) = ga.evolve(&mut rng, &population);
+        }
+
+        for individual in &population {
+            let segments = split(individual.chromosome(), &LENGTHS);
+
+            unwrap_all::<f32>(&segments[0]);
+            unwrap_all::<bool>(&segments[1]);
+            unwrap_all::<i32>(&segments[2]);
+
+            let mut permutation = unwrap_all::<usize>(&segments[3]).genes;
+            permutation.sort_unstable();
+            assert_eq!(permutation, vec![0, 1, 2, 3]);
+        }
+    }
+
+    #[test]
+    fn evolves_mixed_chromosomes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let ga = ga();
+        let mut population: Vec<_> = (0..50).map(|_| random_individual(&mut rng)).collect();
+
+        let (_, initial) = ga.evolve(&mut rng, &population);
+
+        for _ in 0..50 {
+            (population, _) = ga.evolve(&mut rng, &population);
+        }
+
+        let (_, last) = ga.evolve(&mut rng, &population);
+
+        assert!(last.avg_fitness > 2.0 * initial.avg_fitness);
+        assert!(last.max_fitness > 0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected Binary gene")]
+    fn rejects_mismatched_segments() {
+        let chromosome: Chromosome<MixedGene> = vec![MixedGene::Real(1.0)].into_iter().collect();
+
+        unwrap_all::<bool>(&chromosome);
+    }
+}
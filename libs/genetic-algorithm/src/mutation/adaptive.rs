@@ -0,0 +1,118 @@
+use rand::RngCore;
+
+use crate::operator_selection::OperatorSelection;
+use crate::{Chromosome, Error, MutationMethod, Statistics};
+
+/// Mutation counterpart of `AdaptiveCrossover`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdaptiveMutation<M> {
+    methods: Vec<M>,
+    selection: OperatorSelection,
+}
+
+impl<M> AdaptiveMutation<M> {
+    /// `exploration` balances sticking to the best method so far against
+    /// giving the other ones another chance; it should be of roughly the
+    /// same magnitude as the per-generation changes of average fitness.
+    pub fn new(methods: Vec<M>, exploration: f32) -> Self {
+        Self::try_new(methods, exploration).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(methods: Vec<M>, exploration: f32) -> Result<Self, Error> {
+        let selection = OperatorSelection::try_new(methods.len(), exploration)?;
+
+        Ok(Self { methods, selection })
+    }
+
+    /// Index of the method currently used.
+    pub fn current(&self) -> usize {
+        self.selection.current()
+    }
+
+    /// Average fitness gain of offspring produced by each method.
+    pub fn scores(&self) -> Vec<f32> {
+        self.selection.scores()
+    }
+}
+
+impl<G, M> MutationMethod<G> for AdaptiveMutation<M>
+where
+    M: MutationMethod<G>,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        self.methods[self.current()].mutate(rng, child)
+    }
+
+    fn adapt(&mut self, stats: &Statistics) {
+        for method in &mut self.methods {
+            method.adapt(stats);
+        }
+
+        self.selection.adapt(stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        GeneticAlgorithm, Individual, NormalMutation, Optimizer, RouletteWheelSelection,
+        UniformCrossover,
+    };
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn fitness(&self) -> f32 {
+            // Peak at 5.0 for every gene
+            self.chromosome
+                .iter()
+                .map(|gene| 1.0 / (1.0 + (gene - 5.0).powi(2)))
+                .sum()
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+    }
+
+    #[test]
+    fn prefers_useful_mutation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            AdaptiveMutation::new(
+                vec![
+                    // Way too weak to get anywhere in reasonable time
+                    NormalMutation::new(0.5, 0.0001),
+                    NormalMutation::new(0.5, 0.5),
+                ],
+                0.01,
+            ),
+        );
+
+        let mut population: Vec<_> = (0..20)
+            .map(|_| TestIndividual::create(vec![0.0; 4].into_iter().collect()))
+            .collect();
+
+        let mut uses = [0; 2];
+
+        for _ in 0..100 {
+            population = Optimizer::evolve(&mut ga, &mut rng, &population).0;
+            uses[ga.mutation_method().current()] += 1;
+        }
+
+        assert!(uses[1] > uses[0]);
+    }
+}
@@ -0,0 +1,94 @@
+use std::ops::Bound;
+
+use rand::{Rng, RngCore};
+use rand_distr::{Cauchy, Distribution};
+
+use crate::{Chromosome, Error, MutationMethod, TunableMutation};
+
+/// Adds Cauchy-distributed noise to the affected genes.
+///
+/// Cauchy distribution has much heavier tails than the normal one, so
+/// every now and then a gene makes a big jump - which helps to escape
+/// local optima.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CauchyMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    /// Scale of the distribution (half of its interquartile range).
+    scale: f32,
+}
+
+impl CauchyMutation {
+    pub fn new(chance: f32, scale: f32) -> Self {
+        Self::try_new(chance, scale).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(chance: f32, scale: f32) -> Result<Self, Error> {
+        let positive = (Bound::Excluded(0.0), Bound::Unbounded);
+
+        Error::check("mutation chance", chance, 0.0..=1.0, "0.0..=1.0")?;
+        Error::check("scale", scale, positive, "(0.0, inf)")?;
+
+        Ok(Self { chance, scale })
+    }
+}
+
+impl MutationMethod for CauchyMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        let cauchy = Cauchy::new(0.0, self.scale).unwrap();
+
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                let change: f32 = cauchy.sample(rng);
+                *gene += change;
+            }
+        }
+    }
+}
+
+impl TunableMutation for CauchyMutation {
+    fn parameters(&self) -> (f32, f32) {
+        (self.chance, self.scale)
+    }
+
+    fn parameters_mut(&mut self) -> (&mut f32, &mut f32) {
+        (&mut self.chance, &mut self.scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32, scale: f32) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+
+        CauchyMutation::new(chance, scale).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        let actual = actual(0.0, 0.5);
+        let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_max_chance_entirely_changes_the_original_chromosome() {
+        let actual = actual(1.0, 0.5);
+        let expected = vec![1.3335003, 1.718788, 2.8531392, 2.8586097, 4.5361714];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+}
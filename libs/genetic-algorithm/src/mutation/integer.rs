@@ -0,0 +1,79 @@
+use rand::{Rng, RngCore};
+
+use crate::{Chromosome, Error, MutationMethod};
+
+/// Creep mutation for integer chromosomes: adds a random non-zero step
+/// from `<-max_step, max_step>` to the affected genes, keeping them within
+/// `<min, max>`.
+#[derive(Clone, Debug)]
+pub struct IntegerMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    max_step: i32,
+    min: i32,
+    max: i32,
+}
+
+impl IntegerMutation {
+    pub fn new(chance: f32, max_step: i32, min: i32, max: i32) -> Self {
+        Self::try_new(chance, max_step, min, max).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(chance: f32, max_step: i32, min: i32, max: i32) -> Result<Self, Error> {
+        Error::check("mutation chance", chance, 0.0..=1.0, "0.0..=1.0")?;
+        Error::check("max step", max_step as f32, 1.0.., "1..")?;
+        Error::check("max", max as f32, (min as f32).., "min..")?;
+
+        Ok(Self {
+            chance,
+            max_step,
+            min,
+            max,
+        })
+    }
+}
+
+impl MutationMethod<i32> for IntegerMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<i32>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                let step = rng.gen_range(1..=self.max_step);
+                let sign = if rng.gen_bool(0.5) { -1 } else { 1 };
+
+                *gene = gene.saturating_add(sign * step).clamp(self.min, self.max);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32) -> Vec<i32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child = vec![0, 3, 5, 7, 10].into_iter().collect();
+
+        IntegerMutation::new(chance, 3, 0, 10).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        assert_eq!(actual(0.0), vec![0, 3, 5, 7, 10]);
+    }
+
+    #[test]
+    fn given_max_chance_changes_genes_within_bounds() {
+        let actual = actual(1.0);
+
+        assert_eq!(actual, vec![1, 6, 4, 9, 9]);
+        assert!(actual.iter().all(|gene| (0..=10).contains(gene)));
+    }
+}
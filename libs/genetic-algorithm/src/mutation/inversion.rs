@@ -0,0 +1,66 @@
+use rand::{Rng, RngCore};
+
+use crate::crossover::random_segment;
+use crate::{Chromosome, Error, Gene, MutationMethod};
+
+/// Reverses a random segment of the chromosome; for a tour in travelling
+/// salesman problem that's the classic 2-opt move.
+#[derive(Clone, Debug)]
+pub struct InversionMutation {
+    /// Probability of reversing a segment of the chromosome:
+    /// - 0.0 = chromosome will be left intact
+    /// - 1.0 = each chromosome will get a segment reversed
+    chance: f32,
+}
+
+impl InversionMutation {
+    pub fn new(chance: f32) -> Self {
+        Self::try_new(chance).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(chance: f32) -> Result<Self, Error> {
+        Error::check("inversion chance", chance, 0.0..=1.0, "0.0..=1.0")?;
+
+        Ok(Self { chance })
+    }
+}
+
+impl<G> MutationMethod<G> for InversionMutation
+where
+    G: Gene,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        if child.len() < 2 || !rng.gen_bool(self.chance as f64) {
+            return;
+        }
+
+        let (from, to) = random_segment(rng, child.len());
+        child.genes[from..to].reverse();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32) -> Vec<usize> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child = (0..8).collect();
+
+        InversionMutation::new(chance).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        assert_eq!(actual(0.0), (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn given_max_chance_reverses_a_segment() {
+        assert_eq!(actual(1.0), vec![0, 1, 2, 3, 4, 7, 6, 5]);
+    }
+}
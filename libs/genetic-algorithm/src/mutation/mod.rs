@@ -1,9 +1,45 @@
-use crate::{Chromosome, MutationMethod};
+use crate::{Chromosome, Error, MutationMethod, TunableMutation};
 use rand::Rng;
 use rand::RngCore;
 
+mod adaptive;
+mod bit_flip;
+mod cauchy;
+mod integer;
+mod inversion;
+mod normal;
+mod polynomial;
+mod random_reset;
+mod scheduled;
+mod self_adaptive;
+mod swap;
+
+pub use adaptive::AdaptiveMutation;
+pub use bit_flip::BitFlipMutation;
+pub use cauchy::CauchyMutation;
+pub use integer::IntegerMutation;
+pub use inversion::InversionMutation;
+pub use normal::NormalMutation;
+pub use polynomial::PolynomialMutation;
+pub use random_reset::RandomResetMutation;
+pub use scheduled::{MutationSchedule, ScheduledMutation};
+pub use self_adaptive::SelfAdaptiveMutation;
+pub use swap::SwapMutation;
+
+/// Former name of `UniformPerturbationMutation`, kept for compatibility.
+#[deprecated(
+    note = "perturbs genes uniformly, not normally - use `UniformPerturbationMutation` \
+            (same behaviour) or `NormalMutation`"
+)]
+pub type GaussianMutation = UniformPerturbationMutation;
+
+/// Adds a random value from `<-coeff, coeff>` to the affected genes.
+///
+/// Despite being called `GaussianMutation` in the past, the perturbation is
+/// uniform - for a real Gaussian one, see `NormalMutation`.
 #[derive(Clone, Debug)]
-pub struct GaussianMutation {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UniformPerturbationMutation {
     /// Вероятность изменения гена:
     /// - 0.0 = ни один ген не будет затронут
     /// - 1.0 = все гены будут затронуты
@@ -15,16 +51,20 @@ pub struct GaussianMutation {
     coeff: f32,
 }
 
-impl GaussianMutation {
+impl UniformPerturbationMutation {
     #[allow(dead_code)]
     pub fn new(chance: f32, coeff: f32) -> Self {
-        assert!(chance >= 0.0 && chance <= 1.0);
+        Self::try_new(chance, coeff).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(chance: f32, coeff: f32) -> Result<Self, Error> {
+        Error::check("mutation chance", chance, 0.0..=1.0, "0.0..=1.0")?;
 
-        Self { chance, coeff }
+        Ok(Self { chance, coeff })
     }
 }
 
-impl MutationMethod for GaussianMutation {
+impl MutationMethod for UniformPerturbationMutation {
     fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
         for gene in child.iter_mut() {
             let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
@@ -36,11 +76,21 @@ impl MutationMethod for GaussianMutation {
     }
 }
 
+impl TunableMutation for UniformPerturbationMutation {
+    fn parameters(&self) -> (f32, f32) {
+        (self.chance, self.coeff)
+    }
+
+    fn parameters_mut(&mut self) -> (&mut f32, &mut f32) {
+        (&mut self.chance, &mut self.coeff)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    mod gaussian_mutation {
+    mod uniform_perturbation_mutation {
         use super::*;
         use rand::SeedableRng;
         use rand_chacha::ChaCha8Rng;
@@ -49,7 +99,7 @@ mod tests {
             let mut rng = ChaCha8Rng::from_seed(Default::default());
             let mut child = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
 
-            GaussianMutation::new(chance, coeff).mutate(&mut rng, &mut child);
+            UniformPerturbationMutation::new(chance, coeff).mutate(&mut rng, &mut child);
 
             child.into_iter().collect()
         }
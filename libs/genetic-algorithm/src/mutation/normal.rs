@@ -0,0 +1,95 @@
+use rand::{Rng, RngCore};
+use rand_distr::StandardNormal;
+
+use crate::{Chromosome, Error, MutationMethod, TunableMutation};
+
+/// Adds normally distributed noise, `N(0, sigma²)`, to the affected genes.
+///
+/// Small changes are common, large ones get rare quickly - unlike with
+/// `UniformPerturbationMutation`, where every change up to `coeff` is
+/// equally likely.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NormalMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    /// Standard deviation of the change.
+    sigma: f32,
+}
+
+impl NormalMutation {
+    pub fn new(chance: f32, sigma: f32) -> Self {
+        Self::try_new(chance, sigma).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(chance: f32, sigma: f32) -> Result<Self, Error> {
+        Error::check("mutation chance", chance, 0.0..=1.0, "0.0..=1.0")?;
+        Error::check("sigma", sigma, 0.0.., "0.0..")?;
+
+        Ok(Self { chance, sigma })
+    }
+}
+
+impl MutationMethod for NormalMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene += self.sigma * rng.sample::<f32, _>(StandardNormal);
+            }
+        }
+    }
+}
+
+impl TunableMutation for NormalMutation {
+    fn parameters(&self) -> (f32, f32) {
+        (self.chance, self.sigma)
+    }
+
+    fn parameters_mut(&mut self) -> (&mut f32, &mut f32) {
+        (&mut self.chance, &mut self.sigma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32, sigma: f32) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+
+        NormalMutation::new(chance, sigma).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        let actual = actual(0.0, 0.5);
+        let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_zero_sigma_does_not_change_the_original_chromosome() {
+        let actual = actual(1.0, 0.0);
+        let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_max_chance_entirely_changes_the_original_chromosome() {
+        let actual = actual(1.0, 0.5);
+        let expected = vec![1.6888486, 2.2026734, 2.4018655, 3.0324764, 4.664113];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+}
@@ -0,0 +1,117 @@
+use std::ops::Bound;
+
+use rand::{Rng, RngCore};
+
+use crate::{Chromosome, Error, MutationMethod};
+
+/// Polynomial mutation (Deb & Goyal, 1996) for genes bounded by
+/// `<min, max>`: the perturbation follows a polynomial distribution that
+/// shrinks near the bounds, so genes never leave the allowed range.
+#[derive(Clone, Debug)]
+pub struct PolynomialMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    /// Distribution index:
+    /// - small values (e.g. 5.0) = large perturbations are common
+    /// - large values (e.g. 100.0) = genes barely move
+    eta: f32,
+
+    min: f32,
+    max: f32,
+}
+
+impl PolynomialMutation {
+    pub fn new(chance: f32, eta: f32, min: f32, max: f32) -> Self {
+        Self::try_new(chance, eta, min, max).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(chance: f32, eta: f32, min: f32, max: f32) -> Result<Self, Error> {
+        Error::check("mutation chance", chance, 0.0..=1.0, "0.0..=1.0")?;
+        Error::check("eta", eta, 0.0.., "0.0..")?;
+        Error::check(
+            "max",
+            max,
+            (Bound::Excluded(min), Bound::Unbounded),
+            "(min, inf)",
+        )?;
+
+        Ok(Self {
+            chance,
+            eta,
+            min,
+            max,
+        })
+    }
+}
+
+impl MutationMethod for PolynomialMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        let range = self.max - self.min;
+        let power = 1.0 / (self.eta + 1.0);
+
+        for gene in child.iter_mut() {
+            if !rng.gen_bool(self.chance as f64) {
+                continue;
+            }
+
+            let x = gene.clamp(self.min, self.max);
+            let delta_min = (x - self.min) / range;
+            let delta_max = (self.max - x) / range;
+            let u: f32 = rng.gen();
+
+            let delta = if u < 0.5 {
+                let value = 2.0 * u + (1.0 - 2.0 * u) * (1.0 - delta_min).powf(self.eta + 1.0);
+                value.powf(power) - 1.0
+            } else {
+                let value =
+                    2.0 * (1.0 - u) + 2.0 * (u - 0.5) * (1.0 - delta_max).powf(self.eta + 1.0);
+                1.0 - value.powf(power)
+            };
+
+            *gene = (x + delta * range).clamp(self.min, self.max);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32, eta: f32) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+
+        PolynomialMutation::new(chance, eta, 0.0, 5.0).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        let actual = actual(0.0, 20.0);
+        let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_max_chance_entirely_changes_the_original_chromosome() {
+        let actual = actual(1.0, 20.0);
+        let expected = vec![0.7749996, 2.2597628, 3.3897655, 4.071315, 5.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn keeps_genes_within_bounds() {
+        let actual = actual(1.0, 0.0);
+
+        assert!(actual.iter().all(|gene| (0.0..=5.0).contains(gene)));
+    }
+}
@@ -0,0 +1,73 @@
+use rand::{Rng, RngCore};
+
+use crate::{Chromosome, Error, MutationMethod};
+
+/// Replaces the affected genes with completely new values drawn uniformly
+/// from `<min, max>`.
+#[derive(Clone, Debug)]
+pub struct RandomResetMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    min: f32,
+    max: f32,
+}
+
+impl RandomResetMutation {
+    pub fn new(chance: f32, min: f32, max: f32) -> Self {
+        Self::try_new(chance, min, max).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(chance: f32, min: f32, max: f32) -> Result<Self, Error> {
+        Error::check("mutation chance", chance, 0.0..=1.0, "0.0..=1.0")?;
+        Error::check("max", max, min.., "min..")?;
+
+        Ok(Self { chance, min, max })
+    }
+}
+
+impl MutationMethod for RandomResetMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene = rng.gen_range(self.min..=self.max);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+
+        RandomResetMutation::new(chance, -1.0, 1.0).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        let actual = actual(0.0);
+        let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_max_chance_entirely_changes_the_original_chromosome() {
+        let actual = actual(1.0);
+        let expected = vec![-0.6255188, 0.67383957, 0.8181262, 0.26284897, 0.5238807];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        assert!(actual.iter().all(|gene| (-1.0..=1.0).contains(gene)));
+    }
+}
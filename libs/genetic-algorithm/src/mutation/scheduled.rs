@@ -0,0 +1,305 @@
+use rand::RngCore;
+use std::collections::VecDeque;
+use std::ops::Bound;
+
+use crate::{Chromosome, Error, MutationMethod, Statistics, TunableMutation};
+
+/// How `ScheduledMutation` changes the rate (chance) and strength of the
+/// mutation it wraps, generation after generation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MutationSchedule {
+    /// Simulated annealing: both the rate and the strength are multiplied
+    /// by `decay` each generation, but never drop below given minimums -
+    /// explore a lot at first, fine-tune later.
+    Annealing {
+        decay: f32,
+        min_chance: f32,
+        min_strength: f32,
+    },
+
+    /// Rechenberg's 1/5th success rule: when more than one fifth of the
+    /// last `window` generations improved the best fitness, the strength is
+    /// divided by `factor` (we are doing fine, so let's take larger steps),
+    /// otherwise it's multiplied by it.
+    ///
+    /// `factor` must lie in `(0.0, 1.0)`; 0.85 is the classic choice.
+    OneFifthRule { factor: f32, window: usize },
+
+    /// Whenever the population's diversity drops below `threshold`, both
+    /// the rate and the strength get multiplied by `factor` (up to
+    /// `max_strength`); once the diversity is back, they return to their
+    /// original values.
+    ///
+    /// `factor` must be at least 1.0.
+    DiversityBoost {
+        threshold: f32,
+        factor: f32,
+        max_strength: f32,
+    },
+}
+
+impl MutationSchedule {
+    fn validate(&self) -> Result<(), Error> {
+        match *self {
+            Self::Annealing {
+                decay,
+                min_chance,
+                min_strength,
+            } => {
+                Error::check("decay", decay, 0.0..=1.0, "0.0..=1.0")?;
+                Error::check("min chance", min_chance, 0.0..=1.0, "0.0..=1.0")?;
+                Error::check("min strength", min_strength, 0.0.., "0.0..")?;
+            }
+
+            Self::OneFifthRule { factor, window } => {
+                let unit = (Bound::Excluded(0.0), Bound::Excluded(1.0));
+
+                Error::check("factor", factor, unit, "(0.0, 1.0)")?;
+                Error::check("window", window as f32, 1.0.., "1..")?;
+            }
+
+            Self::DiversityBoost {
+                threshold,
+                factor,
+                max_strength,
+            } => {
+                Error::check("threshold", threshold, 0.0.., "0.0..")?;
+                Error::check("factor", factor, 1.0.., "1.0..")?;
+                Error::check("max strength", max_strength, 0.0.., "0.0..")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Wraps a tunable mutation and adjusts its parameters according to given
+/// schedule; schedules can be combined by nesting `ScheduledMutation`s.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduledMutation<M> {
+    mutation: M,
+    schedule: MutationSchedule,
+
+    /// Parameters the mutation had been created with.
+    base: (f32, f32),
+
+    /// Best fitness seen in the previous generation.
+    last_max_fitness: Option<f32>,
+
+    /// Recent generations, `true` marking the ones that improved.
+    successes: VecDeque<bool>,
+}
+
+impl<M> ScheduledMutation<M>
+where
+    M: TunableMutation,
+{
+    pub fn new(mutation: M, schedule: MutationSchedule) -> Self {
+        Self::try_new(mutation, schedule).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(mutation: M, schedule: MutationSchedule) -> Result<Self, Error> {
+        schedule.validate()?;
+
+        let base = mutation.parameters();
+
+        Ok(Self {
+            mutation,
+            schedule,
+            base,
+            last_max_fitness: None,
+            successes: VecDeque::new(),
+        })
+    }
+
+    pub fn mutation(&self) -> &M {
+        &self.mutation
+    }
+}
+
+impl<M> MutationMethod for ScheduledMutation<M>
+where
+    M: TunableMutation,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        self.mutation.mutate(rng, child)
+    }
+
+    fn adapt(&mut self, stats: &Statistics) {
+        self.mutation.adapt(stats);
+
+        let improved = self
+            .last_max_fitness
+            .map_or(false, |last| stats.max_fitness > last);
+
+        self.last_max_fitness = Some(stats.max_fitness);
+
+        let (base_chance, base_strength) = self.base;
+        let (chance, strength) = self.mutation.parameters_mut();
+
+        match self.schedule {
+            MutationSchedule::Annealing {
+                decay,
+                min_chance,
+                min_strength,
+            } => {
+                *chance = (*chance * decay).max(min_chance);
+                *strength = (*strength * decay).max(min_strength);
+            }
+
+            MutationSchedule::OneFifthRule { factor, window } => {
+                self.successes.push_back(improved);
+
+                if self.successes.len() > window {
+                    self.successes.pop_front();
+                }
+
+                if self.successes.len() == window {
+                    let ratio =
+                        self.successes.iter().filter(|&&s| s).count() as f32 / window as f32;
+
+                    if ratio > 0.2 {
+                        *strength /= factor;
+                    } else if ratio < 0.2 {
+                        *strength *= factor;
+                    }
+                }
+            }
+
+            MutationSchedule::DiversityBoost {
+                threshold,
+                factor,
+                max_strength,
+            } => {
+                if stats.diversity < threshold {
+                    *chance = (*chance * factor).min(1.0);
+                    *strength = (*strength * factor).min(max_strength);
+                } else {
+                    *chance = base_chance;
+                    *strength = base_strength;
+                }
+            }
+        }
+    }
+}
+
+impl<M> TunableMutation for ScheduledMutation<M>
+where
+    M: TunableMutation,
+{
+    fn parameters(&self) -> (f32, f32) {
+        self.mutation.parameters()
+    }
+
+    fn parameters_mut(&mut self) -> (&mut f32, &mut f32) {
+        self.mutation.parameters_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NormalMutation;
+    use approx::assert_relative_eq;
+
+    fn stats(max_fitness: f32, diversity: f32) -> Statistics {
+        Statistics {
+            min_fitness: 0.0,
+            max_fitness,
+            avg_fitness: 0.0,
+            diversity,
+            pareto_front: Vec::new(),
+            species: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn annealing() {
+        let mut mutation = ScheduledMutation::new(
+            NormalMutation::new(0.5, 1.0),
+            MutationSchedule::Annealing {
+                decay: 0.5,
+                min_chance: 0.1,
+                min_strength: 0.2,
+            },
+        );
+
+        mutation.adapt(&stats(0.0, 1.0));
+        let (chance, strength) = mutation.parameters();
+        assert_relative_eq!(chance, 0.25);
+        assert_relative_eq!(strength, 0.5);
+
+        for _ in 0..10 {
+            mutation.adapt(&stats(0.0, 1.0));
+        }
+
+        let (chance, strength) = mutation.parameters();
+        assert_relative_eq!(chance, 0.1);
+        assert_relative_eq!(strength, 0.2);
+    }
+
+    #[test]
+    fn one_fifth_rule() {
+        let mut mutation = ScheduledMutation::new(
+            NormalMutation::new(0.5, 1.0),
+            MutationSchedule::OneFifthRule {
+                factor: 0.5,
+                window: 5,
+            },
+        );
+
+        // Best fitness keeps improving - steps grow
+        for max_fitness in 0..6 {
+            mutation.adapt(&stats(max_fitness as f32, 1.0));
+        }
+
+        assert_relative_eq!(mutation.parameters().1, 4.0);
+
+        // Stagnation - once the successes leave the window, steps shrink
+        for _ in 0..12 {
+            mutation.adapt(&stats(10.0, 1.0));
+        }
+
+        assert_relative_eq!(mutation.parameters().1, 0.5);
+    }
+
+    #[test]
+    fn diversity_boost() {
+        let mut mutation = ScheduledMutation::new(
+            NormalMutation::new(0.5, 1.0),
+            MutationSchedule::DiversityBoost {
+                threshold: 0.1,
+                factor: 2.0,
+                max_strength: 3.0,
+            },
+        );
+
+        mutation.adapt(&stats(0.0, 0.01));
+        assert_eq!(mutation.parameters(), (1.0, 2.0));
+
+        mutation.adapt(&stats(0.0, 0.01));
+        assert_eq!(mutation.parameters(), (1.0, 3.0));
+
+        mutation.adapt(&stats(0.0, 0.5));
+        assert_eq!(mutation.parameters(), (0.5, 1.0));
+    }
+
+    #[test]
+    fn invalid_schedule() {
+        let err = ScheduledMutation::try_new(
+            NormalMutation::new(0.5, 1.0),
+            MutationSchedule::OneFifthRule {
+                factor: 0.0,
+                window: 5,
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "factor must be within (0.0, 1.0), but got 0"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use std::ops::Bound;
+
+use rand::{Rng, RngCore};
+use rand_distr::StandardNormal;
+
+use crate::{Chromosome, Error, MutationMethod};
+
+/// Self-adaptive Gaussian mutation, as in evolution strategies: each
+/// chromosome carries its own step size (see `Chromosome::sigma()`), which
+/// is mutated first and then used to mutate all of the genes.
+///
+/// Chromosomes with well-tuned step sizes tend to produce better
+/// offspring, so good step sizes get selected together with good genes.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelfAdaptiveMutation {
+    /// Step size given to chromosomes that don't carry one yet.
+    initial_sigma: f32,
+
+    /// Lower bound for step sizes, so that they can't collapse to zero.
+    min_sigma: f32,
+}
+
+impl SelfAdaptiveMutation {
+    pub fn new(initial_sigma: f32, min_sigma: f32) -> Self {
+        Self::try_new(initial_sigma, min_sigma).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(initial_sigma: f32, min_sigma: f32) -> Result<Self, Error> {
+        let positive = (Bound::Excluded(0.0), Bound::Unbounded);
+
+        Error::check("min sigma", min_sigma, positive, "(0.0, inf)")?;
+        Error::check("initial sigma", initial_sigma, min_sigma.., "min_sigma..")?;
+
+        Ok(Self {
+            initial_sigma,
+            min_sigma,
+        })
+    }
+}
+
+impl MutationMethod for SelfAdaptiveMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        if child.is_empty() {
+            return;
+        }
+
+        // Learning rate recommended by Schwefel for a single step size
+        let tau = 1.0 / (child.len() as f32).sqrt();
+
+        let sigma = child.sigma().unwrap_or(self.initial_sigma);
+        let sigma = sigma * (tau * rng.sample::<f32, _>(StandardNormal)).exp();
+        let sigma = sigma.max(self.min_sigma);
+
+        for gene in child.iter_mut() {
+            *gene += sigma * rng.sample::<f32, _>(StandardNormal);
+        }
+
+        child.set_sigma(Some(sigma));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn self_adaptive_mutation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child: Chromosome = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+        let mutation = SelfAdaptiveMutation::new(0.5, 0.01);
+
+        mutation.mutate(&mut rng, &mut child);
+        assert_relative_eq!(child.sigma().unwrap(), 0.9258692);
+
+        mutation.mutate(&mut rng, &mut child);
+        assert_relative_eq!(child.sigma().unwrap(), 0.3541362);
+
+        let actual: Vec<_> = child.into_iter().collect();
+        let expected = vec![1.0202546, 0.77040607, 1.3477707, 3.5073245, 3.9656339];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn sigma_is_inherited_while_breeding() {
+        let mut parent_a: Chromosome = vec![1.0].into_iter().collect();
+        let mut parent_b: Chromosome = vec![1.0].into_iter().collect();
+        parent_a.set_sigma(Some(0.5));
+        parent_b.set_sigma(Some(2.0));
+
+        let mut child: Chromosome = vec![1.0].into_iter().collect();
+        child.inherit_sigma(&parent_a, &parent_b);
+
+        assert_relative_eq!(child.sigma().unwrap(), 1.0);
+    }
+}
@@ -0,0 +1,71 @@
+use rand::{Rng, RngCore};
+
+use crate::{Chromosome, Error, Gene, MutationMethod};
+
+/// Swaps the affected genes with genes at random positions; since it never
+/// changes *which* genes a chromosome consists of, it's safe to use with
+/// permutations.
+#[derive(Clone, Debug)]
+pub struct SwapMutation {
+    /// Probability of swapping a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+}
+
+impl SwapMutation {
+    pub fn new(chance: f32) -> Self {
+        Self::try_new(chance).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(chance: f32) -> Result<Self, Error> {
+        Error::check("swap chance", chance, 0.0..=1.0, "0.0..=1.0")?;
+
+        Ok(Self { chance })
+    }
+}
+
+impl<G> MutationMethod<G> for SwapMutation
+where
+    G: Gene,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        for idx in 0..child.len() {
+            if rng.gen_bool(self.chance as f64) {
+                let other = rng.gen_range(0..child.len());
+                child.genes.swap(idx, other);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32) -> Vec<usize> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child = (0..8).collect();
+
+        SwapMutation::new(chance).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        assert_eq!(actual(0.0), (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn given_max_chance_shuffles_the_permutation() {
+        let actual = actual(1.0);
+        let mut sorted = actual.clone();
+        sorted.sort_unstable();
+
+        assert_eq!(actual, vec![1, 2, 3, 7, 0, 4, 5, 6]);
+        assert_eq!(sorted, (0..8).collect::<Vec<_>>());
+    }
+}
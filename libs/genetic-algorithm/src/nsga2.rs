@@ -0,0 +1,341 @@
+use rand::{Rng, RngCore};
+use std::cmp::Ordering;
+
+use crate::genetic_algorithm::breed;
+use crate::{CrossoverMethod, Error, MultiObjectiveIndividual, MutationMethod, Statistics};
+
+/// Multi-objective counterpart of `GeneticAlgorithm`, following NSGA-II
+/// (Deb et al., 2002).
+///
+/// Every objective is maximised, the same way `Individual::fitness` is.
+///
+/// Since the individuals we return get evaluated by the caller (e.g. by
+/// living through a whole generation inside the simulation), the classic
+/// `P ∪ Q` merge happens *inside* the population we are given: the better
+/// half of it survives as parents, the other half is replaced by their
+/// offspring.
+#[derive(Debug)]
+pub struct Nsga2<C, M> {
+    crossover_method: C,
+    mutation_method: M,
+}
+
+impl<C, M> Nsga2<C, M>
+where
+    C: CrossoverMethod,
+    M: MutationMethod,
+{
+    pub fn new(crossover_method: C, mutation_method: M) -> Self {
+        Self {
+            crossover_method,
+            mutation_method,
+        }
+    }
+
+    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+    where
+        I: MultiObjectiveIndividual,
+    {
+        self.try_evolve(rng, population)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Same as `evolve()`, but returns an error instead of panicking - e.g.
+    /// when the population is empty or when individuals disagree on the
+    /// number of objectives.
+    pub fn try_evolve<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Statistics), Error>
+    where
+        I: MultiObjectiveIndividual,
+    {
+        if population.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
+
+        let objectives: Vec<_> = population.iter().map(|i| i.objectives()).collect();
+
+        for individual in &objectives {
+            if individual.len() != objectives[0].len() {
+                return Err(Error::LengthMismatch {
+                    what: "objectives",
+                    expected: objectives[0].len(),
+                    found: individual.len(),
+                });
+            }
+        }
+        let fronts = fast_non_dominated_sort(&objectives);
+
+        // (rank, crowding distance) of every individual
+        let mut scores = vec![(0, 0.0); population.len()];
+        for (rank, front) in fronts.iter().enumerate() {
+            for (&idx, distance) in front.iter().zip(crowding_distance(&objectives, front)) {
+                scores[idx] = (rank, distance);
+            }
+        }
+
+        let survivors = population.len().div_ceil(2);
+        let mut ranked: Vec<_> = (0..population.len()).collect();
+        ranked.sort_by(|&a, &b| crowded_comparison(scores[a], scores[b]));
+        ranked.truncate(survivors);
+
+        let offspring: Vec<_> = (survivors..population.len())
+            .map(|_| {
+                let parent_a = &population[tournament(rng, &ranked, &scores)];
+                let parent_b = &population[tournament(rng, &ranked, &scores)];
+                I::create(breed(
+                    rng,
+                    &self.crossover_method,
+                    &self.mutation_method,
+                    parent_a.chromosome(),
+                    parent_b.chromosome(),
+                ))
+            })
+            .collect();
+
+        let new_population = ranked
+            .iter()
+            .map(|&idx| I::create(population[idx].chromosome().clone()))
+            .chain(offspring)
+            .collect();
+
+        let pareto_front = fronts[0]
+            .iter()
+            .map(|&idx| objectives[idx].clone())
+            .collect();
+
+        let stats = Statistics {
+            pareto_front,
+            ..Statistics::new(population)
+        };
+
+        Ok((new_population, stats))
+    }
+}
+
+/// Returns whether `a` is at least as good as `b` in every objective and
+/// strictly better in at least one.
+pub fn dominates(a: &[f32], b: &[f32]) -> bool {
+    assert_eq!(a.len(), b.len());
+
+    a.iter().zip(b).all(|(a, b)| a >= b) && a.iter().zip(b).any(|(a, b)| a > b)
+}
+
+/// Splits the population into Pareto fronts, best front first; each front
+/// contains indices into `objectives`.
+pub fn fast_non_dominated_sort(objectives: &[Vec<f32>]) -> Vec<Vec<usize>> {
+    let len = objectives.len();
+
+    // For each individual: whom it dominates and by how many it is dominated
+    let mut dominated = vec![Vec::new(); len];
+    let mut domination_count = vec![0; len];
+    let mut fronts = vec![Vec::new()];
+
+    for p in 0..len {
+        for q in 0..len {
+            if dominates(&objectives[p], &objectives[q]) {
+                dominated[p].push(q);
+            } else if dominates(&objectives[q], &objectives[p]) {
+                domination_count[p] += 1;
+            }
+        }
+
+        if domination_count[p] == 0 {
+            fronts[0].push(p);
+        }
+    }
+
+    loop {
+        let mut next = Vec::new();
+
+        for &p in fronts.last().unwrap() {
+            for &q in &dominated[p] {
+                domination_count[q] -= 1;
+
+                if domination_count[q] == 0 {
+                    next.push(q);
+                }
+            }
+        }
+
+        if next.is_empty() {
+            break;
+        }
+
+        next.sort_unstable();
+        fronts.push(next);
+    }
+
+    fronts.retain(|front| !front.is_empty());
+    fronts
+}
+
+/// Computes crowding distance of every member of `front`; boundary
+/// individuals get `f32::INFINITY`, so they are always preferred.
+pub fn crowding_distance(objectives: &[Vec<f32>], front: &[usize]) -> Vec<f32> {
+    let mut distances = vec![0.0; front.len()];
+
+    if front.len() <= 2 {
+        distances.fill(f32::INFINITY);
+        return distances;
+    }
+
+    let dimensions = objectives[front[0]].len();
+    let mut order: Vec<_> = (0..front.len()).collect();
+
+    for m in 0..dimensions {
+        let value = |i: usize| objectives[front[i]][m];
+
+        order.sort_by(|&a, &b| value(a).total_cmp(&value(b)));
+
+        let first = order[0];
+        let last = order[front.len() - 1];
+        let range = value(last) - value(first);
+
+        distances[first] = f32::INFINITY;
+        distances[last] = f32::INFINITY;
+
+        if range <= 0.0 {
+            continue;
+        }
+
+        for w in order.windows(3) {
+            distances[w[1]] += (value(w[2]) - value(w[0])) / range;
+        }
+    }
+
+    distances
+}
+
+/// Lower rank wins; within the same rank, the less crowded individual wins.
+fn crowded_comparison(a: (usize, f32), b: (usize, f32)) -> Ordering {
+    a.0.cmp(&b.0).then(b.1.total_cmp(&a.1))
+}
+
+fn tournament(rng: &mut dyn RngCore, candidates: &[usize], scores: &[(usize, f32)]) -> usize {
+    let a = candidates[rng.gen_range(0..candidates.len())];
+    let b = candidates[rng.gen_range(0..candidates.len())];
+
+    if crowded_comparison(scores[a], scores[b]) == Ordering::Greater {
+        b
+    } else {
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Chromosome, Individual, UniformCrossover, UniformPerturbationMutation};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn dominance() {
+        assert!(dominates(&[1.0, 2.0], &[1.0, 1.0]));
+        assert!(!dominates(&[1.0, 1.0], &[1.0, 1.0]));
+        assert!(!dominates(&[2.0, 0.0], &[1.0, 1.0]));
+    }
+
+    #[test]
+    fn non_dominated_sort() {
+        let objectives = vec![
+            vec![1.0, 1.0], // 0: dominated by 1 and 2
+            vec![3.0, 1.0], // 1: front 0
+            vec![1.0, 3.0], // 2: front 0
+            vec![0.0, 0.0], // 3: dominated by everyone
+            vec![2.0, 2.0], // 4: front 0
+        ];
+
+        let fronts = fast_non_dominated_sort(&objectives);
+
+        assert_eq!(fronts, vec![vec![1, 2, 4], vec![0], vec![3]]);
+    }
+
+    #[test]
+    fn crowding() {
+        let objectives = vec![
+            vec![0.0, 4.0],
+            vec![1.0, 3.0],
+            vec![3.0, 1.0],
+            vec![4.0, 0.0],
+        ];
+
+        let distances = crowding_distance(&objectives, &[0, 1, 2, 3]);
+
+        assert_eq!(distances[0], f32::INFINITY);
+        assert_eq!(distances[3], f32::INFINITY);
+        approx::assert_relative_eq!(distances[1], 1.5);
+        approx::assert_relative_eq!(distances[2], 1.5);
+    }
+
+    /// Classic trade-off: the more genes sum up to, the worse the second
+    /// objective gets.
+    #[derive(Debug)]
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn fitness(&self) -> f32 {
+            self.objectives().iter().sum()
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+    }
+
+    impl MultiObjectiveIndividual for TestIndividual {
+        fn objectives(&self) -> Vec<f32> {
+            let sum: f32 = self.chromosome.iter().sum();
+            vec![sum, -sum * sum]
+        }
+    }
+
+    #[test]
+    fn nsga2() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let nsga = Nsga2::new(UniformCrossover, UniformPerturbationMutation::new(0.5, 0.5));
+
+        let mut population: Vec<_> = (0..8)
+            .map(|n| TestIndividual::create(vec![n as f32 * 0.1, -0.2].into_iter().collect()))
+            .collect();
+
+        let mut stats = None;
+
+        for _ in 0..10 {
+            let (next, s) = nsga.evolve(&mut rng, &population);
+            population = next;
+            stats = Some(s);
+        }
+
+        assert_eq!(population.len(), 8);
+
+        let front = stats.unwrap().pareto_front;
+        assert!(!front.is_empty());
+
+        // No member of the reported front may be dominated by another one
+        for a in &front {
+            assert!(front.iter().all(|b| !dominates(b, a)));
+        }
+    }
+
+    #[test]
+    fn empty_population() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let nsga = Nsga2::new(UniformCrossover, UniformPerturbationMutation::new(0.5, 0.5));
+
+        assert_eq!(
+            nsga.try_evolve::<TestIndividual>(&mut rng, &[])
+                .unwrap_err(),
+            Error::EmptyPopulation
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{Error, Statistics};
+
+/// Adaptive operator selection, treated as a multi-armed bandit solved with
+/// UCB1 (Auer et al., 2002): each generation uses one operator, which gets
+/// rewarded with the fitness gain of the offspring it has produced.
+///
+/// UCB1 is deterministic, which is handy, since `adapt()` doesn't get
+/// access to any random number generator.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct OperatorSelection {
+    /// Sum of rewards of each operator.
+    rewards: Vec<f32>,
+
+    /// How many times each operator got rewarded.
+    uses: Vec<usize>,
+
+    /// Balance between exploiting the best operator so far and exploring
+    /// other ones; should be roughly of the magnitude of fitness gains.
+    exploration: f32,
+
+    /// Operator used for breeding right now.
+    current: usize,
+
+    /// Operator that has bred the population we'll get statistics of next.
+    breeder: Option<usize>,
+
+    /// Average fitness of the previous generation.
+    last_avg_fitness: Option<f32>,
+}
+
+impl OperatorSelection {
+    pub fn try_new(operators: usize, exploration: f32) -> Result<Self, Error> {
+        Error::check("number of operators", operators as f32, 1.0.., "1..")?;
+        Error::check("exploration", exploration, 0.0.., "0.0..")?;
+
+        Ok(Self {
+            rewards: vec![0.0; operators],
+            uses: vec![0; operators],
+            exploration,
+            current: 0,
+            breeder: None,
+            last_avg_fitness: None,
+        })
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Average reward of each operator (zero for the ones never used).
+    pub fn scores(&self) -> Vec<f32> {
+        self.rewards
+            .iter()
+            .zip(&self.uses)
+            .map(|(&reward, &uses)| if uses > 0 { reward / uses as f32 } else { 0.0 })
+            .collect()
+    }
+
+    pub fn adapt(&mut self, stats: &Statistics) {
+        // `stats` describe the population bred by `self.breeder` out of the
+        // population whose average fitness is `self.last_avg_fitness`
+        if let (Some(breeder), Some(last)) = (self.breeder, self.last_avg_fitness) {
+            self.rewards[breeder] += stats.avg_fitness - last;
+            self.uses[breeder] += 1;
+        }
+
+        self.last_avg_fitness = Some(stats.avg_fitness);
+        self.breeder = Some(self.current);
+        self.current = self.choose();
+    }
+
+    fn choose(&self) -> usize {
+        // Try every operator at least once
+        if let Some(unused) = self.uses.iter().position(|&uses| uses == 0) {
+            if Some(unused) != self.breeder {
+                return unused;
+            }
+        }
+
+        let total = self.uses.iter().sum::<usize>().max(1) as f32;
+
+        let bound = |idx: usize| {
+            let uses = self.uses[idx].max(1) as f32;
+            self.rewards[idx] / uses + self.exploration * (2.0 * total.ln() / uses).sqrt()
+        };
+
+        (0..self.rewards.len())
+            .max_by(|&a, &b| bound(a).total_cmp(&bound(b)))
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(avg_fitness: f32) -> Statistics {
+        Statistics {
+            min_fitness: 0.0,
+            max_fitness: 0.0,
+            avg_fitness,
+            diversity: 0.0,
+            pareto_front: Vec::new(),
+            species: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn prefers_operator_with_higher_gains() {
+        let mut selection = OperatorSelection::try_new(2, 0.1).unwrap();
+        let mut avg_fitness = 0.0;
+
+        for _ in 0..50 {
+            // Operator #1 improves the population ten times faster
+            let bred_by = selection.breeder;
+            avg_fitness += match bred_by {
+                Some(1) => 1.0,
+                Some(_) => 0.1,
+                None => 0.0,
+            };
+
+            selection.adapt(&stats(avg_fitness));
+        }
+
+        let scores = selection.scores();
+        assert!(scores[1] > scores[0]);
+        assert!(selection.uses[1] > selection.uses[0] * 5);
+    }
+}
@@ -0,0 +1,211 @@
+use std::ops::Bound;
+
+use rand::{Rng, RngCore};
+
+use crate::{Chromosome, Error, Individual, Optimizer, Statistics};
+
+/// Particle swarm optimization (Kennedy & Eberhart, 1995), with inertia
+/// weight (Shi & Eberhart, 1998).
+///
+/// Chromosomes are treated as positions of particles flying through the
+/// search space; each particle is pulled towards the best position it has
+/// found itself and towards the best position found by the whole swarm.
+///
+/// The population passed to `evolve()` must consist of the particles
+/// returned by the previous call, in the same order.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParticleSwarm {
+    /// How much of its velocity a particle keeps; usually within
+    /// `<0.4, 0.9>`.
+    inertia: f32,
+
+    /// Pull towards particle's own best position.
+    cognitive: f32,
+
+    /// Pull towards swarm's best position.
+    social: f32,
+
+    /// Maximum velocity along any axis, keeps the swarm from exploding.
+    max_velocity: f32,
+
+    particles: Vec<Particle>,
+
+    /// Best position ever found by the swarm, with its fitness.
+    best: Option<(Chromosome, f32)>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Particle {
+    velocity: Vec<f32>,
+    best: (Chromosome, f32),
+}
+
+impl ParticleSwarm {
+    pub fn new(inertia: f32, cognitive: f32, social: f32, max_velocity: f32) -> Self {
+        Self::try_new(inertia, cognitive, social, max_velocity)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(
+        inertia: f32,
+        cognitive: f32,
+        social: f32,
+        max_velocity: f32,
+    ) -> Result<Self, Error> {
+        let positive = (Bound::Excluded(0.0), Bound::Unbounded);
+
+        Error::check("inertia", inertia, 0.0.., "0.0..")?;
+        Error::check("cognitive coefficient", cognitive, 0.0.., "0.0..")?;
+        Error::check("social coefficient", social, 0.0.., "0.0..")?;
+        Error::check("max velocity", max_velocity, positive, "(0.0, inf)")?;
+
+        Ok(Self {
+            inertia,
+            cognitive,
+            social,
+            max_velocity,
+            particles: Vec::new(),
+            best: None,
+        })
+    }
+
+    /// Best position ever found by the swarm, with its fitness.
+    pub fn best(&self) -> Option<&(Chromosome, f32)> {
+        self.best.as_ref()
+    }
+
+    fn remember_bests<I>(&mut self, population: &[I])
+    where
+        I: Individual,
+    {
+        if self.particles.len() != population.len() {
+            self.particles = population
+                .iter()
+                .map(|individual| Particle {
+                    velocity: vec![0.0; individual.chromosome().len()],
+                    best: (individual.chromosome().clone(), individual.fitness()),
+                })
+                .collect();
+        }
+
+        for (particle, individual) in self.particles.iter_mut().zip(population) {
+            let fitness = individual.fitness();
+
+            if fitness > particle.best.1 {
+                particle.best = (individual.chromosome().clone(), fitness);
+            }
+
+            if self.best.as_ref().map_or(true, |best| fitness > best.1) {
+                self.best = Some((individual.chromosome().clone(), fitness));
+            }
+        }
+    }
+}
+
+impl<I> Optimizer<I> for ParticleSwarm
+where
+    I: Individual,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics) {
+        assert!(!population.is_empty());
+
+        self.remember_bests(population);
+
+        let swarm_best = &self.best.as_ref().unwrap().0;
+
+        let new_population = self
+            .particles
+            .iter_mut()
+            .zip(population)
+            .map(|(particle, individual)| {
+                let position = individual.chromosome();
+
+                let genes = particle
+                    .velocity
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(j, velocity)| {
+                        let r1: f32 = rng.gen();
+                        let r2: f32 = rng.gen();
+
+                        *velocity = self.inertia * *velocity
+                            + self.cognitive * r1 * (particle.best.0[j] - position[j])
+                            + self.social * r2 * (swarm_best[j] - position[j]);
+
+                        *velocity = velocity.clamp(-self.max_velocity, self.max_velocity);
+
+                        position[j] + *velocity
+                    })
+                    .collect();
+
+                I::create(genes)
+            })
+            .collect();
+
+        (new_population, Statistics::new(population))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Negated sphere function, with the optimum at (1, 1, ...)
+    #[derive(Debug)]
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn fitness(&self) -> f32 {
+            -self
+                .chromosome
+                .iter()
+                .map(|g| (g - 1.0).powi(2))
+                .sum::<f32>()
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+    }
+
+    fn run() -> (Vec<f32>, f32) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut pso = ParticleSwarm::new(0.7, 1.5, 1.5, 1.0);
+
+        let mut population: Vec<_> = (0..20)
+            .map(|_| TestIndividual::create((0..5).map(|_| rng.gen_range(-5.0..5.0)).collect()))
+            .collect();
+
+        let mut history = Vec::new();
+
+        for _ in 0..150 {
+            let (new_population, stats) = pso.evolve(&mut rng, &population);
+            history.push(stats.max_fitness);
+            population = new_population;
+        }
+
+        (history, pso.best().unwrap().1)
+    }
+
+    #[test]
+    fn converges() {
+        let (_, best) = run();
+
+        assert!(best > -1e-3);
+    }
+
+    #[test]
+    fn is_reproducible() {
+        assert_eq!(run().0, run().0);
+    }
+}
@@ -1,16 +1,37 @@
-use crate::{Individual, SelectionMethod};
+use crate::{Error, Individual, SelectionMethod};
+use rand::distributions::WeightedError;
 use rand::{seq::SliceRandom, RngCore};
 
+mod tournament;
+
+pub use tournament::TournamentSelection;
+
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RouletteWheelSelection;
 impl SelectionMethod for RouletteWheelSelection {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
     where
-        I: Individual,
+        I: Individual<G>,
+    {
+        self.try_select(rng, population)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_select<'a, I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+    ) -> Result<&'a I, Error>
+    where
+        I: Individual<G>,
     {
         population
             .choose_weighted(rng, |individual| individual.fitness())
-            .expect("получена пустая популяция")
+            .map_err(|err| match err {
+                WeightedError::NoItem => Error::EmptyPopulation,
+                _ => Error::InvalidFitness,
+            })
     }
 }
 
@@ -72,4 +93,22 @@ mod tests {
 
         assert_eq!(actual_histogram, expected_histogram);
     }
+
+    #[test]
+    fn roulette_wheel_selection_errors() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let empty: Vec<TestIndividual> = Vec::new();
+        let starving = vec![TestIndividual::new(0.0), TestIndividual::new(0.0)];
+
+        assert_eq!(
+            RouletteWheelSelection.try_select(&mut rng, &empty).err(),
+            Some(Error::EmptyPopulation)
+        );
+
+        assert_eq!(
+            RouletteWheelSelection.try_select(&mut rng, &starving).err(),
+            Some(Error::InvalidFitness)
+        );
+    }
 }
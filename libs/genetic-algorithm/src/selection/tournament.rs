@@ -0,0 +1,108 @@
+use crate::{Error, Individual, SelectionMethod};
+use rand::{seq::SliceRandom, RngCore};
+
+/// Picks `size` random individuals (with replacement) and returns the
+/// fittest one among them.
+///
+/// Contrary to `RouletteWheelSelection`, only the ordering of fitnesses
+/// matters - so it works with negative fitnesses, too, and its selection
+/// pressure can be tuned through `size`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TournamentSelection {
+    size: usize,
+}
+
+impl TournamentSelection {
+    pub fn new(size: usize) -> Self {
+        Self::try_new(size).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(size: usize) -> Result<Self, Error> {
+        Error::check("tournament size", size as f32, 1.0.., "1..")?;
+
+        Ok(Self { size })
+    }
+}
+
+impl SelectionMethod for TournamentSelection {
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual<G>,
+    {
+        (0..self.size)
+            .map(|_| population.choose(rng).expect("получена пустая популяция"))
+            .reduce(|best, individual| {
+                if individual.fitness() > best.fitness() {
+                    individual
+                } else {
+                    best
+                }
+            })
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Chromosome;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::BTreeMap;
+
+    struct TestIndividual {
+        fitness: f32,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(_: Chromosome) -> Self {
+            unimplemented!()
+        }
+
+        fn fitness(&self) -> f32 {
+            self.fitness
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            unimplemented!()
+        }
+    }
+
+    fn histogram(size: usize) -> BTreeMap<i32, usize> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population: Vec<_> = [2.0, 1.0, -4.0, 3.0]
+            .into_iter()
+            .map(|fitness| TestIndividual { fitness })
+            .collect();
+
+        let mut histogram = BTreeMap::new();
+
+        for _ in 0..1000 {
+            let fitness = TournamentSelection::new(size)
+                .select(&mut rng, &population)
+                .fitness() as i32;
+
+            *histogram.entry(fitness).or_insert(0) += 1;
+        }
+
+        histogram
+    }
+
+    #[test]
+    fn single_contestant_is_uniform() {
+        assert_eq!(
+            histogram(1),
+            BTreeMap::from_iter([(-4, 254), (1, 262), (2, 246), (3, 238)])
+        );
+    }
+
+    #[test]
+    fn larger_tournaments_favour_the_fittest() {
+        assert_eq!(
+            histogram(3),
+            BTreeMap::from_iter([(-4, 23), (1, 120), (2, 309), (3, 548)])
+        );
+    }
+}
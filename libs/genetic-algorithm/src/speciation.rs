@@ -0,0 +1,450 @@
+use std::ops::Bound;
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::{Rng, RngCore};
+
+use crate::genetic_algorithm::breed;
+use crate::{CrossoverMethod, Error, Individual, MutationMethod, Optimizer, Statistics};
+
+/// Fitness sharing (Goldberg & Richardson, 1987): individuals crowded
+/// together split their fitness between each other, so a lonely mutant
+/// is worth more than yet another copy of the current champion.
+#[derive(Clone, Debug)]
+pub struct FitnessSharing {
+    /// Chromosomes further apart than this don't share anything.
+    radius: f32,
+
+    /// Shape of the sharing function:
+    /// - 1.0 = sharing decreases linearly with distance
+    /// - >1.0 = only really close neighbours share a lot
+    alpha: f32,
+}
+
+impl FitnessSharing {
+    pub fn new(radius: f32, alpha: f32) -> Self {
+        Self::try_new(radius, alpha).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(radius: f32, alpha: f32) -> Result<Self, Error> {
+        let positive = (Bound::Excluded(0.0), Bound::Unbounded);
+
+        Error::check("sharing radius", radius, positive, "(0.0, inf)")?;
+        Error::check("sharing alpha", alpha, positive, "(0.0, inf)")?;
+
+        Ok(Self { radius, alpha })
+    }
+
+    /// Sharing only makes sense for non-negative fitness, so when some is
+    /// negative (e.g. when minimising), all of it gets shifted first, so
+    /// that the worst individual scores zero.
+    pub fn shared_fitness<I>(&self, population: &[I]) -> Vec<f32>
+    where
+        I: Individual,
+    {
+        let offset = population
+            .iter()
+            .map(Individual::fitness)
+            .fold(0.0, f32::min);
+
+        population
+            .iter()
+            .map(|individual| {
+                let niche_count: f32 = population
+                    .iter()
+                    .map(|other| self.sharing(individual.chromosome().distance(other.chromosome())))
+                    .sum();
+
+                // `niche_count` is at least 1.0, since everybody shares
+                // with themselves
+                (individual.fitness() - offset) / niche_count
+            })
+            .collect()
+    }
+
+    fn sharing(&self, distance: f32) -> f32 {
+        if distance < self.radius {
+            1.0 - (distance / self.radius).powf(self.alpha)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Genetic algorithm that clusters the population into species and lets
+/// each species breed on its own, with the number of offspring allocated
+/// proportionally to species' shared fitness.
+///
+/// Selection is fitness-proportionate on the shared fitness, as that's
+/// what fitness sharing has been designed for.
+#[derive(Debug)]
+pub struct SpeciatedGeneticAlgorithm<C, M> {
+    crossover_method: C,
+    mutation_method: M,
+    sharing: FitnessSharing,
+
+    /// Maximum distance between an individual and species' representative
+    /// (its first member) for the individual to join that species.
+    threshold: f32,
+}
+
+impl<C, M> SpeciatedGeneticAlgorithm<C, M>
+where
+    C: CrossoverMethod,
+    M: MutationMethod,
+{
+    pub fn new(
+        crossover_method: C,
+        mutation_method: M,
+        sharing: FitnessSharing,
+        threshold: f32,
+    ) -> Self {
+        Self::try_new(crossover_method, mutation_method, sharing, threshold)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(
+        crossover_method: C,
+        mutation_method: M,
+        sharing: FitnessSharing,
+        threshold: f32,
+    ) -> Result<Self, Error> {
+        let positive = (Bound::Excluded(0.0), Bound::Unbounded);
+
+        Error::check("species threshold", threshold, positive, "(0.0, inf)")?;
+
+        Ok(Self {
+            crossover_method,
+            mutation_method,
+            sharing,
+            threshold,
+        })
+    }
+
+    /// Clusters population into species; each species is a list of
+    /// indices into `population`.
+    pub fn species<I>(&self, population: &[I]) -> Vec<Vec<usize>>
+    where
+        I: Individual,
+    {
+        let mut species: Vec<Vec<usize>> = Vec::new();
+
+        for (idx, individual) in population.iter().enumerate() {
+            let home = species.iter_mut().find(|members| {
+                let representative = population[members[0]].chromosome();
+                individual.chromosome().distance(representative) < self.threshold
+            });
+
+            match home {
+                Some(members) => members.push(idx),
+                None => species.push(vec![idx]),
+            }
+        }
+
+        species
+    }
+
+    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+    where
+        I: Individual,
+    {
+        self.try_evolve(rng, population)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Same as `evolve()`, but returns an error instead of panicking - e.g.
+    /// when the population is empty.
+    pub fn try_evolve<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Statistics), Error>
+    where
+        I: Individual,
+    {
+        if population.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
+
+        let species = self.species(population);
+        let shared_fitness = self.sharing.shared_fitness(population);
+
+        let scores: Vec<f32> = species
+            .iter()
+            .map(|members| members.iter().map(|&idx| shared_fitness[idx]).sum())
+            .collect();
+
+        let offspring = allocate(&scores, population.len());
+
+        let new_population = species
+            .iter()
+            .zip(offspring)
+            .flat_map(|(members, count)| {
+                let weights: Vec<_> = members.iter().map(|&idx| shared_fitness[idx]).collect();
+                let selection = WeightedIndex::new(weights).ok();
+
+                let select = move |rng: &mut dyn RngCore| {
+                    let pick = match &selection {
+                        Some(selection) => selection.sample(rng),
+                        None => rng.gen_range(0..members.len()),
+                    };
+
+                    &population[members[pick]]
+                };
+
+                (0..count)
+                    .map(|_| {
+                        let parent_a = select(rng);
+                        let parent_b = select(rng);
+                        I::create(breed(
+                            rng,
+                            &self.crossover_method,
+                            &self.mutation_method,
+                            parent_a.chromosome(),
+                            parent_b.chromosome(),
+                        ))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let stats = Statistics {
+            species: species.iter().map(|members| members.len()).collect(),
+            ..Statistics::new(population)
+        };
+
+        Ok((new_population, stats))
+    }
+}
+
+impl<C, M, I> Optimizer<I> for SpeciatedGeneticAlgorithm<C, M>
+where
+    C: CrossoverMethod,
+    M: MutationMethod,
+    I: Individual,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics) {
+        SpeciatedGeneticAlgorithm::evolve(self, rng, population)
+    }
+
+    fn try_evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Statistics), Error> {
+        SpeciatedGeneticAlgorithm::try_evolve(self, rng, population)
+    }
+}
+
+/// Splits `total` offspring proportionally to `scores`, using the largest
+/// remainder method, so that the counts always add up to `total`.
+///
+/// Negative scores count as zero; when no species scores anything (or the
+/// scores overflow), all of them get the same share.
+fn allocate(scores: &[f32], total: usize) -> Vec<usize> {
+    let scores: Vec<f32> = scores.iter().map(|score| score.max(0.0)).collect();
+    let sum: f32 = scores.iter().sum();
+
+    let quotas: Vec<f32> = if sum > 0.0 && sum.is_finite() {
+        scores
+            .iter()
+            .map(|score| score / sum * total as f32)
+            .collect()
+    } else {
+        vec![total as f32 / scores.len() as f32; scores.len()]
+    };
+
+    let mut counts: Vec<_> = quotas.iter().map(|quota| quota.floor() as usize).collect();
+
+    // Rounding errors can make the quotas add up to a bit more than
+    // `total`, in which case the largest ones get trimmed
+    while counts.iter().sum::<usize>() > total {
+        let largest = (0..counts.len()).max_by_key(|&idx| counts[idx]).unwrap();
+        counts[largest] -= 1;
+    }
+
+    let missing = total.saturating_sub(counts.iter().sum::<usize>());
+
+    let mut by_remainder: Vec<_> = (0..quotas.len()).collect();
+    by_remainder.sort_by(|&a, &b| {
+        (quotas[b] - quotas[b].floor()).total_cmp(&(quotas[a] - quotas[a].floor()))
+    });
+
+    for &idx in by_remainder.iter().take(missing) {
+        counts[idx] += 1;
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Chromosome, UniformCrossover, UniformPerturbationMutation};
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[derive(Debug)]
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn fitness(&self) -> f32 {
+            self.chromosome.iter().map(|gene| gene.abs()).sum()
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+    }
+
+    /// Individual whose fitness is its (only) gene, sign included.
+    #[derive(Debug)]
+    struct SignedIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for SignedIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn fitness(&self) -> f32 {
+            self.chromosome[0]
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+    }
+
+    fn signed_population(genes: &[f32]) -> Vec<SignedIndividual> {
+        genes
+            .iter()
+            .map(|&gene| SignedIndividual::create(vec![gene].into_iter().collect()))
+            .collect()
+    }
+
+    fn population(genes: &[f32]) -> Vec<TestIndividual> {
+        genes
+            .iter()
+            .map(|&gene| TestIndividual::create(vec![gene].into_iter().collect()))
+            .collect()
+    }
+
+    fn algorithm() -> SpeciatedGeneticAlgorithm<UniformCrossover, UniformPerturbationMutation> {
+        SpeciatedGeneticAlgorithm::new(
+            UniformCrossover,
+            UniformPerturbationMutation::new(0.5, 0.1),
+            FitnessSharing::new(1.0, 1.0),
+            1.0,
+        )
+    }
+
+    #[test]
+    fn shared_fitness() {
+        // Two individuals crowded together and a lonely one
+        let population = population(&[2.0, 2.5, 10.0]);
+        let actual = FitnessSharing::new(1.0, 1.0).shared_fitness(&population);
+
+        assert_relative_eq!(actual.as_slice(), [2.0 / 1.5, 2.5 / 1.5, 10.0].as_slice());
+    }
+
+    #[test]
+    fn species() {
+        let population = population(&[0.0, 5.0, 0.5, 5.2, 20.0]);
+        let actual = algorithm().species(&population);
+
+        assert_eq!(actual, vec![vec![0, 2], vec![1, 3], vec![4]]);
+    }
+
+    #[test]
+    fn allocation() {
+        assert_eq!(allocate(&[1.0, 1.0, 2.0], 8), vec![2, 2, 4]);
+        assert_eq!(allocate(&[1.0, 1.0, 1.0], 10), vec![4, 3, 3]);
+        assert_eq!(allocate(&[0.0, 0.0], 3), vec![2, 1]);
+        assert_eq!(allocate(&[3.0, -1.0], 10), vec![10, 0]);
+        assert_eq!(allocate(&[-3.0, -1.0], 5), vec![3, 2]);
+        assert_eq!(allocate(&[1.0, -1.0, 1.0], 7), vec![4, 0, 3]);
+        assert_eq!(allocate(&[f32::MAX, f32::MAX], 3).iter().sum::<usize>(), 3);
+    }
+
+    #[test]
+    fn shared_negative_fitness() {
+        // Same as `shared_fitness`, but minimising - everybody gets shifted
+        // so that the worst individual scores zero
+        let population = signed_population(&[-2.0, -2.5, -10.0]);
+        let actual = FitnessSharing::new(1.0, 1.0).shared_fitness(&population);
+
+        assert_relative_eq!(actual.as_slice(), [8.0 / 1.5, 7.5 / 1.5, 0.0].as_slice());
+    }
+
+    #[test]
+    fn evolve() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let algorithm = algorithm();
+
+        let population = population(&[1.0, 1.2, 1.1, 1.3, 1.2, 1.1, 1.0, -8.0]);
+        let (new_population, stats) = algorithm.evolve(&mut rng, &population);
+
+        assert_eq!(new_population.len(), population.len());
+        assert_eq!(stats.species, vec![7, 1]);
+
+        // Without sharing, the lonely -8.0 would get only about half of the
+        // offspring; with sharing it gets almost all of them, since the
+        // crowded species splits its fitness seven ways
+        let descendants = new_population
+            .iter()
+            .filter(|individual| individual.chromosome[0] < -4.0)
+            .count();
+
+        assert_eq!(descendants, 7);
+    }
+
+    #[test]
+    fn evolve_mixed_sign_fitness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let algorithm = algorithm();
+
+        // Three species: a negative one, a barely positive one and a
+        // strong one
+        let population = signed_population(&[-3.0, 0.25, 0.5, 0.75, 20.0, 20.5]);
+
+        for _ in 0..10 {
+            let (new_population, stats) = algorithm.evolve(&mut rng, &population);
+
+            assert_eq!(new_population.len(), population.len());
+            assert_eq!(stats.species, vec![1, 3, 2]);
+
+            // The worst species scores zero, so it doesn't breed at all
+            assert!(new_population
+                .iter()
+                .all(|individual| individual.chromosome[0] > -2.0));
+        }
+    }
+
+    #[test]
+    fn evolve_negative_fitness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let algorithm = algorithm();
+
+        let population = signed_population(&[-1.0, -1.2, -1.1, -8.0, -8.2]);
+        let (new_population, stats) = algorithm.evolve(&mut rng, &population);
+
+        assert_eq!(new_population.len(), population.len());
+        assert_eq!(stats.species, vec![3, 2]);
+
+        // When minimising, the species closer to zero is the better one
+        let descendants = new_population
+            .iter()
+            .filter(|individual| individual.chromosome[0] > -4.0)
+            .count();
+
+        assert_eq!(descendants, 5);
+    }
+}
@@ -1,16 +1,31 @@
-use crate::Individual;
+use crate::{Gene, Individual};
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Statistics {
     pub min_fitness: f32,
     pub max_fitness: f32,
     pub avg_fitness: f32,
+
+    /// Average distance between chromosomes and their centroid; when it
+    /// drops close to zero, the population has converged (or collapsed).
+    pub diversity: f32,
+
+    /// Objective vectors of the non-dominated individuals; filled only by
+    /// multi-objective algorithms such as `Nsga2`.
+    pub pareto_front: Vec<Vec<f32>>,
+
+    /// Sizes of the species the population has been clustered into (so
+    /// `species.len()` is the number of species); filled only by
+    /// `SpeciatedGeneticAlgorithm`.
+    pub species: Vec<usize>,
 }
 
 impl Statistics {
-    pub fn new<I>(population: &[I]) -> Self
+    pub fn new<I, G>(population: &[I]) -> Self
     where
-        I: Individual,
+        I: Individual<G>,
+        G: Gene,
     {
         assert!(!population.is_empty());
 
@@ -30,6 +45,96 @@ impl Statistics {
             min_fitness,
             max_fitness,
             avg_fitness: sum_fitness / (population.len() as f32),
+            diversity: diversity(population),
+            pareto_front: Vec::new(),
+            species: Vec::new(),
+        }
+    }
+}
+fn diversity<I, G>(population: &[I]) -> f32
+where
+    I: Individual<G>,
+    G: Gene,
+{
+    let genes = population[0].chromosome().len();
+    let mut centroid = vec![0.0; genes];
+
+    for individual in population {
+        for (sum, gene) in centroid.iter_mut().zip(individual.chromosome().iter()) {
+            *sum += gene.value();
+        }
+    }
+
+    for sum in &mut centroid {
+        *sum /= population.len() as f32;
+    }
+
+    population
+        .iter()
+        .map(|individual| {
+            individual
+                .chromosome()
+                .iter()
+                .zip(&centroid)
+                .map(|(gene, mean)| (gene.value() - mean).powi(2))
+                .sum::<f32>()
+                .sqrt()
+        })
+        .sum::<f32>()
+        / (population.len() as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Chromosome;
+    use approx::assert_relative_eq;
+
+    struct TestIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for TestIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn fitness(&self) -> f32 {
+            self.chromosome.iter().sum()
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
         }
     }
-}
\ No newline at end of file
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::create(genes.iter().copied().collect())
+    }
+
+    #[test]
+    fn statistics() {
+        let population = vec![
+            individual(&[0.0, 0.0]),
+            individual(&[3.0, 4.0]),
+            individual(&[0.0, 4.0]),
+            individual(&[3.0, 0.0]),
+        ];
+
+        let stats = Statistics::new(&population);
+
+        assert_relative_eq!(stats.min_fitness, 0.0);
+        assert_relative_eq!(stats.max_fitness, 7.0);
+        assert_relative_eq!(stats.avg_fitness, 3.5);
+
+        // Every chromosome lies 2.5 away from the centroid at (1.5, 2.0)
+        assert_relative_eq!(stats.diversity, 2.5);
+    }
+
+    #[test]
+    fn converged_population_has_no_diversity() {
+        let population = vec![individual(&[1.0, 2.0]), individual(&[1.0, 2.0])];
+
+        assert_relative_eq!(Statistics::new(&population).diversity, 0.0);
+    }
+}
@@ -0,0 +1,259 @@
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+use crate::Statistics;
+
+/// Decides when `Evolution` should stop.
+///
+/// Called once per generation, right after the population has been
+/// evolved; criteria are allowed to keep state (e.g. the best fitness seen
+/// so far), which is why they get `&mut self`.
+pub trait TerminationCriterion {
+    /// Called once at the beginning of a run, before the first generation
+    /// gets evolved (see `Monitor::start()`).
+    fn start(&mut self) {}
+
+    /// `generation` is the number of generations evolved so far (so it's
+    /// 1 on the first call).
+    fn should_stop(&mut self, generation: usize, stats: &Statistics) -> bool;
+}
+
+/// Stops after given number of generations.
+#[derive(Clone, Debug)]
+pub struct MaxGenerations {
+    generations: usize,
+}
+
+impl MaxGenerations {
+    pub fn new(generations: usize) -> Self {
+        assert!(generations > 0);
+
+        Self { generations }
+    }
+}
+
+impl TerminationCriterion for MaxGenerations {
+    fn should_stop(&mut self, generation: usize, _: &Statistics) -> bool {
+        generation >= self.generations
+    }
+}
+
+/// Stops once any individual has reached given fitness.
+#[derive(Clone, Debug)]
+pub struct TargetFitness {
+    fitness: f32,
+}
+
+impl TargetFitness {
+    pub fn new(fitness: f32) -> Self {
+        Self { fitness }
+    }
+}
+
+impl TerminationCriterion for TargetFitness {
+    fn should_stop(&mut self, _: usize, stats: &Statistics) -> bool {
+        stats.max_fitness >= self.fitness
+    }
+}
+
+/// Stops when the best fitness hasn't improved by more than
+/// `min_improvement` for given number of generations.
+#[derive(Clone, Debug)]
+pub struct Stagnation {
+    generations: usize,
+    min_improvement: f32,
+
+    best_fitness: f32,
+    stagnant_for: usize,
+}
+
+impl Stagnation {
+    pub fn new(generations: usize, min_improvement: f32) -> Self {
+        assert!(generations > 0);
+        assert!(min_improvement >= 0.0);
+
+        Self {
+            generations,
+            min_improvement,
+            best_fitness: f32::NEG_INFINITY,
+            stagnant_for: 0,
+        }
+    }
+}
+
+impl TerminationCriterion for Stagnation {
+    fn should_stop(&mut self, _: usize, stats: &Statistics) -> bool {
+        if stats.max_fitness > self.best_fitness + self.min_improvement {
+            self.best_fitness = stats.max_fitness;
+            self.stagnant_for = 0;
+        } else {
+            self.stagnant_for += 1;
+        }
+
+        self.stagnant_for >= self.generations
+    }
+}
+
+/// Stops once given time has passed since the run has started (or, when
+/// used by a run loop that doesn't call `Monitor::start()`, since the
+/// criterion has been created).
+///
+/// `std::time::Instant` panics on `wasm32-unknown-unknown`, so there this
+/// criterion has to be given a clock (e.g. one built on JavaScript's
+/// `performance.now()`) through `with_clock()`.
+#[derive(Clone, Debug)]
+pub struct WallClock {
+    budget: Duration,
+
+    /// Returns time elapsed since an arbitrary (but fixed) moment.
+    clock: fn() -> Duration,
+
+    started_at: Duration,
+}
+
+impl WallClock {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new(budget: Duration) -> Self {
+        Self::with_clock(budget, monotonic_clock)
+    }
+
+    pub fn with_clock(budget: Duration, clock: fn() -> Duration) -> Self {
+        Self {
+            budget,
+            clock,
+            started_at: clock(),
+        }
+    }
+}
+
+impl TerminationCriterion for WallClock {
+    fn start(&mut self) {
+        self.started_at = (self.clock)();
+    }
+
+    fn should_stop(&mut self, _: usize, _: &Statistics) -> bool {
+        (self.clock)().saturating_sub(self.started_at) >= self.budget
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn monotonic_clock() -> Duration {
+    static ORIGIN: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
+
+    ORIGIN.get_or_init(Instant::now).elapsed()
+}
+
+/// Stops when population's diversity (see `Statistics::diversity`) drops
+/// below given threshold - past that point, there's little left for
+/// crossover to work with.
+#[derive(Clone, Debug)]
+pub struct DiversityCollapse {
+    threshold: f32,
+}
+
+impl DiversityCollapse {
+    pub fn new(threshold: f32) -> Self {
+        assert!(threshold >= 0.0);
+
+        Self { threshold }
+    }
+}
+
+impl TerminationCriterion for DiversityCollapse {
+    fn should_stop(&mut self, _: usize, stats: &Statistics) -> bool {
+        stats.diversity < self.threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(max_fitness: f32, diversity: f32) -> Statistics {
+        Statistics {
+            min_fitness: 0.0,
+            max_fitness,
+            avg_fitness: 0.0,
+            diversity,
+            pareto_front: Vec::new(),
+            species: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn max_generations() {
+        let mut criterion = MaxGenerations::new(3);
+
+        assert!(!criterion.should_stop(1, &stats(0.0, 1.0)));
+        assert!(!criterion.should_stop(2, &stats(0.0, 1.0)));
+        assert!(criterion.should_stop(3, &stats(0.0, 1.0)));
+    }
+
+    #[test]
+    fn target_fitness() {
+        let mut criterion = TargetFitness::new(10.0);
+
+        assert!(!criterion.should_stop(1, &stats(9.9, 1.0)));
+        assert!(criterion.should_stop(2, &stats(10.0, 1.0)));
+    }
+
+    #[test]
+    fn stagnation() {
+        let mut criterion = Stagnation::new(2, 0.1);
+
+        assert!(!criterion.should_stop(1, &stats(1.0, 1.0)));
+
+        // Improvements too small to count
+        assert!(!criterion.should_stop(2, &stats(1.05, 1.0)));
+        assert!(criterion.should_stop(3, &stats(1.1, 1.0)));
+
+        // A real improvement resets the counter
+        assert!(!criterion.should_stop(4, &stats(1.5, 1.0)));
+        assert!(!criterion.should_stop(5, &stats(1.5, 1.0)));
+        assert!(criterion.should_stop(6, &stats(1.5, 1.0)));
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn wall_clock() {
+        assert!(WallClock::new(Duration::ZERO).should_stop(1, &stats(0.0, 1.0)));
+        assert!(!WallClock::new(Duration::from_secs(3600)).should_stop(1, &stats(0.0, 1.0)));
+    }
+
+    #[test]
+    fn wall_clock_with_custom_clock() {
+        use std::cell::Cell;
+
+        thread_local! {
+            static NOW: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+        }
+
+        let mut criterion = WallClock::with_clock(Duration::from_secs(10), || NOW.with(Cell::get));
+
+        // The first generation counts towards the budget, too
+        NOW.with(|now| now.set(Duration::from_secs(9)));
+        assert!(!criterion.should_stop(1, &stats(0.0, 1.0)));
+
+        NOW.with(|now| now.set(Duration::from_secs(10)));
+        assert!(criterion.should_stop(2, &stats(0.0, 1.0)));
+
+        // Starting a run restarts the clock
+        NOW.with(|now| now.set(Duration::from_secs(100)));
+        criterion.start();
+
+        NOW.with(|now| now.set(Duration::from_secs(109)));
+        assert!(!criterion.should_stop(1, &stats(0.0, 1.0)));
+
+        NOW.with(|now| now.set(Duration::from_secs(110)));
+        assert!(criterion.should_stop(2, &stats(0.0, 1.0)));
+    }
+
+    #[test]
+    fn diversity_collapse() {
+        let mut criterion = DiversityCollapse::new(0.01);
+
+        assert!(!criterion.should_stop(1, &stats(0.0, 0.5)));
+        assert!(criterion.should_stop(2, &stats(0.0, 0.001)));
+    }
+}
@@ -0,0 +1,90 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Error returned by fallible (`try_*`) operations on networks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Network needs at least two layers: the inputs and the outputs.
+    TooFewLayers { found: usize },
+
+    /// Network has a layer without any neurons.
+    EmptyLayer { layer: usize },
+
+    /// Number of weights doesn't match network's topology (see
+    /// `Network::neuron_sizes()`).
+    WrongWeightCount { expected: usize, found: usize },
+
+    /// Number of inputs doesn't match the size of network's first layer.
+    WrongInputCount { expected: usize, found: usize },
+
+    /// Number of targets doesn't match the size of network's last layer.
+    WrongTargetCount { expected: usize, found: usize },
+
+    /// Network's layer sizes don't match the ones of a `StaticNetwork`.
+    WrongTopology {
+        expected: Vec<usize>,
+        found: Vec<usize>,
+    },
+
+    /// Data doesn't start with the header of a `QuantizedNetwork`.
+    NotAQuantizedNetwork,
+
+    /// Quantized network has been written by an incompatible version of
+    /// this crate.
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    /// Header of a quantized network is fine, but the rest of it isn't.
+    Corrupted,
+
+    /// Training needs mini-batches of at least one sample.
+    InvalidBatchSize,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewLayers { found } => {
+                write!(f, "network needs at least 2 layers, but got {}", found)
+            }
+
+            Self::EmptyLayer { layer } => write!(f, "layer #{} has no neurons", layer),
+
+            Self::WrongWeightCount { expected, found } => {
+                write!(f, "network needs {} weights, but got {}", expected, found)
+            }
+
+            Self::WrongInputCount { expected, found } => {
+                write!(f, "network needs {} inputs, but got {}", expected, found)
+            }
+
+            Self::WrongTargetCount { expected, found } => {
+                write!(
+                    f,
+                    "network has {} outputs, but got {} targets",
+                    expected, found
+                )
+            }
+
+            Self::WrongTopology { expected, found } => write!(
+                f,
+                "network needs layers of {:?} neurons, but got {:?}",
+                expected, found
+            ),
+
+            Self::NotAQuantizedNetwork => write!(f, "not a quantized network"),
+
+            Self::UnsupportedVersion { found, supported } => write!(
+                f,
+                "quantized network has version {}, but only version {} is supported",
+                found, supported
+            ),
+
+            Self::Corrupted => write!(f, "quantized network is corrupted"),
+
+            Self::InvalidBatchSize => write!(f, "batch size must be positive"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,127 @@
+use crate::Float;
+use core::ops::{Add, Mul, Neg, Sub};
+
+/// Q16.16 fixed-point number - for running networks on hardware without
+/// a floating-point unit.
+///
+/// Covers `-32768.0..32768.0` with precision of `1 / 65536`; operations
+/// saturate instead of overflowing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fixed(i32);
+
+impl Fixed {
+    const FRAC_BITS: u32 = 16;
+    const ONE: i64 = 1 << Self::FRAC_BITS;
+
+    pub const MIN: Self = Self(i32::MIN);
+    pub const MAX: Self = Self(i32::MAX);
+
+    pub const fn from_bits(bits: i32) -> Self {
+        Self(bits)
+    }
+
+    pub const fn to_bits(self) -> i32 {
+        self.0
+    }
+}
+
+impl Float for Fixed {
+    const ZERO: Self = Self(0);
+
+    fn from_f32(value: f32) -> Self {
+        let value = value * Self::ONE as f32;
+
+        // `as` saturates (and maps NaN to zero), so all we have to do here
+        // is rounding
+        let value = if value >= 0.0 {
+            value + 0.5
+        } else {
+            value - 0.5
+        };
+
+        Self(value as i32)
+    }
+
+    fn to_f32(self) -> f32 {
+        self.0 as f32 / Self::ONE as f32
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let value = (self.0 as i64 * rhs.0 as i64 + Self::ONE / 2) >> Self::FRAC_BITS;
+
+        Self(value.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.saturating_neg())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixed(value: f32) -> Fixed {
+        Fixed::from_f32(value)
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(fixed(1.0).to_bits(), 0x1_0000);
+        assert_eq!(fixed(-0.5).to_bits(), -0x8000);
+        assert_eq!(fixed(1.0 / 65536.0).to_bits(), 1);
+        assert_eq!(fixed(0.4 / 65536.0).to_bits(), 0);
+        assert_eq!(fixed(1e9), Fixed::MAX);
+        assert_eq!(fixed(-1e9), Fixed::MIN);
+        assert_eq!(fixed(f32::NAN), Fixed::ZERO);
+
+        assert_eq!(fixed(0.75).to_f32(), 0.75);
+        assert_eq!(fixed(-123.25).to_f32(), -123.25);
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(fixed(1.5) + fixed(2.25), fixed(3.75));
+        assert_eq!(fixed(1.5) - fixed(2.25), fixed(-0.75));
+        assert_eq!(fixed(1.5) * fixed(-2.25), fixed(-3.375));
+        assert_eq!(-fixed(1.5), fixed(-1.5));
+        assert!(fixed(-0.1) < fixed(0.1));
+
+        assert_eq!(fixed(-0.1).relu(), Fixed::ZERO);
+        assert_eq!(fixed(0.1).relu(), fixed(0.1));
+    }
+
+    #[test]
+    fn saturation() {
+        assert_eq!(Fixed::MAX + fixed(1.0), Fixed::MAX);
+        assert_eq!(Fixed::MIN - fixed(1.0), Fixed::MIN);
+        assert_eq!(fixed(30000.0) * fixed(30000.0), Fixed::MAX);
+        assert_eq!(fixed(30000.0) * fixed(-30000.0), Fixed::MIN);
+        assert_eq!(-Fixed::MIN, Fixed::MAX);
+    }
+}
@@ -0,0 +1,63 @@
+use core::fmt::Debug;
+use core::ops::{Add, Mul, Neg, Sub};
+
+/// Number a `Network` can compute with: `f32` (the default), `f64` or the
+/// fixed-point `Fixed`.
+///
+/// Conversions go through `f32`, since that's the precision networks are
+/// evolved in.
+pub trait Float:
+    Copy
+    + Debug
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+
+    fn from_f32(value: f32) -> Self;
+    fn to_f32(self) -> f32;
+
+    fn abs(self) -> Self {
+        if self < Self::ZERO {
+            -self
+        } else {
+            self
+        }
+    }
+
+    /// Rectified linear unit, i.e. `max(self, 0)`.
+    fn relu(self) -> Self {
+        if self > Self::ZERO {
+            self
+        } else {
+            Self::ZERO
+        }
+    }
+}
+
+impl Float for f32 {
+    const ZERO: Self = 0.0;
+
+    fn from_f32(value: f32) -> Self {
+        value
+    }
+
+    fn to_f32(self) -> f32 {
+        self
+    }
+}
+
+impl Float for f64 {
+    const ZERO: Self = 0.0;
+
+    fn from_f32(value: f32) -> Self {
+        value as f64
+    }
+
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+}
@@ -1,18 +1,57 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::{once, repeat};
 use rand::{Rng, RngCore};
-use std::iter::once;
+
+mod error;
+mod fixed;
+mod float;
+mod quantized;
+mod static_network;
+#[cfg(feature = "std")]
+mod training;
+
+pub use error::Error;
+pub use fixed::Fixed;
+pub use float::Float;
+pub use quantized::{DriftReport, QuantizedLayer, QuantizedNetwork};
+pub use static_network::{StaticLayer, StaticNetwork};
+#[cfg(feature = "std")]
+pub use training::{Adam, GradientDescent, Gradients, Loss, Sample, Sgd};
 
 #[derive(Debug)]
 pub struct LayerTopology {
     pub neurons: usize,
 }
 
-#[derive(Debug, PartialEq)]
-pub struct Network {
-    layers: Vec<Layer>,
+/// Feed-forward network whose hidden layers go through ReLU, while the
+/// output layer stays linear - so that the outputs can be negative, too.
+///
+/// Computes in `f32` by default; see `Float` for the alternatives and
+/// `StaticNetwork` for an allocation-free variant.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Network<T = f32> {
+    layers: Vec<Layer<T>>,
 }
-impl Network {
+impl<T: Float> PartialEq for Network<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.layers == other.layers
+    }
+}
+impl<T: Float> Network<T> {
+    /// Weights are drawn as `f32`s, so given the same seed networks of
+    /// all precisions start the same.
     pub fn random(rng: &mut dyn RngCore, layers: &[LayerTopology]) -> Self {
-        assert!(layers.len() > 1);
+        Self::try_random(rng, layers).unwrap_or_else(|err| panic!("{}", err))
+    }
+    pub fn try_random(rng: &mut dyn RngCore, layers: &[LayerTopology]) -> Result<Self, Error> {
+        check_topology(layers)?;
+
         let layers = layers
             .iter()
             .take(layers.len() - 1)
@@ -20,22 +59,123 @@ impl Network {
             .map(|(input, output)| Layer::random(rng, input.neurons, output.neurons))
             .collect();
 
-        Self { layers }
+        Ok(Self { layers })
     }
-    pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
-        self.layers
+    /// Panics if the number of inputs doesn't match the network (see
+    /// `try_propagate()`).
+    pub fn propagate(&self, inputs: Vec<T>) -> Vec<T> {
+        self.try_propagate(inputs)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+    pub fn try_propagate(&self, inputs: Vec<T>) -> Result<Vec<T>, Error> {
+        self.check_inputs(&inputs)?;
+
+        let layers = self.layers.len();
+
+        Ok(self
+            .layers
             .iter()
-            .fold(inputs, |inputs, layer| layer.propagate(&inputs))
+            .enumerate()
+            .fold(inputs, |inputs, (idx, layer)| {
+                layer.propagate(&inputs, Activation::of(idx, layers))
+            }))
     }
-    pub fn weights(&self) -> impl Iterator<Item = f32> + '_ {
+    /// Same as `propagate()`, but returns activations of every layer: the
+    /// inputs come first and the outputs come last.
+    pub fn propagate_traced(&self, inputs: Vec<T>) -> Vec<Vec<T>> {
+        self.try_propagate_traced(inputs)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+    pub fn try_propagate_traced(&self, inputs: Vec<T>) -> Result<Vec<Vec<T>>, Error> {
+        self.check_inputs(&inputs)?;
+
+        let mut activations = Vec::with_capacity(self.layers.len() + 1);
+        activations.push(inputs);
+
+        for (idx, layer) in self.layers.iter().enumerate() {
+            let activation = Activation::of(idx, self.layers.len());
+            let outputs = layer.propagate(activations.last().unwrap(), activation);
+
+            activations.push(outputs);
+        }
+
+        Ok(activations)
+    }
+    pub fn layers(&self) -> &[Layer<T>] {
+        &self.layers
+    }
+    /// Number of neurons in each layer, starting with the number of inputs
+    /// (i.e. the topology this network has been created with).
+    pub fn layer_sizes(&self) -> Vec<usize> {
+        once(self.layers[0].input_size())
+            .chain(self.layers.iter().map(Layer::output_size))
+            .collect()
+    }
+    /// Tells how often each hidden neuron fires for given inputs; neurons
+    /// that never do (*dead* ReLUs) don't contribute anything to the
+    /// outputs.
+    pub fn relu_stats<'a>(&self, inputs: impl IntoIterator<Item = &'a [T]>) -> Vec<ReluStats>
+    where
+        T: 'a,
+    {
+        let hidden = &self.layers[..self.layers.len() - 1];
+
+        let mut fired: Vec<Vec<usize>> = hidden
+            .iter()
+            .map(|layer| vec![0; layer.output_size()])
+            .collect();
+
+        let mut samples = 0;
+
+        for inputs in inputs {
+            let activations = self.propagate_traced(inputs.to_vec());
+
+            for (fired, outputs) in fired.iter_mut().zip(&activations[1..]) {
+                for (fired, output) in fired.iter_mut().zip(outputs) {
+                    if *output > T::ZERO {
+                        *fired += 1;
+                    }
+                }
+            }
+
+            samples += 1;
+        }
+
+        fired
+            .into_iter()
+            .map(|fired| ReluStats {
+                activity: fired
+                    .into_iter()
+                    .map(|fired| fired as f32 / samples.max(1) as f32)
+                    .collect(),
+            })
+            .collect()
+    }
+    pub fn weights(&self) -> impl Iterator<Item = T> + '_ {
         self.layers
             .iter()
             .flat_map(|layer| layer.neurons.iter())
             .flat_map(|neuron| once(&neuron.bias).chain(&neuron.weights))
             .copied()
     }
-    pub fn from_weights(layers: &[LayerTopology], weights: impl IntoIterator<Item = f32>) -> Self {
-        assert!(layers.len() > 1);
+    pub fn from_weights(layers: &[LayerTopology], weights: impl IntoIterator<Item = T>) -> Self {
+        Self::try_from_weights(layers, weights).unwrap_or_else(|err| panic!("{}", err))
+    }
+    pub fn try_from_weights(
+        layers: &[LayerTopology],
+        weights: impl IntoIterator<Item = T>,
+    ) -> Result<Self, Error> {
+        check_topology(layers)?;
+
+        let weights: Vec<_> = weights.into_iter().collect();
+        let expected = Network::neuron_sizes(layers).iter().sum();
+
+        if weights.len() != expected {
+            return Err(Error::WrongWeightCount {
+                expected,
+                found: weights.len(),
+            });
+        }
 
         let mut weights = weights.into_iter();
 
@@ -44,35 +184,147 @@ impl Network {
             .map(|layers| Layer::from_weights(layers[0].neurons, layers[1].neurons, &mut weights))
             .collect();
 
-        if weights.next().is_some() {
-            panic!("got too many weights");
+        Ok(Self { layers })
+    }
+
+    /// Converts this network into another precision - e.g. into `Fixed`,
+    /// to run it on a microcontroller.
+    pub fn cast<U: Float>(&self) -> Network<U> {
+        Network::from_weights(
+            &self.topology(),
+            self.weights().map(|weight| U::from_f32(weight.to_f32())),
+        )
+    }
+
+    fn topology(&self) -> Vec<LayerTopology> {
+        self.layer_sizes()
+            .into_iter()
+            .map(|neurons| LayerTopology { neurons })
+            .collect()
+    }
+
+    fn check_inputs(&self, inputs: &[T]) -> Result<(), Error> {
+        let expected = self.layers[0].input_size();
+
+        if inputs.len() == expected {
+            Ok(())
+        } else {
+            Err(Error::WrongInputCount {
+                expected,
+                found: inputs.len(),
+            })
         }
+    }
+}
 
-        Self { layers }
+impl Network {
+    /// Number of weights (bias included) of each neuron, in the same order
+    /// as they are yielded by `weights()`.
+    pub fn neuron_sizes(layers: &[LayerTopology]) -> Vec<usize> {
+        layers
+            .windows(2)
+            .flat_map(|layers| repeat(1 + layers[0].neurons).take(layers[1].neurons))
+            .collect()
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct Layer {
-    neurons: Vec<Neuron>,
+fn check_topology(layers: &[LayerTopology]) -> Result<(), Error> {
+    if layers.len() < 2 {
+        return Err(Error::TooFewLayers {
+            found: layers.len(),
+        });
+    }
+
+    match layers.iter().position(|layer| layer.neurons == 0) {
+        Some(layer) => Err(Error::EmptyLayer { layer }),
+        None => Ok(()),
+    }
+}
+
+/// What neurons of a layer do with the weighted sum of their inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Activation {
+    Relu,
+    Linear,
+}
+impl Activation {
+    /// Activation of the `layer`-th out of `layers` layers: the hidden
+    /// ones go through ReLU, the output one stays linear.
+    pub(crate) fn of(layer: usize, layers: usize) -> Self {
+        if layer + 1 < layers {
+            Self::Relu
+        } else {
+            Self::Linear
+        }
+    }
+
+    pub(crate) fn apply<T: Float>(self, value: T) -> T {
+        match self {
+            Self::Relu => value.relu(),
+            Self::Linear => value,
+        }
+    }
+}
+
+/// How often neurons of a single layer fire (see `Network::relu_stats()`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReluStats {
+    /// Fraction of inputs each neuron has fired (returned a positive
+    /// value) for.
+    pub activity: Vec<f32>,
+}
+impl ReluStats {
+    /// Indices of neurons that haven't fired even once.
+    pub fn dead(&self) -> Vec<usize> {
+        self.activity
+            .iter()
+            .enumerate()
+            .filter(|(_, activity)| **activity == 0.0)
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+    pub fn dead_ratio(&self) -> f32 {
+        self.dead().len() as f32 / self.activity.len() as f32
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Layer<T = f32> {
+    neurons: Vec<Neuron<T>>,
 }
-impl Layer {
+impl<T: Float> PartialEq for Layer<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.neurons == other.neurons
+    }
+}
+impl<T: Float> Layer<T> {
+    pub fn neurons(&self) -> &[Neuron<T>] {
+        &self.neurons
+    }
+    pub fn input_size(&self) -> usize {
+        self.neurons[0].weights.len()
+    }
+    pub fn output_size(&self) -> usize {
+        self.neurons.len()
+    }
+
     fn random(rng: &mut dyn RngCore, input: usize, output: usize) -> Self {
         let neurons = (0..output).map(|_| Neuron::random(rng, input)).collect();
         Self { neurons }
     }
 
-    fn propagate(&self, inputs: &Vec<f32>) -> Vec<f32> {
+    fn propagate(&self, inputs: &[T], activation: Activation) -> Vec<T> {
         self.neurons
             .iter()
-            .map(|neuron| neuron.propagate(inputs))
+            .map(|neuron| activation.apply(neuron.propagate(inputs)))
             .collect()
     }
 
     fn from_weights(
         input_size: usize,
         output_size: usize,
-        weights: &mut dyn Iterator<Item = f32>,
+        weights: &mut dyn Iterator<Item = T>,
     ) -> Self {
         let neurons = (0..output_size)
             .map(|_| Neuron::from_weights(input_size, weights))
@@ -83,16 +335,25 @@ impl Layer {
 }
 
 #[derive(Debug)]
-struct Neuron {
-    bias: f32,
-    weights: Vec<f32>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Neuron<T = f32> {
+    bias: T,
+    weights: Vec<T>,
 }
-impl PartialEq for Neuron {
+impl<T: Float> PartialEq for Neuron<T> {
     fn eq(&self, other: &Self) -> bool {
         self.approx_eq(other)
     }
 }
-impl Neuron {
+impl<T: Float> Neuron<T> {
+    pub fn bias(&self) -> T {
+        self.bias
+    }
+    /// Weights of neuron's inputs.
+    pub fn weights(&self) -> &[T] {
+        &self.weights
+    }
+
     fn approx_eq(&self, other: &Self) -> bool {
         abs_diff_eq(&self.bias, &other.bias)
             && self
@@ -103,22 +364,25 @@ impl Neuron {
     }
 
     fn random(rng: &mut dyn RngCore, size: usize) -> Self {
-        let bias = rng.gen_range(-1.0..=1.0);
-        let weights = (0..size).map(|_| rng.gen_range(-1.0..=1.0)).collect();
+        let mut random = || T::from_f32(rng.gen_range(-1.0..=1.0));
+
+        let bias = random();
+        let weights = (0..size).map(|_| random()).collect();
         Self { bias, weights }
     }
 
-    fn propagate(&self, inputs: &Vec<f32>) -> f32 {
-        assert_eq!(inputs.len(), self.weights.len());
+    fn propagate(&self, inputs: &[T]) -> T {
+        // Already checked by `Network::check_inputs()`
+        debug_assert_eq!(inputs.len(), self.weights.len());
 
         inputs
             .iter()
             .zip(&self.weights)
-            .fold(self.bias, |acc, (input, weight)| acc + input * weight)
-            .max(0.0)
+            .fold(self.bias, |acc, (&input, &weight)| acc + input * weight)
     }
 
-    fn from_weights(input_size: usize, weights: &mut dyn Iterator<Item = f32>) -> Self {
+    fn from_weights(input_size: usize, weights: &mut dyn Iterator<Item = T>) -> Self {
+        // Already checked by `Network::try_from_weights()`
         let bias = weights.next().expect("got not enough weights");
 
         let weights = (0..input_size)
@@ -129,9 +393,9 @@ impl Neuron {
     }
 }
 
-fn abs_diff_eq(lhv: &f32, rhv: &f32) -> bool {
+fn abs_diff_eq<T: Float>(lhv: &T, rhv: &T) -> bool {
     const EPSILON: f32 = 1e-6;
-    (lhv - rhv).abs() < EPSILON
+    Float::abs(lhv.to_f32() - rhv.to_f32()) < EPSILON
 }
 
 #[cfg(test)]
@@ -140,6 +404,7 @@ mod tests {
     ///
     ///
     use super::*;
+    use alloc::string::ToString;
     use approx::assert_relative_eq;
     use rand::SeedableRng;
     use rand_chacha::ChaCha8Rng;
@@ -166,9 +431,10 @@ mod tests {
                 weights: vec![-0.3, 0.8],
             };
 
-            assert_relative_eq!(neuron.propagate(&vec![-10.0, -10.0]), 0.0,);
+            // Activation is up to the layer (see `Activation`)
+            assert_relative_eq!(neuron.propagate(&[-10.0, -10.0]), -4.5);
             assert_relative_eq!(
-                neuron.propagate(&vec![0.5, 1.0]),
+                neuron.propagate(&[0.5, 1.0]),
                 (-0.3 * 0.5) + (0.8 * 1.0) + 0.5,
             );
         }
@@ -202,12 +468,17 @@ mod tests {
             };
 
             assert!(layer
-                .propagate(&vec![-10.0, -10.0])
+                .propagate(&[-10.0, -10.0], Activation::Relu)
                 .iter()
                 .all(|solution| abs_diff_eq(solution, &0.0)));
 
             assert!(layer
-                .propagate(&vec![0.5, 1.0])
+                .propagate(&[-10.0, -10.0], Activation::Linear)
+                .iter()
+                .all(|solution| abs_diff_eq(solution, &-4.5)));
+
+            assert!(layer
+                .propagate(&[0.5, 1.0], Activation::Relu)
                 .iter()
                 .all(|solution| abs_diff_eq(solution, &((-0.3 * 0.5) + (0.8 * 1.0) + 0.5))));
         }
@@ -221,7 +492,7 @@ mod tests {
             let mut rng = ChaCha8Rng::from_seed(Default::default());
             let network = Network::random(
                 &mut rng,
-                &vec![LayerTopology { neurons: 2 }, LayerTopology { neurons: 1 }],
+                &[LayerTopology { neurons: 2 }, LayerTopology { neurons: 1 }],
             );
 
             let expected = Network {
@@ -246,10 +517,11 @@ mod tests {
                 }],
             };
 
+            // The output layer is linear, so negative answers are fine
             assert!(network
                 .propagate(vec![-10.0, -10.0])
                 .iter()
-                .all(|solution| abs_diff_eq(solution, &0.0)));
+                .all(|solution| abs_diff_eq(solution, &-4.5)));
 
             assert!(network
                 .propagate(vec![0.5, 1.0])
@@ -292,5 +564,182 @@ mod tests {
 
             assert_relative_eq!(actual.as_slice(), weights.as_slice());
         }
+
+        #[test]
+        fn errors() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let layers = &[LayerTopology { neurons: 3 }, LayerTopology { neurons: 2 }];
+
+            assert_eq!(
+                Network::<f32>::try_random(&mut rng, &layers[..1]),
+                Err(Error::TooFewLayers { found: 1 })
+            );
+
+            assert_eq!(
+                Network::<f32>::try_random(
+                    &mut rng,
+                    &[LayerTopology { neurons: 3 }, LayerTopology { neurons: 0 }]
+                ),
+                Err(Error::EmptyLayer { layer: 1 })
+            );
+
+            assert_eq!(
+                Network::try_from_weights(layers, vec![0.0; 7]),
+                Err(Error::WrongWeightCount {
+                    expected: 8,
+                    found: 7
+                })
+            );
+
+            assert_eq!(
+                Network::try_from_weights(layers, vec![0.0; 9]),
+                Err(Error::WrongWeightCount {
+                    expected: 8,
+                    found: 9
+                })
+            );
+
+            let network = Network::try_from_weights(layers, vec![0.0; 8]).unwrap();
+
+            assert_eq!(
+                network.try_propagate(vec![1.0, 2.0]),
+                Err(Error::WrongInputCount {
+                    expected: 3,
+                    found: 2
+                })
+            );
+
+            assert_eq!(
+                network.try_propagate(vec![1.0, 2.0, 3.0]),
+                Ok(vec![0.0, 0.0])
+            );
+
+            assert_eq!(
+                Error::WrongInputCount {
+                    expected: 3,
+                    found: 2
+                }
+                .to_string(),
+                "network needs 3 inputs, but got 2"
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "network needs 8 weights, but got 7")]
+        fn from_weights_panics_on_wrong_weight_count() {
+            let layers = &[LayerTopology { neurons: 3 }, LayerTopology { neurons: 2 }];
+            Network::from_weights(layers, vec![0.0; 7]);
+        }
+
+        #[test]
+        fn propagate_traced() {
+            let network = Network {
+                layers: vec![
+                    Layer {
+                        neurons: vec![
+                            Neuron {
+                                bias: 0.0,
+                                weights: vec![1.0, 2.0],
+                            },
+                            Neuron {
+                                bias: -5.0,
+                                weights: vec![1.0, 1.0],
+                            },
+                        ],
+                    },
+                    Layer {
+                        neurons: vec![Neuron {
+                            bias: 0.5,
+                            weights: vec![2.0, 3.0],
+                        }],
+                    },
+                ],
+            };
+
+            let activations = network.propagate_traced(vec![1.0, 2.0]);
+
+            assert_eq!(
+                activations,
+                vec![vec![1.0, 2.0], vec![5.0, 0.0], vec![10.5]]
+            );
+            assert_eq!(
+                activations.last().unwrap(),
+                &network.propagate(vec![1.0, 2.0])
+            );
+
+            assert_eq!(network.layer_sizes(), vec![2, 2, 1]);
+            assert_eq!(network.layers()[1].neurons()[0].bias(), 0.5);
+            assert_eq!(network.layers()[1].neurons()[0].weights(), &[2.0, 3.0]);
+
+            // The second neuron of the first layer fires only for large
+            // inputs
+            let inputs = [[1.0, 2.0], [4.0, 4.0], [0.0, 0.0], [-1.0, 0.0]];
+            let stats = network.relu_stats(inputs.iter().map(|inputs| inputs.as_slice()));
+
+            assert_eq!(stats.len(), 1);
+            assert_eq!(stats[0].activity, vec![0.5, 0.25]);
+            assert!(stats[0].dead().is_empty());
+
+            let stats = network.relu_stats([[0.0, 0.0].as_slice()]);
+
+            assert_eq!(stats[0].dead(), vec![0, 1]);
+            assert_eq!(stats[0].dead_ratio(), 1.0);
+        }
+
+        #[test]
+        fn precisions() {
+            let layers = &[
+                LayerTopology { neurons: 3 },
+                LayerTopology { neurons: 6 },
+                LayerTopology { neurons: 2 },
+            ];
+
+            let random = || ChaCha8Rng::from_seed(Default::default());
+            let network = Network::<f32>::random(&mut random(), layers);
+            let inputs = [0.25, 0.5, 0.75];
+
+            let expected = network.propagate(inputs.to_vec());
+
+            // Given the same seed, networks of all precisions start the same
+            let network64 = Network::<f64>::random(&mut random(), layers);
+            let network_fixed = Network::<Fixed>::random(&mut random(), layers);
+
+            assert_eq!(network64, network.cast());
+            assert_eq!(network_fixed, network.cast());
+
+            let actual64: Vec<_> = network64
+                .propagate(inputs.map(f64::from).to_vec())
+                .into_iter()
+                .map(Float::to_f32)
+                .collect();
+
+            let actual_fixed: Vec<_> = network_fixed
+                .propagate(inputs.map(Fixed::from_f32).to_vec())
+                .into_iter()
+                .map(Float::to_f32)
+                .collect();
+
+            assert_relative_eq!(actual64.as_slice(), expected.as_slice(), epsilon = 1e-6);
+            assert_relative_eq!(actual_fixed.as_slice(), expected.as_slice(), epsilon = 1e-3);
+        }
+
+        #[test]
+        fn neuron_sizes() {
+            let layers = &[
+                LayerTopology { neurons: 3 },
+                LayerTopology { neurons: 2 },
+                LayerTopology { neurons: 1 },
+            ];
+
+            let actual = Network::neuron_sizes(layers);
+
+            assert_eq!(actual, vec![4, 4, 3]);
+            assert_eq!(
+                actual.iter().sum::<usize>(),
+                Network::<f32>::random(&mut ChaCha8Rng::from_seed(Default::default()), layers)
+                    .weights()
+                    .count()
+            );
+        }
     }
 }
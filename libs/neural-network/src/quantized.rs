@@ -0,0 +1,492 @@
+use crate::{check_topology, Activation, Error, Float, Layer, LayerTopology, Network};
+use alloc::vec::Vec;
+use core::iter::once;
+
+/// Marks the beginning of every quantized network.
+const MAGIC: &[u8; 4] = b"SLQN";
+
+/// Version of the binary format (see `QuantizedNetwork::to_bytes()`).
+const VERSION: u32 = 1;
+
+/// Network with weights quantized into `i8`s, with a separate scale for
+/// each layer - about four times smaller than its `f32` counterpart (see
+/// `Network::quantize()`).
+///
+/// Activations are not quantized: they are computed in whatever `Float`
+/// the inputs are given in, so on hardware without a floating-point unit
+/// `Fixed` can be used.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuantizedNetwork {
+    layers: Vec<QuantizedLayer>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuantizedLayer {
+    input_size: usize,
+
+    /// Weight represented by `1i8`.
+    scale: f32,
+
+    /// Bias and weights of each neuron, in the same order as
+    /// `Network::weights()` yields them.
+    weights: Vec<i8>,
+}
+
+/// How much outputs of a quantized network differ from the original ones
+/// (see `QuantizedNetwork::drift()`).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DriftReport {
+    /// Number of inputs both networks have been run on.
+    pub samples: usize,
+
+    /// Mean absolute difference between the outputs.
+    pub mean_error: f32,
+
+    /// Largest absolute difference between the outputs.
+    pub max_error: f32,
+}
+
+impl<T: Float> Network<T> {
+    /// Quantizes weights of each layer into `-127..=127`, scaled by the
+    /// largest (absolute) weight of that layer.
+    pub fn quantize(&self) -> QuantizedNetwork {
+        QuantizedNetwork {
+            layers: self.layers.iter().map(QuantizedLayer::new).collect(),
+        }
+    }
+}
+
+impl QuantizedNetwork {
+    /// Panics if the number of inputs doesn't match the network (see
+    /// `try_propagate()`).
+    pub fn propagate<T: Float>(&self, inputs: Vec<T>) -> Vec<T> {
+        self.try_propagate(inputs)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_propagate<T: Float>(&self, inputs: Vec<T>) -> Result<Vec<T>, Error> {
+        let expected = self.layers[0].input_size;
+
+        if inputs.len() != expected {
+            return Err(Error::WrongInputCount {
+                expected,
+                found: inputs.len(),
+            });
+        }
+
+        let layers = self.layers.len();
+
+        Ok(self
+            .layers
+            .iter()
+            .enumerate()
+            .fold(inputs, |inputs, (idx, layer)| {
+                layer.propagate(&inputs, Activation::of(idx, layers))
+            }))
+    }
+
+    pub fn layers(&self) -> &[QuantizedLayer] {
+        &self.layers
+    }
+
+    /// Number of neurons in each layer, starting with the number of inputs.
+    pub fn layer_sizes(&self) -> Vec<usize> {
+        once(self.layers[0].input_size)
+            .chain(self.layers.iter().map(QuantizedLayer::output_size))
+            .collect()
+    }
+
+    /// Converts weights back into `f32`s.
+    pub fn dequantize(&self) -> Network {
+        let topology: Vec<_> = self
+            .layer_sizes()
+            .into_iter()
+            .map(|neurons| LayerTopology { neurons })
+            .collect();
+
+        let weights = self.layers.iter().flat_map(|layer| {
+            layer
+                .weights
+                .iter()
+                .map(|&weight| weight as f32 * layer.scale)
+        });
+
+        Network::from_weights(&topology, weights)
+    }
+
+    /// Runs both networks on given inputs (e.g. ones recorded from birds'
+    /// eyes) and compares their outputs.
+    ///
+    /// Panics if `network` has got a different topology.
+    pub fn drift<'a>(
+        &self,
+        network: &Network,
+        inputs: impl IntoIterator<Item = &'a [f32]>,
+    ) -> DriftReport {
+        assert_eq!(
+            self.layer_sizes(),
+            network.layer_sizes(),
+            "networks have different topologies"
+        );
+
+        let mut report = DriftReport::default();
+        let mut errors = 0.0;
+        let mut outputs = 0;
+
+        for inputs in inputs {
+            let expected = network.propagate(inputs.to_vec());
+            let actual = self.propagate(inputs.to_vec());
+
+            for (expected, actual) in expected.into_iter().zip(actual) {
+                let error = Float::abs(expected - actual);
+
+                errors += error;
+                outputs += 1;
+
+                if error > report.max_error {
+                    report.max_error = error;
+                }
+            }
+
+            report.samples += 1;
+        }
+
+        if outputs > 0 {
+            report.mean_error = errors / outputs as f32;
+        }
+
+        report
+    }
+
+    /// Serializes this network into a compact binary format:
+    ///
+    /// - magic number (`SLQN`) and format version, as `u32`,
+    /// - number of layer sizes and the sizes themselves, as `u32`s,
+    /// - for each layer: its scale, as `f32`, followed by its weights.
+    ///
+    /// Numbers are little-endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let sizes = self.layer_sizes();
+        let mut bytes = Vec::new();
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(sizes.len() as u32).to_le_bytes());
+
+        for size in sizes {
+            bytes.extend_from_slice(&(size as u32).to_le_bytes());
+        }
+
+        for layer in &self.layers {
+            bytes.extend_from_slice(&layer.scale.to_le_bytes());
+            bytes.extend(layer.weights.iter().map(|&weight| weight as u8));
+        }
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader(bytes);
+
+        match reader.take(MAGIC.len()) {
+            Ok(magic) if magic == MAGIC => (),
+            _ => return Err(Error::NotAQuantizedNetwork),
+        }
+
+        match reader.u32()? {
+            VERSION => (),
+            found => {
+                return Err(Error::UnsupportedVersion {
+                    found,
+                    supported: VERSION,
+                })
+            }
+        }
+
+        let sizes = (0..reader.u32()?)
+            .map(|_| {
+                Ok(LayerTopology {
+                    neurons: reader.u32()? as usize,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        check_topology(&sizes)?;
+
+        let layers = sizes
+            .windows(2)
+            .map(|sizes| {
+                let input_size = sizes[0].neurons;
+                let scale = f32::from_le_bytes(reader.array()?);
+
+                let len = (1 + input_size)
+                    .checked_mul(sizes[1].neurons)
+                    .ok_or(Error::Corrupted)?;
+
+                let weights = reader
+                    .take(len)?
+                    .iter()
+                    .map(|&weight| weight as i8)
+                    .collect();
+
+                Ok(QuantizedLayer {
+                    input_size,
+                    scale,
+                    weights,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        if !reader.0.is_empty() {
+            return Err(Error::Corrupted);
+        }
+
+        Ok(Self { layers })
+    }
+}
+
+impl QuantizedLayer {
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Quantized weights; multiply them by `scale()` to get the originals.
+    pub fn weights(&self) -> &[i8] {
+        &self.weights
+    }
+
+    pub fn input_size(&self) -> usize {
+        self.input_size
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.weights.len() / (1 + self.input_size)
+    }
+
+    fn new<T: Float>(layer: &Layer<T>) -> Self {
+        let weights = || {
+            layer
+                .neurons
+                .iter()
+                .flat_map(|neuron| once(&neuron.bias).chain(&neuron.weights))
+                .map(|weight| weight.to_f32())
+        };
+
+        let max = weights().fold(0.0, |max, weight| Float::abs(weight).max(max));
+
+        // All-zero layer can have any scale, it doesn't matter
+        let scale = if max > 0.0 { max / 127.0 } else { 1.0 };
+
+        let weights = weights()
+            .map(|weight| {
+                let weight = weight / scale;
+
+                // `as` saturates, so all we have to do here is rounding
+                (if weight >= 0.0 {
+                    weight + 0.5
+                } else {
+                    weight - 0.5
+                }) as i8
+            })
+            .collect();
+
+        Self {
+            input_size: layer.input_size(),
+            scale,
+            weights,
+        }
+    }
+
+    fn propagate<T: Float>(&self, inputs: &[T], activation: Activation) -> Vec<T> {
+        let scale = T::from_f32(self.scale);
+
+        self.weights
+            .chunks(1 + self.input_size)
+            .map(|neuron| {
+                let (&bias, weights) = neuron.split_first().unwrap();
+
+                let output = inputs
+                    .iter()
+                    .zip(weights)
+                    .fold(T::from_f32(bias as f32), |acc, (&input, &weight)| {
+                        acc + input * T::from_f32(weight as f32)
+                    });
+
+                activation.apply(output * scale)
+            })
+            .collect()
+    }
+}
+
+/// Consumes `bytes` from the front.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < len {
+            return Err(Error::Corrupted);
+        }
+
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    fn network() -> Network {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let layers = [9, 18, 2].map(|neurons| LayerTopology { neurons });
+
+        Network::random(&mut rng, &layers)
+    }
+
+    fn inputs() -> Vec<Vec<f32>> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        (0..100)
+            .map(|_| (0..9).map(|_| rng.gen_range(0.0..=1.0)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn quantize() {
+        let network = network();
+        let quantized = network.quantize();
+
+        assert_eq!(quantized.layer_sizes(), vec![9, 18, 2]);
+
+        for (layer, original) in quantized.layers().iter().zip(network.layers()) {
+            assert_eq!(layer.output_size(), original.output_size());
+
+            // Largest weight is mapped into the edge of the range
+            assert!(layer.weights().iter().any(|weight| weight.abs() == 127));
+            assert!(layer.weights().iter().all(|&weight| weight != i8::MIN));
+        }
+
+        // Rounding errors are no larger than half of the scale
+        let dequantized = quantized.dequantize();
+
+        for (layer, (dequantized, original)) in quantized
+            .layers()
+            .iter()
+            .zip(dequantized.layers().iter().zip(network.layers()))
+        {
+            let dequantized = dequantized
+                .neurons()
+                .iter()
+                .flat_map(|neuron| neuron.weights());
+            let original = original
+                .neurons()
+                .iter()
+                .flat_map(|neuron| neuron.weights());
+
+            for (dequantized, original) in dequantized.zip(original) {
+                assert!((dequantized - original).abs() <= layer.scale() / 2.0 + 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn zero_layer() {
+        let layers = [2, 1].map(|neurons| LayerTopology { neurons });
+        let network = Network::from_weights(&layers, [0.0; 3]);
+        let quantized = network.quantize();
+
+        assert_eq!(quantized.layers()[0].weights(), &[0, 0, 0]);
+        assert_eq!(quantized.propagate(vec![1.0, 1.0]), vec![0.0]);
+    }
+
+    #[test]
+    fn drift() {
+        let network = network();
+        let quantized = network.quantize();
+        let inputs = inputs();
+
+        let report = quantized.drift(&network, inputs.iter().map(Vec::as_slice));
+
+        assert_eq!(report.samples, 100);
+        assert!(report.mean_error > 0.0);
+        assert!(report.mean_error <= report.max_error);
+        assert!(report.max_error < 0.05, "{:?}", report);
+
+        // Network compared against itself doesn't drift at all
+        let report = quantized.drift(&quantized.dequantize(), inputs.iter().map(Vec::as_slice));
+
+        assert!(report.max_error < 1e-5, "{:?}", report);
+    }
+
+    #[test]
+    fn propagate_in_fixed_point() {
+        let quantized = network().quantize();
+
+        for inputs in inputs() {
+            let expected = quantized.propagate(inputs.clone());
+
+            let actual =
+                quantized.propagate(inputs.into_iter().map(crate::Fixed::from_f32).collect());
+
+            for (expected, actual) in expected.into_iter().zip(actual) {
+                assert!((expected - actual.to_f32()).abs() < 1e-2);
+            }
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        let quantized = network().quantize();
+        let bytes = quantized.to_bytes();
+
+        // Header, sizes, scales and weights
+        assert_eq!(bytes.len(), 12 + 3 * 4 + 2 * 4 + 18 * 10 + 2 * 19);
+        assert_eq!(QuantizedNetwork::from_bytes(&bytes), Ok(quantized));
+
+        assert_eq!(
+            QuantizedNetwork::from_bytes(b"SLCK"),
+            Err(Error::NotAQuantizedNetwork)
+        );
+
+        assert_eq!(
+            QuantizedNetwork::from_bytes(b"SL"),
+            Err(Error::NotAQuantizedNetwork)
+        );
+
+        let mut newer = bytes.clone();
+        newer[4] = 2;
+
+        assert_eq!(
+            QuantizedNetwork::from_bytes(&newer),
+            Err(Error::UnsupportedVersion {
+                found: 2,
+                supported: 1
+            })
+        );
+
+        assert_eq!(
+            QuantizedNetwork::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(Error::Corrupted)
+        );
+
+        assert_eq!(
+            QuantizedNetwork::from_bytes(&[bytes.as_slice(), &[0]].concat()),
+            Err(Error::Corrupted)
+        );
+    }
+}
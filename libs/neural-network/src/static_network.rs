@@ -0,0 +1,164 @@
+use crate::{Activation, Error, Float, Network};
+use alloc::vec;
+use core::array;
+
+/// Allocation-free counterpart of `Layer`, with its size known at compile
+/// time: `I` inputs and `O` neurons.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StaticLayer<T, const I: usize, const O: usize> {
+    biases: [T; O],
+    weights: [[T; I]; O],
+}
+impl<T: Float, const I: usize, const O: usize> StaticLayer<T, I, O> {
+    /// Propagates as a hidden layer, i.e. through ReLU.
+    pub fn propagate(&self, inputs: &[T; I]) -> [T; O] {
+        self.propagate_with(inputs, Activation::Relu)
+    }
+
+    /// Propagates as an output layer, i.e. without any activation.
+    pub fn propagate_linear(&self, inputs: &[T; I]) -> [T; O] {
+        self.propagate_with(inputs, Activation::Linear)
+    }
+
+    fn propagate_with(&self, inputs: &[T; I], activation: Activation) -> [T; O] {
+        array::from_fn(|neuron| {
+            let output = inputs
+                .iter()
+                .zip(&self.weights[neuron])
+                .fold(self.biases[neuron], |acc, (&input, &weight)| {
+                    acc + input * weight
+                });
+
+            activation.apply(output)
+        })
+    }
+
+    /// Consumes weights in the same order as `Network::weights()` yields
+    /// them.
+    fn from_weights(weights: &mut dyn Iterator<Item = T>) -> Self {
+        let mut next = || weights.next().expect("got not enough weights");
+        let mut biases = [T::ZERO; O];
+        let mut neurons = [[T::ZERO; I]; O];
+
+        for (bias, neuron) in biases.iter_mut().zip(&mut neurons) {
+            *bias = next();
+            neuron.fill_with(&mut next);
+        }
+
+        Self {
+            biases,
+            weights: neurons,
+        }
+    }
+}
+
+/// Allocation-free counterpart of `Network` with one hidden layer (the
+/// topology birds' brains have): `I` inputs, `H` hidden neurons and `O`
+/// outputs.
+///
+/// Meant for devices without a heap - evolve a `Network` as usual and
+/// then convert it:
+///
+/// ```
+/// # use lib_neural_network::*;
+/// # use rand::SeedableRng;
+/// # let mut rng = rand_chacha::ChaCha8Rng::from_seed(Default::default());
+/// let layers = [3, 6, 2].map(|neurons| LayerTopology { neurons });
+/// let network = Network::<f32>::random(&mut rng, &layers);
+///
+/// let brain = StaticNetwork::<f32, 3, 6, 2>::try_from(&network).unwrap();
+///
+/// assert_eq!(
+///     brain.propagate(&[0.1, 0.2, 0.3]).as_slice(),
+///     network.propagate(vec![0.1, 0.2, 0.3]),
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StaticNetwork<T, const I: usize, const H: usize, const O: usize> {
+    hidden: StaticLayer<T, I, H>,
+    output: StaticLayer<T, H, O>,
+}
+impl<T: Float, const I: usize, const H: usize, const O: usize> StaticNetwork<T, I, H, O> {
+    pub fn propagate(&self, inputs: &[T; I]) -> [T; O] {
+        self.output.propagate_linear(&self.hidden.propagate(inputs))
+    }
+}
+
+impl<T: Float, const I: usize, const H: usize, const O: usize> TryFrom<&Network<T>>
+    for StaticNetwork<T, I, H, O>
+{
+    type Error = Error;
+
+    fn try_from(network: &Network<T>) -> Result<Self, Error> {
+        let expected = vec![I, H, O];
+        let found = network.layer_sizes();
+
+        if found != expected {
+            return Err(Error::WrongTopology { expected, found });
+        }
+
+        let mut weights = network.weights();
+
+        Ok(Self {
+            hidden: StaticLayer::from_weights(&mut weights),
+            output: StaticLayer::from_weights(&mut weights),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Fixed, LayerTopology};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn network<T: Float>() -> Network<T> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let layers = [4, 8, 2].map(|neurons| LayerTopology { neurons });
+
+        Network::random(&mut rng, &layers)
+    }
+
+    #[test]
+    fn matches_network() {
+        let network = network::<f32>();
+        let brain = StaticNetwork::<f32, 4, 8, 2>::try_from(&network).unwrap();
+
+        for inputs in [
+            [0.0, 0.0, 0.0, 0.0],
+            [0.1, 0.9, 0.4, 0.0],
+            [1.0, 0.5, 0.2, 0.7],
+        ] {
+            assert_eq!(
+                brain.propagate(&inputs).as_slice(),
+                network.propagate(inputs.to_vec())
+            );
+        }
+    }
+
+    #[test]
+    fn matches_network_in_fixed_point() {
+        let network = network::<Fixed>();
+        let brain = StaticNetwork::<Fixed, 4, 8, 2>::try_from(&network).unwrap();
+        let inputs = [0.1, 0.9, 0.4, 0.0].map(Fixed::from_f32);
+
+        assert_eq!(
+            brain.propagate(&inputs).as_slice(),
+            network.propagate(inputs.to_vec())
+        );
+    }
+
+    #[test]
+    fn wrong_topology() {
+        let err = StaticNetwork::<f32, 4, 7, 2>::try_from(&network()).unwrap_err();
+
+        assert_eq!(
+            err,
+            Error::WrongTopology {
+                expected: vec![4, 7, 2],
+                found: vec![4, 8, 2],
+            }
+        );
+    }
+}
@@ -0,0 +1,519 @@
+use crate::{Activation, Error, Network};
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::iter::once;
+
+/// Inputs together with the outputs a network should learn to return for
+/// them.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sample {
+    pub inputs: Vec<f32>,
+    pub targets: Vec<f32>,
+}
+
+/// How far network's outputs are from the targets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Loss {
+    /// Mean of squared differences between the outputs and the targets.
+    MeanSquaredError,
+
+    /// Cross-entropy between softmax of the outputs (treated as logits)
+    /// and the targets (treated as probabilities).
+    CrossEntropy,
+}
+
+impl Loss {
+    /// Returns the loss, together with its derivatives with respect to
+    /// each of the outputs.
+    pub fn eval(self, outputs: &[f32], targets: &[f32]) -> (f32, Vec<f32>) {
+        match self {
+            Self::MeanSquaredError => {
+                let len = outputs.len() as f32;
+
+                let loss = outputs
+                    .iter()
+                    .zip(targets)
+                    .map(|(output, target)| (output - target).powi(2))
+                    .sum::<f32>()
+                    / len;
+
+                let derivatives = outputs
+                    .iter()
+                    .zip(targets)
+                    .map(|(output, target)| 2.0 * (output - target) / len)
+                    .collect();
+
+                (loss, derivatives)
+            }
+
+            Self::CrossEntropy => {
+                let max = outputs.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                let exps: Vec<_> = outputs.iter().map(|output| (output - max).exp()).collect();
+                let sum: f32 = exps.iter().sum();
+                let probs: Vec<_> = exps.into_iter().map(|exp| exp / sum).collect();
+
+                let loss = -probs
+                    .iter()
+                    .zip(targets)
+                    .map(|(prob, target)| target * prob.max(f32::MIN_POSITIVE).ln())
+                    .sum::<f32>();
+
+                // Targets usually sum up to one, but there's no need to
+                // depend on that
+                let total: f32 = targets.iter().sum();
+
+                let derivatives = probs
+                    .iter()
+                    .zip(targets)
+                    .map(|(prob, target)| prob * total - target)
+                    .collect();
+
+                (loss, derivatives)
+            }
+        }
+    }
+}
+
+/// Derivatives of the loss with respect to network's weights.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradients {
+    layers: Vec<Vec<f32>>,
+}
+
+impl Gradients {
+    /// Gradients of each layer, in the same order as `Network::weights()`
+    /// yields the weights.
+    pub fn layers(&self) -> &[Vec<f32>] {
+        &self.layers
+    }
+
+    /// Euclidean norm of each layer's gradients - handy for spotting the
+    /// vanishing (or exploding) ones.
+    pub fn norms(&self) -> Vec<f32> {
+        self.layers
+            .iter()
+            .map(|layer| layer.iter().map(|grad| grad * grad).sum::<f32>().sqrt())
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = f32> + '_ {
+        self.layers.iter().flatten().copied()
+    }
+
+    fn add(&mut self, other: &Self) {
+        for (lhs, rhs) in self.layers.iter_mut().zip(&other.layers) {
+            for (lhs, rhs) in lhs.iter_mut().zip(rhs) {
+                *lhs += rhs;
+            }
+        }
+    }
+
+    fn scale(&mut self, factor: f32) {
+        for grad in self.layers.iter_mut().flatten() {
+            *grad *= factor;
+        }
+    }
+}
+
+/// Method of updating network's weights given their gradients.
+pub trait GradientDescent {
+    fn step(&mut self, network: &mut Network, gradients: &Gradients);
+}
+
+/// Stochastic gradient descent with momentum.
+#[derive(Clone, Debug)]
+pub struct Sgd {
+    learning_rate: f32,
+    momentum: f32,
+    velocities: Vec<f32>,
+}
+
+impl Sgd {
+    /// Momentum of `0.0` gives the plain gradient descent.
+    pub fn new(learning_rate: f32, momentum: f32) -> Self {
+        Self {
+            learning_rate,
+            momentum,
+            velocities: Vec::new(),
+        }
+    }
+}
+
+impl GradientDescent for Sgd {
+    fn step(&mut self, network: &mut Network, gradients: &Gradients) {
+        self.velocities.resize(network.weights().count(), 0.0);
+
+        for ((weight, velocity), grad) in network
+            .weights_mut()
+            .zip(&mut self.velocities)
+            .zip(gradients.iter())
+        {
+            *velocity = self.momentum * *velocity - self.learning_rate * grad;
+            *weight += *velocity;
+        }
+    }
+}
+
+/// Adam - https://arxiv.org/abs/1412.6980.
+#[derive(Clone, Debug)]
+pub struct Adam {
+    learning_rate: f32,
+    beta1: f32,
+    beta2: f32,
+    epsilon: f32,
+    moments: Vec<f32>,
+    velocities: Vec<f32>,
+    steps: i32,
+}
+
+impl Adam {
+    /// Uses the decay rates recommended by the paper.
+    pub fn new(learning_rate: f32) -> Self {
+        Self::with_decays(learning_rate, 0.9, 0.999)
+    }
+
+    pub fn with_decays(learning_rate: f32, beta1: f32, beta2: f32) -> Self {
+        Self {
+            learning_rate,
+            beta1,
+            beta2,
+            epsilon: 1e-8,
+            moments: Vec::new(),
+            velocities: Vec::new(),
+            steps: 0,
+        }
+    }
+}
+
+impl GradientDescent for Adam {
+    fn step(&mut self, network: &mut Network, gradients: &Gradients) {
+        let weights = network.weights().count();
+
+        self.moments.resize(weights, 0.0);
+        self.velocities.resize(weights, 0.0);
+        self.steps += 1;
+
+        let moment_correction = 1.0 - self.beta1.powi(self.steps);
+        let velocity_correction = 1.0 - self.beta2.powi(self.steps);
+
+        for (((weight, moment), velocity), grad) in network
+            .weights_mut()
+            .zip(&mut self.moments)
+            .zip(&mut self.velocities)
+            .zip(gradients.iter())
+        {
+            *moment = self.beta1 * *moment + (1.0 - self.beta1) * grad;
+            *velocity = self.beta2 * *velocity + (1.0 - self.beta2) * grad * grad;
+
+            let moment = *moment / moment_correction;
+            let velocity = *velocity / velocity_correction;
+
+            *weight -= self.learning_rate * moment / (velocity.sqrt() + self.epsilon);
+        }
+    }
+}
+
+impl Network {
+    /// Panics if the sample doesn't fit the network (see
+    /// `try_backprop()`).
+    pub fn backprop(&self, sample: &Sample, loss: Loss) -> (f32, Gradients) {
+        self.try_backprop(sample, loss)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Computes the loss for given sample, together with its gradients.
+    pub fn try_backprop(&self, sample: &Sample, loss: Loss) -> Result<(f32, Gradients), Error> {
+        let expected = self.layers.last().unwrap().output_size();
+
+        if sample.targets.len() != expected {
+            return Err(Error::WrongTargetCount {
+                expected,
+                found: sample.targets.len(),
+            });
+        }
+
+        let activations = self.try_propagate_traced(sample.inputs.clone())?;
+        let (loss, mut deltas) = loss.eval(activations.last().unwrap(), &sample.targets);
+        let mut layers = vec![Vec::new(); self.layers.len()];
+
+        for (idx, layer) in self.layers.iter().enumerate().rev() {
+            let inputs = &activations[idx];
+            let outputs = &activations[idx + 1];
+
+            // ReLU passes the gradient only through neurons that've fired
+            // (the output layer is linear, so there it passes as it is)
+            if Activation::of(idx, self.layers.len()) == Activation::Relu {
+                for (delta, output) in deltas.iter_mut().zip(outputs) {
+                    if *output <= 0.0 {
+                        *delta = 0.0;
+                    }
+                }
+            }
+
+            let mut input_deltas = vec![0.0; inputs.len()];
+
+            for (neuron, delta) in layer.neurons.iter().zip(&deltas) {
+                layers[idx].extend(once(*delta).chain(inputs.iter().map(|input| delta * input)));
+
+                for (input_delta, weight) in input_deltas.iter_mut().zip(&neuron.weights) {
+                    *input_delta += delta * weight;
+                }
+            }
+
+            deltas = input_deltas;
+        }
+
+        Ok((loss, Gradients { layers }))
+    }
+
+    /// Panics if any of the samples doesn't fit the network (see
+    /// `try_train_epoch()`).
+    pub fn train_epoch(
+        &mut self,
+        rng: &mut dyn RngCore,
+        samples: &[Sample],
+        loss: Loss,
+        optimizer: &mut dyn GradientDescent,
+        batch_size: usize,
+    ) -> f32 {
+        self.try_train_epoch(rng, samples, loss, optimizer, batch_size)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Goes through all the samples once, in random order, updating the
+    /// weights after each mini-batch of `batch_size` samples; returns the
+    /// mean loss.
+    pub fn try_train_epoch(
+        &mut self,
+        rng: &mut dyn RngCore,
+        samples: &[Sample],
+        loss: Loss,
+        optimizer: &mut dyn GradientDescent,
+        batch_size: usize,
+    ) -> Result<f32, Error> {
+        if batch_size == 0 {
+            return Err(Error::InvalidBatchSize);
+        }
+
+        let mut order: Vec<_> = samples.iter().collect();
+        order.shuffle(rng);
+
+        let mut total = 0.0;
+
+        for batch in order.chunks(batch_size) {
+            let mut batch_loss = 0.0;
+            let mut gradients: Option<Gradients> = None;
+
+            for sample in batch {
+                let (loss, sample_gradients) = self.try_backprop(sample, loss)?;
+
+                batch_loss += loss;
+
+                match &mut gradients {
+                    Some(gradients) => gradients.add(&sample_gradients),
+                    None => gradients = Some(sample_gradients),
+                }
+            }
+
+            let mut gradients = gradients.unwrap();
+            gradients.scale(1.0 / batch.len() as f32);
+
+            optimizer.step(self, &gradients);
+            total += batch_loss;
+        }
+
+        Ok(total / samples.len().max(1) as f32)
+    }
+
+    fn weights_mut(&mut self) -> impl Iterator<Item = &mut f32> + '_ {
+        self.layers
+            .iter_mut()
+            .flat_map(|layer| layer.neurons.iter_mut())
+            .flat_map(|neuron| once(&mut neuron.bias).chain(&mut neuron.weights))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LayerTopology;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    fn network(rng: &mut dyn RngCore, layers: &[usize]) -> Network {
+        let layers: Vec<_> = layers
+            .iter()
+            .map(|&neurons| LayerTopology { neurons })
+            .collect();
+
+        Network::random(rng, &layers)
+    }
+
+    /// Compares gradients computed by `backprop()` against the ones
+    /// approximated with finite differences.
+    fn check_gradients(loss: Loss, targets: Vec<f32>) {
+        const EPSILON: f32 = 1e-3;
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let network = network(&mut rng, &[4, 12, 3]);
+
+        let sample = Sample {
+            inputs: vec![0.5, 0.9, 0.1, 0.7],
+            targets,
+        };
+
+        let (_, gradients) = network.backprop(&sample, loss);
+        let weights: Vec<_> = network.weights().collect();
+        let topology = [4, 12, 3].map(|neurons| LayerTopology { neurons });
+
+        let loss_at = |weights: Vec<f32>| {
+            let network = Network::from_weights(&topology, weights);
+            loss.eval(&network.propagate(sample.inputs.clone()), &sample.targets)
+                .0
+        };
+
+        let mut checked = 0;
+
+        for (idx, actual) in gradients.iter().enumerate() {
+            let mut plus = weights.clone();
+            plus[idx] += EPSILON;
+
+            let mut minus = weights.clone();
+            minus[idx] -= EPSILON;
+
+            let expected = (loss_at(plus) - loss_at(minus)) / (2.0 * EPSILON);
+
+            assert!(
+                (actual - expected).abs() <= 1e-2 * expected.abs().max(1e-1),
+                "weight #{}: backprop says {}, finite differences say {}",
+                idx,
+                actual,
+                expected
+            );
+
+            if actual != 0.0 {
+                checked += 1;
+            }
+        }
+
+        // Otherwise the test would pass for a network of dead neurons
+        assert!(checked > weights.len() / 4, "{}", checked);
+    }
+
+    #[test]
+    fn gradients_of_mean_squared_error() {
+        check_gradients(Loss::MeanSquaredError, vec![0.2, 1.5, 0.0]);
+    }
+
+    #[test]
+    fn gradients_of_cross_entropy() {
+        check_gradients(Loss::CrossEntropy, vec![0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn per_layer_gradients() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let network = network(&mut rng, &[4, 12, 3]);
+
+        let sample = Sample {
+            inputs: vec![0.5, 0.9, 0.1, 0.7],
+            targets: vec![0.0, 0.0, 0.0],
+        };
+
+        let (_, gradients) = network.backprop(&sample, Loss::MeanSquaredError);
+
+        assert_eq!(gradients.layers().len(), 2);
+        assert_eq!(gradients.layers()[0].len(), 12 * 5);
+        assert_eq!(gradients.layers()[1].len(), 3 * 13);
+        assert_eq!(gradients.norms().len(), 2);
+
+        assert_eq!(
+            network.try_backprop(
+                &Sample {
+                    inputs: vec![0.0; 4],
+                    targets: vec![0.0; 2],
+                },
+                Loss::MeanSquaredError
+            ),
+            Err(Error::WrongTargetCount {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn cross_entropy() {
+        let (loss, derivatives) = Loss::CrossEntropy.eval(&[0.0, 0.0], &[1.0, 0.0]);
+
+        assert!((loss - 2.0f32.ln()).abs() < 1e-6);
+        assert_eq!(derivatives, vec![-0.5, 0.5]);
+    }
+
+    /// Learns `y = (x0 + x1, x0 * 2)` from 64 samples.
+    fn learn(optimizer: &mut dyn GradientDescent) -> (f32, f32) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut network = network(&mut rng, &[2, 12, 2]);
+
+        let samples: Vec<_> = (0..64)
+            .map(|_| {
+                let (x0, x1) = (rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0));
+
+                Sample {
+                    inputs: vec![x0, x1],
+                    targets: vec![x0 + x1, x0 * 2.0],
+                }
+            })
+            .collect();
+
+        let first = network.train_epoch(&mut rng, &samples, Loss::MeanSquaredError, optimizer, 8);
+        let mut last = first;
+
+        for _ in 0..200 {
+            last = network.train_epoch(&mut rng, &samples, Loss::MeanSquaredError, optimizer, 8);
+        }
+
+        (first, last)
+    }
+
+    #[test]
+    fn empty_batches() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut network = network(&mut rng, &[2, 3, 1]);
+
+        let samples = [Sample {
+            inputs: vec![0.5, 0.5],
+            targets: vec![1.0],
+        }];
+
+        let err = network
+            .try_train_epoch(
+                &mut rng,
+                &samples,
+                Loss::MeanSquaredError,
+                &mut Sgd::new(0.05, 0.9),
+                0,
+            )
+            .unwrap_err();
+
+        assert_eq!(err, Error::InvalidBatchSize);
+        assert_eq!(err.to_string(), "batch size must be positive");
+    }
+
+    #[test]
+    fn sgd() {
+        let (first, last) = learn(&mut Sgd::new(0.05, 0.9));
+
+        assert!(last < first / 10.0, "{} -> {}", first, last);
+        assert!(last < 0.01, "{}", last);
+    }
+
+    #[test]
+    fn adam() {
+        let (first, last) = learn(&mut Adam::new(0.01));
+
+        assert!(last < first / 10.0, "{} -> {}", first, last);
+        assert!(last < 0.01, "{}", last);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{Canvas, Renderer};
+use lib_genetic_algorithm as ga;
+use lib_simulation::Simulation;
+use rand::RngCore;
+
+/// Animated GIF, built frame by frame.
+pub struct Animation {
+    encoder: gif::Encoder<Vec<u8>>,
+    width: u16,
+    height: u16,
+
+    /// Delay between frames, in hundredths of a second.
+    delay: u16,
+}
+
+impl Animation {
+    /// `delay` is the time between frames, in hundredths of a second (as
+    /// that's the GIF's resolution).
+    pub fn new(width: u32, height: u32, delay: u16) -> Self {
+        let width = width
+            .try_into()
+            .expect("GIFs can be at most 65535 pixels wide");
+        let height = height
+            .try_into()
+            .expect("GIFs can be at most 65535 pixels high");
+
+        let mut encoder = gif::Encoder::new(Vec::new(), width, height, &[])
+            .expect("writing into memory cannot fail");
+
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .expect("writing into memory cannot fail");
+
+        Self {
+            encoder,
+            width,
+            height,
+            delay,
+        }
+    }
+
+    pub fn push(&mut self, canvas: &Canvas) {
+        assert_eq!(
+            (canvas.width(), canvas.height()),
+            (self.width as u32, self.height as u32),
+            "all frames must be of the same size"
+        );
+
+        let mut pixels = canvas.pixels().to_vec();
+
+        // Speed of 10 is what the `gif` crate recommends as a reasonable
+        // trade-off between quality and time spent on finding the palette
+        let mut frame = gif::Frame::from_rgba_speed(self.width, self.height, &mut pixels, 10);
+        frame.delay = self.delay;
+
+        self.encoder
+            .write_frame(&frame)
+            .expect("writing into memory cannot fail");
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.encoder
+            .into_inner()
+            .expect("writing into memory cannot fail")
+    }
+}
+
+/// Steps `simulation` until the end of current generation, rendering
+/// every `every`-th step into an animated GIF.
+pub fn render_generation(
+    simulation: &mut Simulation,
+    rng: &mut dyn RngCore,
+    renderer: &mut Renderer,
+    every: usize,
+) -> (Vec<u8>, ga::Statistics) {
+    assert!(every > 0);
+
+    let options = renderer.options();
+    let mut animation = Animation::new(options.width, options.height, 4);
+
+    for step in 0.. {
+        if step % every == 0 {
+            animation.push(&renderer.render(simulation.world()));
+        }
+
+        if let Some(stats) = simulation.step(rng) {
+            return (animation.finish(), stats);
+        }
+    }
+
+    unreachable!("generations always end")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RenderOptions;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn renders_whole_generation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(&mut rng);
+
+        let mut renderer = Renderer::new(RenderOptions {
+            width: 100,
+            height: 80,
+            vision_cones: true,
+            trail_length: 10,
+        });
+
+        let (gif, _) = render_generation(&mut sim, &mut rng, &mut renderer, 500);
+
+        assert_eq!(sim.generation(), 1);
+
+        let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+
+        let mut frames = 0;
+
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height), (100, 80));
+            frames += 1;
+        }
+
+        // Generation lasts for 2501 steps
+        assert_eq!(frames, 6);
+    }
+}
@@ -0,0 +1,243 @@
+/// RGBA color; alpha of 255 means fully opaque.
+pub type Color = [u8; 4];
+
+/// RGBA image drawn on the CPU.
+///
+/// Coordinates are in pixels, with (0, 0) being the top-left corner;
+/// shapes cover pixels whose *centers* lie inside of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    pub fn new(width: u32, height: u32, background: Color) -> Self {
+        assert!(width > 0 && height > 0);
+
+        let pixels = background
+            .iter()
+            .copied()
+            .cycle()
+            .take(4 * width as usize * height as usize)
+            .collect();
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Raw pixels, row by row, four bytes (RGBA) each.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> Color {
+        let idx = self.index(x, y);
+        self.pixels[idx..idx + 4].try_into().unwrap()
+    }
+
+    /// Paints given pixel, blending `color` over what's already there;
+    /// pixels outside of the canvas are ignored.
+    pub fn blend(&mut self, x: i64, y: i64, color: Color) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+
+        let idx = self.index(x as u32, y as u32);
+        let alpha = color[3] as u32;
+
+        for (dst, src) in self.pixels[idx..idx + 3].iter_mut().zip(color) {
+            *dst = ((src as u32 * alpha + *dst as u32 * (255 - alpha)) / 255) as u8;
+        }
+
+        self.pixels[idx + 3] = self.pixels[idx + 3].max(color[3]);
+    }
+
+    /// Paints all the pixels within given bounding box (`[min, max]`,
+    /// inclusive) whose centers satisfy `predicate`.
+    pub fn fill_where(
+        &mut self,
+        [min, max]: [[f32; 2]; 2],
+        color: Color,
+        predicate: impl Fn(f32, f32) -> bool,
+    ) {
+        let (x0, y0) = (
+            min[0].floor().max(0.0) as i64,
+            min[1].floor().max(0.0) as i64,
+        );
+        let (x1, y1) = (
+            max[0].ceil().min(self.width as f32) as i64,
+            max[1].ceil().min(self.height as f32) as i64,
+        );
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                if predicate(x as f32 + 0.5, y as f32 + 0.5) {
+                    self.blend(x, y, color);
+                }
+            }
+        }
+    }
+
+    pub fn fill_triangle(&mut self, [a, b, c]: [[f32; 2]; 3], color: Color) {
+        // Signed area of the parallelogram spanned by `from -> to` and
+        // `from -> p` - positive on one side of the edge, negative on the
+        // other
+        let edge = |from: [f32; 2], to: [f32; 2], x: f32, y: f32| {
+            (to[0] - from[0]) * (y - from[1]) - (to[1] - from[1]) * (x - from[0])
+        };
+
+        let min = [a[0].min(b[0]).min(c[0]), a[1].min(b[1]).min(c[1])];
+        let max = [a[0].max(b[0]).max(c[0]), a[1].max(b[1]).max(c[1])];
+
+        self.fill_where([min, max], color, |x, y| {
+            let edges = [edge(a, b, x, y), edge(b, c, x, y), edge(c, a, x, y)];
+
+            edges.iter().all(|e| *e >= 0.0) || edges.iter().all(|e| *e <= 0.0)
+        });
+    }
+
+    pub fn fill_circle(&mut self, [cx, cy]: [f32; 2], radius: f32, color: Color) {
+        let bounds = [[cx - radius, cy - radius], [cx + radius, cy + radius]];
+
+        self.fill_where(bounds, color, |x, y| {
+            (x - cx).powi(2) + (y - cy).powi(2) <= radius * radius
+        });
+    }
+
+    /// Draws a one pixel wide line.
+    pub fn draw_line(&mut self, from: [f32; 2], to: [f32; 2], color: Color) {
+        let steps = (to[0] - from[0])
+            .abs()
+            .max((to[1] - from[1]).abs())
+            .ceil()
+            .max(1.0);
+
+        for step in 0..=steps as usize {
+            let t = step as f32 / steps;
+            let x = from[0] + (to[0] - from[0]) * t;
+            let y = from[1] + (to[1] - from[1]) * t;
+
+            self.blend(x.floor() as i64, y.floor() as i64, color);
+        }
+    }
+
+    /// Encodes this canvas as a PNG image.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width, self.height);
+
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.pixels))
+            .expect("writing into memory cannot fail");
+
+        bytes
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        4 * (y as usize * self.width as usize + x as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Color = [0, 0, 0, 255];
+    const WHITE: Color = [255, 255, 255, 255];
+
+    /// Renders canvas as ASCII-art, `#` marking non-black pixels.
+    fn ascii(canvas: &Canvas) -> Vec<String> {
+        (0..canvas.height())
+            .map(|y| {
+                (0..canvas.width())
+                    .map(|x| {
+                        if canvas.pixel(x, y) == BLACK {
+                            '.'
+                        } else {
+                            '#'
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn triangle() {
+        let mut canvas = Canvas::new(6, 4, BLACK);
+        canvas.fill_triangle([[0.0, 0.0], [6.0, 0.0], [0.0, 4.0]], WHITE);
+
+        assert_eq!(ascii(&canvas), vec!["#####.", "####..", "##....", "#....."]);
+
+        // Winding doesn't matter
+        let mut reversed = Canvas::new(6, 4, BLACK);
+        reversed.fill_triangle([[0.0, 4.0], [6.0, 0.0], [0.0, 0.0]], WHITE);
+
+        assert_eq!(canvas, reversed);
+    }
+
+    #[test]
+    fn circle() {
+        let mut canvas = Canvas::new(7, 7, BLACK);
+        canvas.fill_circle([3.5, 3.5], 2.5, WHITE);
+
+        assert_eq!(
+            ascii(&canvas),
+            vec![".......", "..###..", ".#####.", ".#####.", ".#####.", "..###..", "......."]
+        );
+    }
+
+    #[test]
+    fn line() {
+        let mut canvas = Canvas::new(5, 3, BLACK);
+        canvas.draw_line([0.5, 0.5], [4.5, 2.5], WHITE);
+
+        assert_eq!(ascii(&canvas), vec!["#....", ".##..", "...##"]);
+    }
+
+    #[test]
+    fn blending() {
+        let mut canvas = Canvas::new(2, 1, [0, 0, 0, 0]);
+
+        canvas.blend(0, 0, [255, 0, 100, 255]);
+        canvas.blend(1, 0, [255, 0, 100, 51]);
+
+        // Pixels outside of the canvas are ignored
+        canvas.blend(-1, 0, WHITE);
+        canvas.blend(2, 0, WHITE);
+
+        assert_eq!(canvas.pixel(0, 0), [255, 0, 100, 255]);
+        assert_eq!(canvas.pixel(1, 0), [51, 0, 20, 51]);
+    }
+
+    #[test]
+    fn png() {
+        let mut canvas = Canvas::new(3, 2, BLACK);
+        canvas.blend(1, 1, WHITE);
+
+        let png = canvas.to_png();
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(pixels, canvas.pixels());
+    }
+}
@@ -0,0 +1,10 @@
+//! Draws simulation on the CPU, without a browser - e.g. to attach PNG
+//! frames or GIFs of a run to an experiment's report.
+
+mod animation;
+mod canvas;
+mod renderer;
+
+pub use animation::{render_generation, Animation};
+pub use canvas::{Canvas, Color};
+pub use renderer::{RenderOptions, Renderer};
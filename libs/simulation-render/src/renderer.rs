@@ -0,0 +1,259 @@
+use crate::{Canvas, Color};
+use lib_simulation::{Animal, Point, World};
+use std::collections::{BTreeMap, VecDeque};
+use std::f32::consts::{FRAC_PI_3, PI};
+
+/// Colors borrowed from `template/`, so that both renderers look alike.
+const BACKGROUND: Color = [0x1f, 0x26, 0x39, 255];
+const BIRD: Color = [255, 255, 255, 255];
+const BIRD_OUTLINE: Color = [0, 0, 0, 255];
+const FOOD: Color = [0, 255, 128, 255];
+const VISION_CONE: Color = [255, 255, 255, 24];
+const TRAIL: Color = [255, 255, 255, 96];
+
+#[derive(Clone, Debug)]
+pub struct RenderOptions {
+    pub width: u32,
+    pub height: u32,
+
+    /// Whether to draw the area each bird sees.
+    pub vision_cones: bool,
+
+    /// Number of past positions drawn behind each bird; 0 disables trails.
+    pub trail_length: usize,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            width: 1000,
+            height: 800,
+            vision_cones: false,
+            trail_length: 0,
+        }
+    }
+}
+
+/// Draws `World`s the way `template/index.js` draws them on canvas.
+///
+/// Renderer remembers where birds have been (for their trails), so a
+/// single renderer should be used for the consecutive steps of a single
+/// simulation.
+#[derive(Debug)]
+pub struct Renderer {
+    options: RenderOptions,
+
+    /// Past positions of birds, by bird's id, oldest first.
+    trails: BTreeMap<u64, VecDeque<Point>>,
+}
+
+impl Renderer {
+    pub fn new(options: RenderOptions) -> Self {
+        Self {
+            options,
+            trails: BTreeMap::new(),
+        }
+    }
+
+    pub fn options(&self) -> &RenderOptions {
+        &self.options
+    }
+
+    pub fn render(&mut self, world: &World) -> Canvas {
+        let mut canvas = Canvas::new(self.options.width, self.options.height, BACKGROUND);
+
+        self.update_trails(world);
+
+        if self.options.vision_cones {
+            for animal in world.animals() {
+                self.draw_vision_cone(&mut canvas, animal);
+            }
+        }
+
+        for trail in self.trails.values() {
+            self.draw_trail(&mut canvas, trail);
+        }
+
+        for food in world.foods() {
+            let size = 0.01 * self.options.width as f32;
+            canvas.fill_circle(self.project(food.position()), size / 2.0, FOOD);
+        }
+
+        for animal in world.animals() {
+            self.draw_bird(&mut canvas, animal);
+        }
+
+        canvas
+    }
+
+    fn update_trails(&mut self, world: &World) {
+        if self.options.trail_length == 0 {
+            return;
+        }
+
+        // Birds of past generations are gone for good
+        self.trails
+            .retain(|id, _| world.animals().iter().any(|animal| animal.id() == *id));
+
+        for animal in world.animals() {
+            let trail = self.trails.entry(animal.id()).or_default();
+
+            trail.push_back(animal.position());
+
+            if trail.len() > self.options.trail_length {
+                trail.pop_front();
+            }
+        }
+    }
+
+    /// Same triangle as `drawTriangle()`.
+    fn draw_bird(&self, canvas: &mut Canvas, animal: &Animal) {
+        let [x, y] = self.project(animal.position());
+        let size = 0.01 * self.options.width as f32;
+        let rotation = animal.rotation().angle();
+
+        let vertex = |angle: f32, size: f32| [x - angle.sin() * size, y + angle.cos() * size];
+
+        let vertices = [
+            vertex(rotation, size * 1.5),
+            vertex(rotation + 2.0 * FRAC_PI_3, size),
+            vertex(rotation + 4.0 * FRAC_PI_3, size),
+        ];
+
+        canvas.fill_triangle(vertices, BIRD);
+
+        for (from, to) in vertices.iter().zip(vertices.iter().cycle().skip(1)) {
+            canvas.draw_line(*from, *to, BIRD_OUTLINE);
+        }
+    }
+
+    /// Highlights the area in which `Eye::process_vision()` notices food.
+    fn draw_vision_cone(&self, canvas: &mut Canvas, animal: &Animal) {
+        let eye = animal.eye();
+        let position = animal.position();
+        let rotation = animal.rotation().angle();
+        let (width, height) = (self.options.width as f32, self.options.height as f32);
+
+        let [x, y] = self.project(position);
+        let [rx, ry] = [eye.fov_range() * width, eye.fov_range() * height];
+
+        canvas.fill_where(
+            [[x - rx, y - ry], [x + rx, y + ry]],
+            VISION_CONE,
+            |px, py| {
+                let (dx, dy) = (px / width - position.x, py / height - position.y);
+
+                if (dx * dx + dy * dy).sqrt() >= eye.fov_range() {
+                    return false;
+                }
+
+                // Angle relative to the Y axis, as in `Eye::process_vision()`
+                let angle = (-dx).atan2(dy) - rotation;
+                let angle = (angle + PI).rem_euclid(2.0 * PI) - PI;
+
+                angle.abs() <= eye.fov_angle() / 2.0
+            },
+        );
+    }
+
+    fn draw_trail(&self, canvas: &mut Canvas, trail: &VecDeque<Point>) {
+        for (from, to) in trail.iter().zip(trail.iter().skip(1)) {
+            // Birds wrap around the edges of the world - don't draw lines
+            // across the whole canvas when that happens
+            if (to - from).norm() > 0.5 {
+                continue;
+            }
+
+            canvas.draw_line(self.project(*from), self.project(*to), TRAIL);
+        }
+    }
+
+    /// Converts world's coordinates (0.0 ..= 1.0) into canvas' ones.
+    fn project(&self, point: Point) -> [f32; 2] {
+        [
+            point.x * self.options.width as f32,
+            point.y * self.options.height as f32,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn world() -> World {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        World::random_sized(&mut rng, 3, 5)
+    }
+
+    fn pixel_at(canvas: &Canvas, point: Point) -> Color {
+        canvas.pixel(
+            (point.x * canvas.width() as f32) as u32,
+            (point.y * canvas.height() as f32) as u32,
+        )
+    }
+
+    #[test]
+    fn draws_birds_and_foods() {
+        let world = world();
+        let canvas = Renderer::new(RenderOptions::default()).render(&world);
+
+        assert_eq!((canvas.width(), canvas.height()), (1000, 800));
+        assert_eq!(canvas.pixel(0, 0), BACKGROUND);
+
+        for animal in world.animals() {
+            assert_eq!(pixel_at(&canvas, animal.position()), BIRD);
+        }
+
+        for food in world.foods() {
+            assert_eq!(pixel_at(&canvas, food.position()), FOOD);
+        }
+    }
+
+    #[test]
+    fn draws_vision_cones() {
+        let world = world();
+        let animal = &world.animals()[0];
+
+        let options = RenderOptions {
+            vision_cones: true,
+            ..Default::default()
+        };
+
+        let plain = Renderer::new(RenderOptions::default()).render(&world);
+        let with_cones = Renderer::new(options).render(&world);
+
+        let (position, rotation) = (animal.position(), animal.rotation().angle());
+        let at = |distance: f32| {
+            Point::new(
+                position.x - rotation.sin() * distance,
+                position.y + rotation.cos() * distance,
+            )
+        };
+
+        // Right in front of the bird ...
+        assert_ne!(pixel_at(&with_cones, at(0.1)), pixel_at(&plain, at(0.1)));
+
+        // ... but not behind it
+        assert_eq!(pixel_at(&with_cones, at(-0.1)), pixel_at(&plain, at(-0.1)));
+    }
+
+    #[test]
+    fn remembers_trails() {
+        let world = world();
+
+        let mut renderer = Renderer::new(RenderOptions {
+            trail_length: 2,
+            ..Default::default()
+        });
+
+        renderer.render(&world);
+        renderer.render(&world);
+        renderer.render(&world);
+
+        assert_eq!(renderer.trails.len(), 3);
+        assert!(renderer.trails.values().all(|trail| trail.len() == 2));
+    }
+}
@@ -0,0 +1,186 @@
+use crossterm::event::KeyCode;
+use lib_simulation::{Animal, Simulation};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Highest number of steps simulated per frame when fast-forwarding.
+const MAX_SPEED: usize = 4096;
+
+/// State of the viewer, independent of the terminal - so that it can be
+/// tested without one.
+pub struct App {
+    simulation: Simulation,
+    rng: ChaCha8Rng,
+    paused: bool,
+
+    /// Number of steps simulated per frame.
+    speed: usize,
+
+    /// Index of the selected bird within the world.
+    selected: Option<usize>,
+
+    quit: bool,
+}
+
+impl App {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let simulation = Simulation::random(&mut rng);
+
+        Self {
+            simulation,
+            rng,
+            paused: false,
+            speed: 1,
+            selected: None,
+            quit: false,
+        }
+    }
+
+    pub fn simulation(&self) -> &Simulation {
+        &self.simulation
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn speed(&self) -> usize {
+        self.speed
+    }
+
+    pub fn selected(&self) -> Option<(usize, &Animal)> {
+        self.selected
+            .and_then(|idx| Some((idx, self.simulation.world().animals().get(idx)?)))
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char(' ') => self.paused = !self.paused,
+
+            KeyCode::Char('s') | KeyCode::Char('.') => {
+                self.paused = true;
+                self.simulation.step(&mut self.rng);
+            }
+
+            KeyCode::Char('f') | KeyCode::Char('+') => {
+                self.speed = (self.speed * 4).min(MAX_SPEED);
+            }
+
+            KeyCode::Char('F') | KeyCode::Char('-') => {
+                self.speed = (self.speed / 4).max(1);
+            }
+
+            KeyCode::Char('t') => {
+                self.simulation.train(&mut self.rng);
+            }
+
+            KeyCode::Tab | KeyCode::Char('n') => self.select(1),
+            KeyCode::BackTab | KeyCode::Char('p') => self.select(-1),
+            KeyCode::Esc => self.selected = None,
+
+            KeyCode::Char('q') => self.quit = true,
+
+            _ => {}
+        }
+    }
+
+    /// Advances the simulation by a single frame.
+    pub fn tick(&mut self) {
+        if self.paused {
+            return;
+        }
+
+        for _ in 0..self.speed {
+            self.simulation.step(&mut self.rng);
+        }
+    }
+
+    fn select(&mut self, delta: isize) {
+        let birds = self.simulation.world().animals().len() as isize;
+
+        if birds == 0 {
+            return;
+        }
+
+        self.selected = Some(match self.selected {
+            Some(idx) => (idx as isize + delta).rem_euclid(birds) as usize,
+            None if delta > 0 => 0,
+            None => birds as usize - 1,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pause_and_single_step() {
+        let mut app = App::new(0);
+
+        app.tick();
+        assert_eq!(app.simulation().age(), 1);
+
+        app.handle_key(KeyCode::Char(' '));
+        app.tick();
+        assert!(app.paused());
+        assert_eq!(app.simulation().age(), 1);
+
+        app.handle_key(KeyCode::Char('s'));
+        assert_eq!(app.simulation().age(), 2);
+    }
+
+    #[test]
+    fn fast_forward() {
+        let mut app = App::new(0);
+
+        app.handle_key(KeyCode::Char('f'));
+        app.handle_key(KeyCode::Char('f'));
+        app.tick();
+
+        assert_eq!(app.speed(), 16);
+        assert_eq!(app.simulation().age(), 16);
+
+        for _ in 0..10 {
+            app.handle_key(KeyCode::Char('f'));
+        }
+
+        assert_eq!(app.speed(), MAX_SPEED);
+
+        for _ in 0..10 {
+            app.handle_key(KeyCode::Char('F'));
+        }
+
+        assert_eq!(app.speed(), 1);
+    }
+
+    #[test]
+    fn train_one_generation() {
+        let mut app = App::new(0);
+
+        app.handle_key(KeyCode::Char('t'));
+
+        assert_eq!(app.simulation().generation(), 1);
+        assert_eq!(app.simulation().age(), 0);
+    }
+
+    #[test]
+    fn select_bird() {
+        let mut app = App::new(0);
+        assert!(app.selected().is_none());
+
+        app.handle_key(KeyCode::BackTab);
+        assert_eq!(app.selected().unwrap().0, 9);
+
+        app.handle_key(KeyCode::Tab);
+        assert_eq!(app.selected().unwrap().0, 0);
+
+        app.handle_key(KeyCode::Esc);
+        assert!(app.selected().is_none());
+    }
+}
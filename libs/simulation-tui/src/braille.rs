@@ -0,0 +1,100 @@
+/// Grid of braille characters, each one made of 2x4 dots - that's the
+/// highest "resolution" a plain terminal can offer.
+#[derive(Clone, Debug)]
+pub struct Braille {
+    width: usize,
+    height: usize,
+
+    /// Dots of each character, as bits of its code point (see `DOTS`).
+    cells: Vec<u8>,
+}
+
+/// Bit of each dot within a character, by its (x, y) within that character.
+const DOTS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+impl Braille {
+    /// Creates a grid of given size, in characters.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![0; width * height],
+        }
+    }
+
+    /// Size of the grid, in dots.
+    pub fn dots(&self) -> (usize, usize) {
+        (2 * self.width, 4 * self.height)
+    }
+
+    /// Sets given dot; dots outside of the grid are ignored.
+    pub fn set(&mut self, x: usize, y: usize) {
+        if x >= 2 * self.width || y >= 4 * self.height {
+            return;
+        }
+
+        self.cells[(y / 4) * self.width + x / 2] |= DOTS[x % 2][y % 4];
+    }
+
+    /// Sets dots along a line between given dots.
+    pub fn line(&mut self, (x0, y0): (usize, usize), (x1, y1): (usize, usize)) {
+        let steps = x0.abs_diff(x1).max(y0.abs_diff(y1)).max(1);
+
+        for step in 0..=steps {
+            let t = step as f32 / steps as f32;
+            let x = x0 as f32 + (x1 as f32 - x0 as f32) * t;
+            let y = y0 as f32 + (y1 as f32 - y0 as f32) * t;
+
+            self.set(x.round() as usize, y.round() as usize);
+        }
+    }
+
+    pub fn rows(&self) -> Vec<Vec<char>> {
+        self.cells
+            .chunks(self.width.max(1))
+            .take(self.height)
+            .map(|row| {
+                row.iter()
+                    .map(|&dots| char::from_u32(0x2800 + dots as u32).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(braille: &Braille) -> Vec<String> {
+        braille
+            .rows()
+            .into_iter()
+            .map(|row| row.into_iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn dots() {
+        let mut braille = Braille::new(2, 1);
+
+        braille.set(0, 0);
+        braille.set(1, 3);
+        braille.set(2, 1);
+
+        // Outside of the grid
+        braille.set(4, 0);
+        braille.set(0, 4);
+
+        assert_eq!(braille.dots(), (4, 4));
+        assert_eq!(rows(&braille), vec!["⢁⠂"]);
+    }
+
+    #[test]
+    fn line() {
+        let mut braille = Braille::new(2, 2);
+        braille.line((0, 0), (3, 7));
+
+        assert_eq!(rows(&braille), vec!["⢣⠀", "⠀⢣"]);
+    }
+}
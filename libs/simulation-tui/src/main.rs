@@ -0,0 +1,82 @@
+//! Live terminal viewer of the simulation - handy when training over SSH.
+//!
+//! Usage: `simulation-tui [seed]`
+
+mod app;
+mod braille;
+mod view;
+
+use app::App;
+use crossterm::event::{self, Event, KeyEventKind};
+use crossterm::{cursor, execute, queue, terminal};
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// Time between frames.
+const FRAME: Duration = Duration::from_millis(33);
+
+fn main() -> io::Result<()> {
+    let seed = match std::env::args().nth(1) {
+        Some(seed) => seed
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?,
+        None => rand::random(),
+    };
+
+    let mut app = App::new(seed);
+    let _terminal = Terminal::enter()?;
+    let mut stdout = io::stdout();
+
+    while !app.should_quit() {
+        let (width, height) = terminal::size()?;
+
+        queue!(stdout, cursor::MoveTo(0, 0))?;
+
+        for (idx, line) in view::render(&app, width as usize, height as usize)
+            .iter()
+            .enumerate()
+        {
+            queue!(
+                stdout,
+                cursor::MoveTo(0, idx as u16),
+                terminal::Clear(terminal::ClearType::UntilNewLine)
+            )?;
+
+            write!(stdout, "{}", line)?;
+        }
+
+        stdout.flush()?;
+
+        if event::poll(FRAME)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key.code);
+                }
+            }
+        }
+
+        app.tick();
+    }
+
+    Ok(())
+}
+
+/// Puts terminal into raw mode & alternate screen for as long as it lives,
+/// restoring it even if we panic.
+struct Terminal;
+
+impl Terminal {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        Ok(Self)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        _ = terminal::disable_raw_mode();
+    }
+}
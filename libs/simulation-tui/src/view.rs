@@ -0,0 +1,272 @@
+use crate::app::App;
+use crate::braille::Braille;
+use lib_simulation::{Animal, Config, World};
+use std::f32::consts::FRAC_PI_4;
+
+/// Birds, by their heading - starting from "right" and going clockwise
+/// (on screen, the Y axis points down).
+const ARROWS: [char; 8] = ['→', '↘', '↓', '↙', '←', '↖', '↑', '↗'];
+
+/// Ditto, for the selected bird.
+const SELECTED_ARROWS: [char; 8] = ['⇒', '⇘', '⇓', '⇙', '⇐', '⇖', '⇑', '⇗'];
+
+/// Vision cells' values, from none to "lots of food".
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Number of lines below the world's plot.
+const PANEL_LINES: usize = 9;
+
+/// Width of charts' labels (e.g. `max fitness`).
+const LABEL_WIDTH: usize = 12;
+
+const HELP: &str =
+    "[space] pause  [s] step  [f/F] faster/slower  [t] train generation  [tab] select bird  [q] quit";
+
+/// Draws the whole screen, as lines of text.
+pub fn render(app: &App, width: usize, height: usize) -> Vec<String> {
+    let sim = app.simulation();
+    let plot_width = width.saturating_sub(2).max(1);
+    let plot_height = height.saturating_sub(PANEL_LINES + 2).max(1);
+
+    let mut lines = Vec::with_capacity(height);
+
+    lines.push(format!("┌{}┐", "─".repeat(plot_width)));
+
+    for row in plot_world(
+        sim.world(),
+        app.selected().map(|(idx, _)| idx),
+        plot_width,
+        plot_height,
+    ) {
+        lines.push(format!("│{}│", row.into_iter().collect::<String>()));
+    }
+
+    lines.push(format!("└{}┘", "─".repeat(plot_width)));
+
+    lines.push(format!(
+        "generation {} · age {} · {} steps/frame · {}",
+        sim.generation(),
+        sim.age(),
+        app.speed(),
+        if app.paused() { "paused" } else { "running" }
+    ));
+
+    let history = sim.history();
+    let chart_width = width.saturating_sub(LABEL_WIDTH + 10).max(1);
+
+    for (label, series) in [
+        (
+            "max fitness",
+            history
+                .iter()
+                .map(|stats| stats.max_fitness)
+                .collect::<Vec<_>>(),
+        ),
+        (
+            "avg fitness",
+            history.iter().map(|stats| stats.avg_fitness).collect(),
+        ),
+    ] {
+        let last = series
+            .last()
+            .map_or(String::from("-"), |value| format!("{:.1}", value));
+
+        for (idx, row) in chart(&series, chart_width, 2).into_iter().enumerate() {
+            let (label, last) = if idx == 0 {
+                (label, last.as_str())
+            } else {
+                ("", "")
+            };
+
+            lines.push(format!(
+                "{:<width$}{} {}",
+                label,
+                row.into_iter().collect::<String>(),
+                last,
+                width = LABEL_WIDTH
+            ));
+        }
+    }
+
+    lines.extend(describe_bird(
+        app.selected().map(|(_, animal)| animal),
+        sim.world(),
+        sim.config(),
+    ));
+    lines.push(String::from(HELP));
+
+    lines
+        .into_iter()
+        .map(|line| line.chars().take(width).collect())
+        .collect()
+}
+
+/// Foods as braille dots, birds as arrows pointing where they fly.
+fn plot_world(
+    world: &World,
+    selected: Option<usize>,
+    width: usize,
+    height: usize,
+) -> Vec<Vec<char>> {
+    let mut braille = Braille::new(width, height);
+    let (dots_x, dots_y) = braille.dots();
+
+    for food in world.foods() {
+        let position = food.position();
+
+        braille.set(scale(position.x, dots_x), scale(position.y, dots_y));
+    }
+
+    let mut rows = braille.rows();
+
+    for (idx, animal) in world.animals().iter().enumerate() {
+        let position = animal.position();
+        let arrows = if Some(idx) == selected {
+            &SELECTED_ARROWS
+        } else {
+            &ARROWS
+        };
+
+        rows[scale(position.y, height)][scale(position.x, width)] = arrows[heading(animal)];
+    }
+
+    rows
+}
+
+/// Braille line chart of given series, scaled to fit; shows only as many
+/// of the latest values as there are dots.
+fn chart(series: &[f32], width: usize, height: usize) -> Vec<Vec<char>> {
+    let mut braille = Braille::new(width, height);
+    let (dots_x, dots_y) = braille.dots();
+
+    let series = &series[series.len().saturating_sub(dots_x)..];
+    let max = series.iter().copied().fold(0.0, f32::max);
+
+    let points: Vec<_> = series
+        .iter()
+        .enumerate()
+        .map(|(x, value)| {
+            let y = if max > 0.0 { value / max } else { 0.0 };
+            (x, dots_y - 1 - scale(y, dots_y))
+        })
+        .collect();
+
+    match points.as_slice() {
+        [] => {}
+        [point] => braille.set(point.0, point.1),
+        points => {
+            for pair in points.windows(2) {
+                braille.line(pair[0], pair[1]);
+            }
+        }
+    }
+
+    braille.rows()
+}
+
+fn describe_bird(animal: Option<&Animal>, world: &World, config: &Config) -> Vec<String> {
+    let Some(animal) = animal else {
+        return vec![
+            String::from("no bird selected - press [tab] to select one"),
+            String::new(),
+            String::new(),
+        ];
+    };
+
+    // Birds remember what they've seen and thought during the last step;
+    // freshly born ones haven't made any yet, so let's ask them now
+    let (inputs, response) = match animal.activations() {
+        [inputs, .., response] => (inputs.clone(), response.clone()),
+        _ => {
+            let inputs = animal.perceive(world.foods(), config);
+            let response = animal.brain().propagate(inputs.clone());
+
+            (inputs, response)
+        }
+    };
+
+    // Sensors' readings (if any) follow the eye's cells
+    let vision = &inputs[..inputs.len().min(animal.eye().cells())];
+
+    let bars: String = vision
+        .iter()
+        .map(|cell| BARS[scale(cell.clamp(0.0, 1.0), BARS.len())])
+        .collect();
+
+    let values: Vec<_> = vision.iter().map(|cell| format!("{:.2}", cell)).collect();
+
+    vec![
+        format!(
+            "bird #{} · satiation {} · speed {:.4}",
+            animal.id(),
+            animal.satiation(),
+            animal.speed()
+        ),
+        format!("vision  {}  {}", bars, values.join(" ")),
+        format!(
+            "brain   Δspeed {:+.4}  Δrotation {:+.4}",
+            response[0], response[1]
+        ),
+    ]
+}
+
+/// Index of bird's arrow within `ARROWS`.
+fn heading(animal: &Animal) -> usize {
+    // Birds fly along their rotated Y axis (see `process_movements()`)
+    let rotation = animal.rotation().angle();
+    let angle = rotation.cos().atan2(-rotation.sin());
+
+    (angle / FRAC_PI_4).round().rem_euclid(8.0) as usize
+}
+
+/// Maps value from 0.0 ..= 1.0 to 0 .. len.
+fn scale(value: f32, len: usize) -> usize {
+    ((value * len as f32) as usize).min(len - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_the_screen() {
+        let app = App::new(0);
+        let lines = render(&app, 100, 40);
+
+        assert_eq!(lines.len(), 40);
+        assert!(lines.iter().all(|line| line.chars().count() <= 100));
+
+        // All the birds are visible
+        let birds = lines
+            .iter()
+            .flat_map(|line| line.chars())
+            .filter(|c| ARROWS.contains(c))
+            .count();
+
+        assert_eq!(birds, 10);
+    }
+
+    #[test]
+    fn describes_selected_bird() {
+        let mut app = App::new(0);
+        app.handle_key(crossterm::event::KeyCode::Tab);
+
+        let lines = render(&app, 200, 40);
+        let id = app.selected().unwrap().1.id();
+
+        assert!(lines
+            .iter()
+            .any(|line| line.chars().any(|c| SELECTED_ARROWS.contains(&c))));
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with(&format!("bird #{} ", id))));
+        assert!(lines.iter().any(|line| line.starts_with("vision")));
+        assert!(lines.iter().any(|line| line.starts_with("brain")));
+    }
+
+    #[test]
+    fn chart() {
+        let rows = super::chart(&[0.0, 1.0, 2.0, 3.0], 2, 1);
+        assert_eq!(rows, vec![vec!['⡠', '⠊']]);
+    }
+}
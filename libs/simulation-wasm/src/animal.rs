@@ -2,22 +2,51 @@ use lib_simulation as sim;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
-#[derive(Clone, Debug, Copy)]
+#[derive(Clone, Debug)]
 pub struct Animal {
+    pub id: u64,
     pub x: f32,
     pub y: f32,
     pub rotation: f32,
+    pub speed: f32,
+
+    /// Number of foods eaten during the current generation.
+    pub satiation: u32,
+
+    /// What the bird has seen during the last step, one value per eye's
+    /// cell.
+    #[wasm_bindgen(getter_with_clone)]
+    pub vision: Vec<f32>,
+
+    /// Activations of all brain's layers during the last step (vision
+    /// first, brain's response last), flattened - see `layer_sizes`.
+    #[wasm_bindgen(getter_with_clone)]
+    pub activations: Vec<f32>,
+
+    /// Number of neurons in each brain's layer, so that `activations` can
+    /// be split back into layers.
+    #[wasm_bindgen(getter_with_clone)]
+    pub layer_sizes: Vec<u32>,
 }
 // ^ Эта модель меньше `lib_simulation::Animal`, поскольку
-// | позиция птицы - это все, что нам нужно на данный момент
-// | на стороне JS, другие поля нам пока не нужны.
+// | на стороне JS нам нужно лишь то, что мы рисуем.
 
 impl From<&sim::Animal> for Animal {
     fn from(animal: &sim::Animal) -> Self {
         Self {
+            id: animal.id(),
             x: animal.position().x,
             y: animal.position().y,
             rotation: animal.rotation().angle(),
+            speed: animal.speed(),
+            satiation: animal.satiation() as u32,
+            vision: animal.vision().to_vec(),
+            activations: animal.activations().concat(),
+            layer_sizes: animal
+                .activations()
+                .iter()
+                .map(|layer| layer.len() as u32)
+                .collect(),
         }
     }
-}
\ No newline at end of file
+}
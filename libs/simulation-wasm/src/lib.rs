@@ -1,9 +1,13 @@
 mod animal;
 mod food;
+mod options;
+mod progress;
 mod simulation;
 mod word;
 
 pub use animal::Animal;
 pub use food::Food;
+pub use options::{Crossover, Mutation, MutationSchedule, Selection, SimulationOptions};
+pub use progress::Progress;
 pub use simulation::Simulation;
 pub use word::World;
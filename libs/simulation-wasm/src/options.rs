@@ -0,0 +1,372 @@
+use lib_simulation as sim;
+use wasm_bindgen::prelude::*;
+
+/// See: `sim::SelectionConfig`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Selection {
+    RouletteWheel,
+    Tournament,
+}
+
+/// See: `sim::CrossoverConfig`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Crossover {
+    Uniform,
+    SinglePoint,
+    KPoint,
+    Blend,
+    SimulatedBinary,
+    Neuron,
+}
+
+/// See: `sim::MutationConfig`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mutation {
+    UniformPerturbation,
+    Normal,
+    Cauchy,
+}
+
+/// See: `sim::MutationSchedule`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MutationSchedule {
+    None,
+    Annealing,
+    OneFifthRule,
+}
+
+/// Flattened `sim::Config`, so that it's easy to build (and bind to
+/// sliders) from JS; `new SimulationOptions()` reproduces the original
+/// setup.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct SimulationOptions {
+    pub animals: u32,
+    pub foods: u32,
+
+    pub fov_range: f32,
+    pub fov_angle: f32,
+    pub eye_cells: u32,
+
+    /// Birds' sensors (see `sim::SensorConfig`); like the eye, they are
+    /// fixed for the whole simulation.
+    pub speed_sensor: bool,
+    pub compass_sensor: bool,
+    pub hunger_sensor: bool,
+    pub wall_sensor: bool,
+
+    /// Period of the clock sensor, in steps; zero disables the sensor.
+    pub clock_period: u32,
+
+    pub speed_min: f32,
+    pub speed_max: f32,
+
+    pub generation_length: u32,
+
+    pub mutation: Mutation,
+
+    /// Chance of mutating each gene, for all mutations.
+    pub mutation_chance: f32,
+
+    /// Strength of mutations - coefficient for `Mutation.UniformPerturbation`,
+    /// sigma for `Mutation.Normal` and scale for `Mutation.Cauchy`.
+    pub mutation_coeff: f32,
+
+    pub mutation_schedule: MutationSchedule,
+
+    /// Used by `MutationSchedule.Annealing`.
+    pub annealing_decay: f32,
+    pub annealing_min_chance: f32,
+    pub annealing_min_coeff: f32,
+
+    /// Used by `MutationSchedule.OneFifthRule`.
+    pub one_fifth_factor: f32,
+    pub one_fifth_window: u32,
+
+    pub selection: Selection,
+
+    /// Used by `Selection.Tournament`.
+    pub tournament_size: u32,
+
+    pub crossover: Crossover,
+
+    /// Used by `Crossover.KPoint`.
+    pub crossover_points: u32,
+
+    /// Used by `Crossover.Blend`.
+    pub blend_alpha: f32,
+
+    /// Used by `Crossover.SimulatedBinary`.
+    pub sbx_eta: f32,
+
+    /// Seed of the random number generator; when missing, a random one is
+    /// used.
+    pub seed: Option<u64>,
+}
+
+#[wasm_bindgen]
+impl SimulationOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::from(&sim::Config::default())
+    }
+}
+
+impl Default for SimulationOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&sim::Config> for SimulationOptions {
+    fn from(config: &sim::Config) -> Self {
+        let mut options = Self {
+            animals: config.animals as u32,
+            foods: config.foods as u32,
+            fov_range: config.eye.fov_range(),
+            fov_angle: config.eye.fov_angle(),
+            eye_cells: config.eye.cells() as u32,
+            speed_sensor: false,
+            compass_sensor: false,
+            hunger_sensor: false,
+            wall_sensor: false,
+            clock_period: 0,
+            speed_min: config.speed_min,
+            speed_max: config.speed_max,
+            generation_length: config.generation_length as u32,
+            mutation: Mutation::UniformPerturbation,
+            mutation_chance: 0.01,
+            mutation_coeff: 0.2,
+            mutation_schedule: MutationSchedule::None,
+            annealing_decay: 0.99,
+            annealing_min_chance: 0.001,
+            annealing_min_coeff: 0.01,
+            one_fifth_factor: 0.85,
+            one_fifth_window: 10,
+            selection: Selection::RouletteWheel,
+            tournament_size: 2,
+            crossover: Crossover::Uniform,
+            crossover_points: 2,
+            blend_alpha: 0.5,
+            sbx_eta: 2.0,
+            seed: None,
+        };
+
+        for sensor in &config.sensors {
+            match *sensor {
+                sim::SensorConfig::Speed => options.speed_sensor = true,
+                sim::SensorConfig::Compass => options.compass_sensor = true,
+                sim::SensorConfig::Hunger => options.hunger_sensor = true,
+                sim::SensorConfig::Wall => options.wall_sensor = true,
+                sim::SensorConfig::Clock { period } => options.clock_period = period as u32,
+            }
+        }
+
+        // JS can configure only the genetic algorithm (and only some of its
+        // mutations) - everything else keeps the defaults above
+        if let sim::OptimizerConfig::GeneticAlgorithm {
+            selection,
+            crossover,
+            ref mutation,
+        } = config.optimizer
+        {
+            options.set_mutation(mutation);
+
+            options.selection = match selection {
+                sim::SelectionConfig::RouletteWheel => Selection::RouletteWheel,
+
+                sim::SelectionConfig::Tournament { size } => {
+                    options.tournament_size = size as u32;
+                    Selection::Tournament
+                }
+            };
+
+            options.crossover = match crossover {
+                sim::CrossoverConfig::Uniform => Crossover::Uniform,
+                sim::CrossoverConfig::SinglePoint => Crossover::SinglePoint,
+
+                sim::CrossoverConfig::KPoint { k } => {
+                    options.crossover_points = k as u32;
+                    Crossover::KPoint
+                }
+
+                sim::CrossoverConfig::Blend { alpha } => {
+                    options.blend_alpha = alpha;
+                    Crossover::Blend
+                }
+
+                sim::CrossoverConfig::SimulatedBinary { eta } => {
+                    options.sbx_eta = eta;
+                    Crossover::SimulatedBinary
+                }
+
+                sim::CrossoverConfig::Neuron => Crossover::Neuron,
+            };
+        }
+
+        options
+    }
+}
+
+impl SimulationOptions {
+    fn set_mutation(&mut self, config: &sim::MutationConfig) {
+        let mutation = match *config {
+            sim::MutationConfig::UniformPerturbation { chance, coeff } => {
+                sim::TunableMutationConfig::UniformPerturbation { chance, coeff }
+            }
+            sim::MutationConfig::Normal { chance, sigma } => {
+                sim::TunableMutationConfig::Normal { chance, sigma }
+            }
+            sim::MutationConfig::Cauchy { chance, scale } => {
+                sim::TunableMutationConfig::Cauchy { chance, scale }
+            }
+
+            sim::MutationConfig::Scheduled { mutation, schedule } => {
+                match schedule {
+                    sim::MutationSchedule::Annealing {
+                        decay,
+                        min_chance,
+                        min_strength,
+                    } => {
+                        self.mutation_schedule = MutationSchedule::Annealing;
+                        self.annealing_decay = decay;
+                        self.annealing_min_chance = min_chance;
+                        self.annealing_min_coeff = min_strength;
+                    }
+
+                    sim::MutationSchedule::OneFifthRule { factor, window } => {
+                        self.mutation_schedule = MutationSchedule::OneFifthRule;
+                        self.one_fifth_factor = factor;
+                        self.one_fifth_window = window as u32;
+                    }
+
+                    sim::MutationSchedule::DiversityBoost { .. } => (),
+                }
+
+                mutation
+            }
+
+            sim::MutationConfig::SelfAdaptive { .. } | sim::MutationConfig::Adaptive { .. } => {
+                return;
+            }
+        };
+
+        (self.mutation, self.mutation_chance, self.mutation_coeff) = match mutation {
+            sim::TunableMutationConfig::UniformPerturbation { chance, coeff } => {
+                (Mutation::UniformPerturbation, chance, coeff)
+            }
+            sim::TunableMutationConfig::Normal { chance, sigma } => {
+                (Mutation::Normal, chance, sigma)
+            }
+            sim::TunableMutationConfig::Cauchy { chance, scale } => {
+                (Mutation::Cauchy, chance, scale)
+            }
+        };
+    }
+
+    fn mutation(&self) -> sim::MutationConfig {
+        let chance = self.mutation_chance;
+        let coeff = self.mutation_coeff;
+
+        let mutation = match self.mutation {
+            Mutation::UniformPerturbation => {
+                sim::TunableMutationConfig::UniformPerturbation { chance, coeff }
+            }
+            Mutation::Normal => sim::TunableMutationConfig::Normal {
+                chance,
+                sigma: coeff,
+            },
+            Mutation::Cauchy => sim::TunableMutationConfig::Cauchy {
+                chance,
+                scale: coeff,
+            },
+        };
+
+        let schedule = match self.mutation_schedule {
+            MutationSchedule::None => return mutation.into(),
+
+            MutationSchedule::Annealing => sim::MutationSchedule::Annealing {
+                decay: self.annealing_decay,
+                min_chance: self.annealing_min_chance,
+                min_strength: self.annealing_min_coeff,
+            },
+
+            MutationSchedule::OneFifthRule => sim::MutationSchedule::OneFifthRule {
+                factor: self.one_fifth_factor,
+                window: self.one_fifth_window as usize,
+            },
+        };
+
+        sim::MutationConfig::Scheduled { mutation, schedule }
+    }
+}
+
+/// Fails if the eye is invalid (e.g. has no cells); everything else gets
+/// validated along with the config (see `sim::Config::validate()`).
+impl TryFrom<&SimulationOptions> for sim::Config {
+    type Error = sim::Error;
+
+    fn try_from(options: &SimulationOptions) -> Result<Self, Self::Error> {
+        let selection = match options.selection {
+            Selection::RouletteWheel => sim::SelectionConfig::RouletteWheel,
+            Selection::Tournament => sim::SelectionConfig::Tournament {
+                size: options.tournament_size as usize,
+            },
+        };
+
+        let crossover = match options.crossover {
+            Crossover::Uniform => sim::CrossoverConfig::Uniform,
+            Crossover::SinglePoint => sim::CrossoverConfig::SinglePoint,
+            Crossover::KPoint => sim::CrossoverConfig::KPoint {
+                k: options.crossover_points as usize,
+            },
+            Crossover::Blend => sim::CrossoverConfig::Blend {
+                alpha: options.blend_alpha,
+            },
+            Crossover::SimulatedBinary => sim::CrossoverConfig::SimulatedBinary {
+                eta: options.sbx_eta,
+            },
+            Crossover::Neuron => sim::CrossoverConfig::Neuron,
+        };
+
+        let sensors = [
+            (options.speed_sensor, sim::SensorConfig::Speed),
+            (options.compass_sensor, sim::SensorConfig::Compass),
+            (options.hunger_sensor, sim::SensorConfig::Hunger),
+            (options.wall_sensor, sim::SensorConfig::Wall),
+            (
+                options.clock_period > 0,
+                sim::SensorConfig::Clock {
+                    period: options.clock_period as usize,
+                },
+            ),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, sensor)| sensor)
+        .collect();
+
+        Ok(Self {
+            animals: options.animals as usize,
+            foods: options.foods as usize,
+            eye: sim::Eye::try_new(
+                options.fov_range,
+                options.fov_angle,
+                options.eye_cells as usize,
+            )?,
+            sensors,
+            speed_min: options.speed_min,
+            speed_max: options.speed_max,
+            generation_length: options.generation_length as usize,
+            optimizer: sim::OptimizerConfig::GeneticAlgorithm {
+                selection,
+                crossover,
+                mutation: options.mutation(),
+            },
+        })
+    }
+}
@@ -0,0 +1,50 @@
+use lib_simulation as sim;
+use wasm_bindgen::prelude::*;
+
+/// Where the simulation is after a batch of work - handy for progress bars
+/// (e.g. when training inside a Web Worker).
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Progress {
+    pub generation: u32,
+
+    /// Number of steps simulated within the current generation.
+    pub age: u32,
+
+    /// Number of steps each generation lives for.
+    pub generation_length: u32,
+
+    /// Number of steps simulated by the call that returned this progress.
+    pub steps: u32,
+
+    /// Number of generations completed by the call that returned this
+    /// progress.
+    pub generations: u32,
+
+    /// Statistics of the last generation completed by the call that
+    /// returned this progress, if any.
+    #[wasm_bindgen(getter_with_clone)]
+    pub summary: Option<String>,
+}
+
+#[wasm_bindgen]
+impl Progress {
+    /// How much of the current generation has been simulated, from 0.0 to
+    /// 1.0.
+    pub fn fraction(&self) -> f32 {
+        self.age as f32 / self.generation_length as f32
+    }
+}
+
+impl Progress {
+    pub(crate) fn new(sim: &sim::Simulation) -> Self {
+        Self {
+            generation: sim.generation() as u32,
+            age: sim.age() as u32,
+            generation_length: sim.generation_length() as u32,
+            steps: 0,
+            generations: 0,
+            summary: None,
+        }
+    }
+}
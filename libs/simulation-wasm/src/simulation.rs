@@ -1,38 +1,281 @@
-use crate::World;
+use crate::{
+    word, Crossover, Mutation, MutationSchedule, Progress, Selection, SimulationOptions, World,
+};
+use lib_genetic_algorithm as ga;
 use lib_simulation as sim;
 use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use std::time::Duration;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 pub struct Simulation {
-    rng: ThreadRng,
+    // Seeded from `thread_rng()` (unless a seed is given), but - contrary
+    // to it - serializable, so that `to_bytes()` can carry it along with
+    // the simulation
+    rng: ChaCha8Rng,
     sim: sim::Simulation,
 }
 
 #[wasm_bindgen]
 impl Simulation {
+    /// Throws if the options are invalid (e.g. `mutation_chance` is
+    /// outside of `0.0..=1.0`).
     #[wasm_bindgen(constructor)]
-    pub fn new() -> Self {
-        let mut rng = thread_rng();
-        let sim = sim::Simulation::random(&mut rng);
+    pub fn new(options: Option<SimulationOptions>) -> Result<Simulation, JsError> {
+        let options = options.unwrap_or_default();
 
-        Self { rng, sim }
+        let mut rng = match options.seed {
+            Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+            None => ChaCha8Rng::from_rng(thread_rng()).expect("thread_rng() never fails"),
+        };
+
+        let config = sim::Config::try_from(&options)?;
+        let sim = sim::Simulation::try_random_with_config(&mut rng, config)?;
+
+        Ok(Self { rng, sim })
+    }
+
+    /// Restores simulation serialized with `to_bytes()` - e.g. one that's
+    /// been trained inside a Web Worker.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Simulation, JsError> {
+        let (sim, rng) = sim::Simulation::load_checkpoint(bytes)?;
+
+        Ok(Self { rng, sim })
+    }
+
+    /// Serializes the whole simulation into an `Uint8Array`, which can be
+    /// transferred between a worker and the main thread.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.sim.save_checkpoint(&self.rng)
     }
 
     pub fn world(&self) -> World {
-        World::from(self.sim.world())
+        World::from(&self.sim)
     }
 
-    pub fn step(&mut self) {
-        self.sim.step(&mut self.rng);
+    /// Positions of all the animals and foods, packed into a single
+    /// `Float32Array` - cheaper to call every frame than `world()`.
+    ///
+    /// Layout: `[animals, foods, x, y, rotation, ..., x, y, ...]`, i.e.
+    /// both counts first, then three values per animal and two per food.
+    pub fn world_flat(&self) -> Vec<f32> {
+        word::flatten(self.sim.world())
     }
 
-    pub fn train(&mut self) -> String {
-        let stats = self.sim.train(&mut self.rng);
+    /// Options the next generation will live by (without the seed).
+    pub fn options(&self) -> SimulationOptions {
+        SimulationOptions::from(self.sim.next_config())
+    }
+
+    /// Applies given options once the current generation ends; the number
+    /// of birds, their eyes and sensors can't be changed mid-run, though, so
+    /// those options (and the seed) are ignored.
+    ///
+    /// Throws if the options are invalid, leaving the current ones intact.
+    pub fn set_options(&mut self, options: &SimulationOptions) -> Result<(), JsError> {
+        self.sim.try_reconfigure(sim::Config::try_from(options)?)?;
+
+        Ok(())
+    }
+
+    pub fn set_foods(&mut self, foods: u32) -> Result<(), JsError> {
+        self.update_options(|options| options.foods = foods)
+    }
+
+    pub fn set_speed_min(&mut self, speed_min: f32) -> Result<(), JsError> {
+        self.update_options(|options| options.speed_min = speed_min)
+    }
+
+    pub fn set_speed_max(&mut self, speed_max: f32) -> Result<(), JsError> {
+        self.update_options(|options| options.speed_max = speed_max)
+    }
+
+    pub fn set_generation_length(&mut self, generation_length: u32) -> Result<(), JsError> {
+        self.update_options(|options| options.generation_length = generation_length)
+    }
 
-        format!(
-            "min={:.2}, max={:.2}, avg={:.2}",
-            stats.min_fitness, stats.max_fitness, stats.avg_fitness,
-        )
+    pub fn set_mutation(&mut self, mutation: Mutation) -> Result<(), JsError> {
+        self.update_options(|options| options.mutation = mutation)
     }
+
+    pub fn set_mutation_schedule(
+        &mut self,
+        mutation_schedule: MutationSchedule,
+    ) -> Result<(), JsError> {
+        self.update_options(|options| options.mutation_schedule = mutation_schedule)
+    }
+
+    pub fn set_mutation_chance(&mut self, mutation_chance: f32) -> Result<(), JsError> {
+        self.update_options(|options| options.mutation_chance = mutation_chance)
+    }
+
+    pub fn set_mutation_coeff(&mut self, mutation_coeff: f32) -> Result<(), JsError> {
+        self.update_options(|options| options.mutation_coeff = mutation_coeff)
+    }
+
+    pub fn set_selection(&mut self, selection: Selection) -> Result<(), JsError> {
+        self.update_options(|options| options.selection = selection)
+    }
+
+    pub fn set_crossover(&mut self, crossover: Crossover) -> Result<(), JsError> {
+        self.update_options(|options| options.crossover = crossover)
+    }
+
+    pub fn progress(&self) -> Progress {
+        Progress::new(&self.sim)
+    }
+
+    pub fn step(&mut self) -> Result<(), JsError> {
+        self.sim.try_step(&mut self.rng)?;
+
+        Ok(())
+    }
+
+    /// Simulates given number of steps, evolving the birds whenever their
+    /// generation ends.
+    pub fn step_many(&mut self, steps: u32) -> Result<Progress, JsError> {
+        let mut progress = Progress::new(&self.sim);
+
+        for _ in 0..steps {
+            self.step_once(&mut progress)?;
+        }
+
+        Ok(progress)
+    }
+
+    /// Keeps simulating for (roughly) given number of milliseconds; always
+    /// simulates at least one step.
+    ///
+    /// Calling it with a small budget (say, 10 ms) over and over again
+    /// lets the training progress without freezing the page.
+    pub fn train_for(&mut self, ms: f64) -> Result<Progress, JsError> {
+        let mut progress = Progress::new(&self.sim);
+        let started_at = js_sys::Date::now();
+
+        loop {
+            self.step_once(&mut progress)?;
+
+            if js_sys::Date::now() - started_at >= ms {
+                break;
+            }
+        }
+
+        Ok(progress)
+    }
+
+    pub fn train(&mut self) -> Result<String, JsError> {
+        Ok(summarize(&self.sim.try_train(&mut self.rng)?))
+    }
+
+    /// Keeps training until one of given limits is reached:
+    ///
+    /// - `generations` - number of generations to complete,
+    /// - `target_fitness` - fitness the best bird has to reach,
+    /// - `ms` - time budget (see `ga::WallClock`).
+    ///
+    /// Calls `on_generation(summary)` whenever a generation ends.
+    ///
+    /// Throws if no limit is given, since nothing could stop the training
+    /// then.
+    pub fn train_until(
+        &mut self,
+        generations: Option<u32>,
+        target_fitness: Option<f32>,
+        ms: Option<f64>,
+        on_generation: Option<js_sys::Function>,
+    ) -> Result<Progress, JsError> {
+        let mut monitor = ga::Monitor::new();
+
+        if let Some(generations) = generations {
+            if generations == 0 {
+                return Err(JsError::new("generations must be within 1.., but got 0"));
+            }
+
+            monitor = monitor.with_criterion(ga::MaxGenerations::new(generations as usize));
+        }
+
+        if let Some(fitness) = target_fitness {
+            monitor = monitor.with_criterion(ga::TargetFitness::new(fitness));
+        }
+
+        if let Some(ms) = ms {
+            monitor = monitor.with_criterion(ga::WallClock::with_clock(
+                Duration::from_secs_f64(ms.max(0.0) / 1000.0),
+                now,
+            ));
+        }
+
+        if generations.is_none() && target_fitness.is_none() && ms.is_none() {
+            return Err(JsError::new(
+                "at least one of generations, target_fitness and ms is required",
+            ));
+        }
+
+        if let Some(on_generation) = on_generation {
+            monitor = monitor.with_observer(move |_, stats: &ga::Statistics| {
+                // Observers can't fail, so JS exceptions are up to JS
+                let _ = on_generation.call1(&JsValue::NULL, &summarize(stats).into());
+            });
+        }
+
+        let mut progress = Progress::new(&self.sim);
+
+        monitor.start();
+
+        loop {
+            if let Some(stats) = self.step_once(&mut progress)? {
+                let generation = progress.generations as usize;
+
+                if monitor.record(generation, &stats).is_some() {
+                    return Ok(progress);
+                }
+            }
+        }
+    }
+}
+
+impl Simulation {
+    /// Changes some of the options - see `set_options()`.
+    fn update_options(&mut self, f: impl FnOnce(&mut SimulationOptions)) -> Result<(), JsError> {
+        let mut options = self.options();
+        f(&mut options);
+        self.set_options(&options)
+    }
+
+    /// Returns statistics of the generation that has just ended, if any.
+    fn step_once(&mut self, progress: &mut Progress) -> Result<Option<ga::Statistics>, sim::Error> {
+        let stats = self.sim.try_step(&mut self.rng)?;
+
+        progress.generation = self.sim.generation() as u32;
+        progress.age = self.sim.age() as u32;
+        progress.steps += 1;
+
+        if let Some(stats) = &stats {
+            progress.generations += 1;
+            progress.summary = Some(summarize(stats));
+        }
+
+        Ok(stats)
+    }
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Self::new(None).unwrap_or_else(|_| panic!("default options are always valid"))
+    }
+}
+
+/// Time elapsed since the epoch, as seen by JavaScript - the clock
+/// `ga::WallClock` uses here, since `std::time::Instant` isn't available
+/// on the web.
+fn now() -> Duration {
+    Duration::from_secs_f64(js_sys::Date::now() / 1000.0)
+}
+
+fn summarize(stats: &ga::Statistics) -> String {
+    format!(
+        "min={:.2}, max={:.2}, avg={:.2}",
+        stats.min_fitness, stats.max_fitness, stats.avg_fitness,
+    )
 }
@@ -1,20 +1,64 @@
-use crate::{Animal,Food};
+use crate::{Animal, Food};
 use lib_simulation as sim;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 #[derive(Clone, Debug)]
 pub struct World {
+    pub generation: u32,
+
+    /// Number of steps simulated within the current generation.
+    pub age: u32,
+
     #[wasm_bindgen(getter_with_clone)]
     pub animals: Vec<Animal>,
     #[wasm_bindgen(getter_with_clone)]
     pub foods: Vec<Food>,
 }
 
-impl From<&sim::World> for World {
-    fn from(world: &sim::World) -> Self {
+impl From<&sim::Simulation> for World {
+    fn from(sim: &sim::Simulation) -> Self {
+        let world = sim.world();
         let animals = world.animals().iter().map(Animal::from).collect();
         let foods = world.foods().iter().map(Food::from).collect();
-        Self { animals, foods }
+
+        Self {
+            generation: sim.generation() as u32,
+            age: sim.age() as u32,
+            animals,
+            foods,
+        }
+    }
+}
+
+/// Number of values per animal in `flatten()`'s output.
+const ANIMAL_STRIDE: usize = 3;
+
+/// Number of values per food in `flatten()`'s output.
+const FOOD_STRIDE: usize = 2;
+
+/// See: `Simulation::world_flat()`.
+pub(crate) fn flatten(world: &sim::World) -> Vec<f32> {
+    let animals = world.animals();
+    let foods = world.foods();
+
+    let mut flat =
+        Vec::with_capacity(2 + ANIMAL_STRIDE * animals.len() + FOOD_STRIDE * foods.len());
+
+    flat.push(animals.len() as f32);
+    flat.push(foods.len() as f32);
+
+    for animal in animals {
+        flat.extend([
+            animal.position().x,
+            animal.position().y,
+            animal.rotation().angle(),
+        ]);
     }
+
+    for food in foods {
+        flat.extend([food.position().x, food.position().y]);
+    }
+
+    flat
 }
@@ -1,22 +1,54 @@
-use crate::{Brain, Eye, Point, Rotation};
+use crate::{Brain, Config, Eye, Food, Point, Rotation, Sensor, SensorConfig};
 use lib_genetic_algorithm as ga;
 use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Animal {
+    /// Unique within bird's world; assigned by `World` once the bird
+    /// gets released into it.
+    pub(crate) id: u64,
     pub(crate) position: Point,
     pub(crate) rotation: Rotation,
     pub(crate) speed: f32,
     pub(crate) eye: Eye,
+
+    /// What the bird feels besides what it sees (see `Config::sensors`).
+    pub(crate) sensors: Vec<SensorConfig>,
+
     pub(crate) brain: Brain,
     pub(crate) satiation: usize,
+
+    /// Number of steps the bird has lived through.
+    pub(crate) age: usize,
+
+    /// Number of steps since bird's last meal (or birth).
+    pub(crate) hunger: usize,
+
+    /// Activations of brain's layers during the last step, starting with
+    /// what the eye has seen & sensors have felt (see
+    /// `nn::Network::propagate_traced()`); empty until the bird has taken
+    /// its first step.
+    pub(crate) activations: Vec<Vec<f32>>,
+
+    /// Mutation step size inherited from bird's chromosome, so that it
+    /// survives the round trip through `Brain` (see `ga::SelfAdaptiveMutation`).
+    pub(crate) sigma: Option<f32>,
 }
 impl Animal {
     pub fn random(rng: &mut dyn RngCore) -> Self {
-        let eye = Eye::default();
-        let brain = Brain::random(rng, &eye);
+        Self::random_with_senses(rng, Eye::default(), Vec::new())
+    }
+
+    pub(crate) fn random_with_senses(
+        rng: &mut dyn RngCore,
+        eye: Eye,
+        sensors: Vec<SensorConfig>,
+    ) -> Self {
+        let brain = Brain::random(rng, &eye, &sensors);
 
         Self {
+            id: 0,
             position: rng.gen(),
             // ------ ^-------^
             // | Если бы не `rand-no-std`, нам пришлось бы делать
@@ -25,16 +57,29 @@ impl Animal {
             rotation: rng.gen(),
             speed: 0.002,
             eye,
+            sensors,
             brain,
             satiation: 0,
+            age: 0,
+            hunger: 0,
+            activations: Vec::new(),
+            sigma: None,
         }
     }
 
-    pub(crate) fn from_chromosome(chromosome: ga::Chromosome, rng: &mut dyn RngCore) -> Self {
-        let eye = Eye::default();
-        let brain = Brain::from_chromosome(chromosome, &eye);
+    pub(crate) fn from_chromosome(
+        chromosome: ga::Chromosome,
+        eye: Eye,
+        sensors: Vec<SensorConfig>,
+        rng: &mut dyn RngCore,
+    ) -> Self {
+        let sigma = chromosome.sigma();
+        let brain = Brain::from_chromosome(chromosome, &eye, &sensors);
 
-        Self::new(eye, brain, rng)
+        Self {
+            sigma,
+            ..Self::new(eye, sensors, brain, rng)
+        }
     }
 
     pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
@@ -45,20 +90,32 @@ impl Animal {
         // return a longer chromosome that encodes not only the brain,
         // but also, say, birdie's color.
 
-        self.brain.as_chromosome()
+        let mut chromosome = self.brain.as_chromosome();
+        chromosome.set_sigma(self.sigma);
+        chromosome
     }
 
-    fn new(eye: Eye, brain: Brain, rng: &mut dyn RngCore) -> Self {
+    fn new(eye: Eye, sensors: Vec<SensorConfig>, brain: Brain, rng: &mut dyn RngCore) -> Self {
         Self {
+            id: 0,
             position: rng.gen(),
             rotation: rng.gen(),
             speed: 0.002,
             eye,
+            sensors,
             brain,
             satiation: 0,
+            age: 0,
+            hunger: 0,
+            activations: Vec::new(),
+            sigma: None,
         }
     }
 
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
     pub fn position(&self) -> Point {
         // ------------------ ^
         // | Нет необходимости возвращать ссылку, поскольку `na::Point2` является копируемым (реализует типаж `Copy`).
@@ -72,4 +129,68 @@ impl Animal {
     pub fn rotation(&self) -> Rotation {
         self.rotation
     }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Number of foods eaten during the current generation.
+    pub fn satiation(&self) -> usize {
+        self.satiation
+    }
+
+    pub fn eye(&self) -> &Eye {
+        &self.eye
+    }
+
+    pub fn sensors(&self) -> &[SensorConfig] {
+        &self.sensors
+    }
+
+    pub fn brain(&self) -> &Brain {
+        &self.brain
+    }
+
+    /// Number of steps the bird has lived through.
+    pub fn age(&self) -> usize {
+        self.age
+    }
+
+    /// Number of steps since bird's last meal.
+    pub fn hunger(&self) -> usize {
+        self.hunger
+    }
+
+    /// Computes what the bird would feed into its brain right now, given
+    /// the foods around it: what the eye sees, followed by sensors'
+    /// readings.
+    pub fn perceive(&self, foods: &[Food], config: &Config) -> Vec<f32> {
+        let mut inputs = self.eye.process_vision(self.position, self.rotation, foods);
+
+        for sensor in &self.sensors {
+            sensor.sense(self, config, &mut inputs);
+        }
+
+        inputs
+    }
+
+    /// What the eye has seen during the last step.
+    pub fn vision(&self) -> &[f32] {
+        let inputs = self.inputs();
+
+        &inputs[..inputs.len().min(self.eye.cells())]
+    }
+
+    /// Brain's inputs during the last step - the vision, followed by
+    /// sensors' readings.
+    pub fn inputs(&self) -> &[f32] {
+        self.activations.first().map_or(&[], Vec::as_slice)
+    }
+
+    /// Activations of brain's layers during the last step - the inputs
+    /// first, then the hidden layer, then brain's response (change of
+    /// speed and rotation).
+    pub fn activations(&self) -> &[Vec<f32>] {
+        &self.activations
+    }
 }
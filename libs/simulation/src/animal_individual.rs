@@ -1,4 +1,4 @@
-use crate::Animal;
+use crate::{Animal, Eye, SensorConfig};
 use lib_genetic_algorithm as ga;
 use rand::RngCore;
 
@@ -15,7 +15,16 @@ impl AnimalIndividual {
     }
 
     pub fn into_animal(self, rng: &mut dyn RngCore) -> Animal {
-        Animal::from_chromosome(self.chromosome, rng)
+        self.into_animal_with_senses(&Eye::default(), &[], rng)
+    }
+
+    pub(crate) fn into_animal_with_senses(
+        self,
+        eye: &Eye,
+        sensors: &[SensorConfig],
+        rng: &mut dyn RngCore,
+    ) -> Animal {
+        Animal::from_chromosome(self.chromosome, eye.clone(), sensors.to_vec(), rng)
     }
 }
 impl ga::Individual for AnimalIndividual {
@@ -2,38 +2,115 @@ use crate::*;
 use lib_genetic_algorithm as ga;
 use lib_neural_network as nn;
 use rand::RngCore;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Brain {
     pub(crate) nn: nn::Network,
 }
 
 impl Brain {
-    pub fn random(rng: &mut dyn RngCore, eye: &Eye) -> Self {
+    pub fn random(rng: &mut dyn RngCore, eye: &Eye, sensors: &[SensorConfig]) -> Self {
         Self {
-            nn: nn::Network::random(rng, &Self::topology(eye)),
+            nn: nn::Network::random(rng, &Self::topology(eye, sensors)),
         }
     }
 
+    /// Turns what the eye sees (followed by sensors' readings) into brain's
+    /// outputs: change of speed, turn to the left and turn to the right
+    /// (see `response()`).
+    pub fn propagate(&self, vision: Vec<f32>) -> Vec<f32> {
+        self.nn.propagate(vision)
+    }
+
+    /// Same as `propagate()`, but returns an error instead of panicking
+    /// when the vision doesn't fit the brain.
+    pub fn try_propagate(&self, vision: Vec<f32>) -> Result<Vec<f32>, Error> {
+        Ok(self.nn.try_propagate(vision)?)
+    }
+
+    /// Turns brain's outputs into its response: change of speed and change
+    /// of rotation (both unclamped).
+    ///
+    /// Negative outputs count as zeros, so that a random brain (whose
+    /// outputs are just as likely to be negative as positive) does nothing
+    /// about half the time - otherwise random birds would only ever fly in
+    /// circles. That's also why turning left and turning right get an
+    /// output each, and why brains can't slow down.
+    pub fn response(outputs: &[f32]) -> [f32; 2] {
+        let [speed, left, right] =
+            [outputs[0], outputs[1], outputs[2]].map(|output| output.max(0.0));
+
+        [speed, left - right]
+    }
+
+    /// Inverse of `response()`, i.e. the outputs a brain needs to respond
+    /// this way - e.g. targets for `pretrain()`.
+    pub fn outputs([speed, rotation]: [f32; 2]) -> Vec<f32> {
+        vec![speed.max(0.0), rotation.max(0.0), (-rotation).max(0.0)]
+    }
+
+    /// Read-only access to the underlying network - e.g. to draw it.
+    pub fn network(&self) -> &nn::Network {
+        &self.nn
+    }
+
+    /// Teaches this brain to imitate given samples (e.g. collected by
+    /// `demonstrate()`) using backpropagation; returns the mean loss of
+    /// the last epoch.
+    pub fn pretrain(
+        &mut self,
+        rng: &mut dyn RngCore,
+        samples: &[nn::Sample],
+        epochs: usize,
+    ) -> f32 {
+        let mut optimizer = nn::Adam::new(0.001);
+        let mut loss = 0.0;
+
+        for _ in 0..epochs {
+            loss =
+                self.nn
+                    .train_epoch(rng, samples, nn::Loss::MeanSquaredError, &mut optimizer, 32);
+        }
+
+        loss
+    }
+
     pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
         self.nn.weights().collect()
     }
 
-    pub(crate) fn from_chromosome(chromosome: ga::Chromosome, eye: &Eye) -> Self {
+    pub(crate) fn from_chromosome(
+        chromosome: ga::Chromosome,
+        eye: &Eye,
+        sensors: &[SensorConfig],
+    ) -> Self {
         Self {
-            nn: nn::Network::from_weights(&Self::topology(eye), chromosome),
+            nn: nn::Network::from_weights(&Self::topology(eye, sensors), chromosome),
         }
     }
 
-    fn topology(eye: &Eye) -> [nn::LayerTopology; 3] {
+    /// Sizes of brain's neurons inside its chromosome - that's what
+    /// `ga::NeuronCrossover` needs to swap whole neurons.
+    pub fn neuron_sizes(eye: &Eye, sensors: &[SensorConfig]) -> Vec<usize> {
+        nn::Network::neuron_sizes(&Self::topology(eye, sensors))
+    }
+
+    /// Number of brain's inputs: eye's cells, followed by sensors'
+    /// readings.
+    pub fn inputs(eye: &Eye, sensors: &[SensorConfig]) -> usize {
+        eye.cells() + sensors.iter().map(Sensor::size).sum::<usize>()
+    }
+
+    fn topology(eye: &Eye, sensors: &[SensorConfig]) -> [nn::LayerTopology; 3] {
+        let inputs = Self::inputs(eye, sensors);
+
         [
+            nn::LayerTopology { neurons: inputs },
             nn::LayerTopology {
-                neurons: eye.cells(),
-            },
-            nn::LayerTopology {
-                neurons: 2 * eye.cells(),
+                neurons: 2 * inputs,
             },
-            nn::LayerTopology { neurons: 2 },
+            nn::LayerTopology { neurons: 3 },
         ]
     }
 }
@@ -0,0 +1,277 @@
+use crate::checkpoint::{self, CheckpointError};
+use crate::{Animal, Brain, Config, Eye, SensorConfig};
+use lib_genetic_algorithm as ga;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+/// Marks the beginning of every champion file.
+const MAGIC: &[u8; 4] = b"SLCH";
+
+/// Version of the champion file format (see `checkpoint::VERSION`).
+const VERSION: u32 = 1;
+
+/// One of the best birds ever seen by a `Simulation` (see
+/// `Simulation::champion()`), detached from it - so that it can be saved
+/// into a standalone file and released into another world.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Champion {
+    /// Bird's brain, encoded as in `Brain::as_chromosome()`.
+    chromosome: ga::Chromosome,
+    fitness: f32,
+    generation: usize,
+
+    /// Bird's senses, which determine the shape of its brain.
+    eye: Eye,
+    sensors: Vec<SensorConfig>,
+}
+
+impl Champion {
+    /// Detaches entry from the hall of fame of a simulation configured
+    /// with `config`.
+    pub(crate) fn from_entry(entry: &ga::HallOfFameEntry, config: &Config) -> Self {
+        Self {
+            chromosome: entry.chromosome.clone(),
+            fitness: entry.fitness,
+            generation: entry.generation,
+            eye: config.eye.clone(),
+            sensors: config.sensors.clone(),
+        }
+    }
+
+    pub fn fitness(&self) -> f32 {
+        self.fitness
+    }
+
+    /// Generation the bird has lived in.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn eye(&self) -> &Eye {
+        &self.eye
+    }
+
+    pub fn sensors(&self) -> &[SensorConfig] {
+        &self.sensors
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        checkpoint::encode(MAGIC, VERSION, self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CheckpointError> {
+        let champion: Self = checkpoint::decode(MAGIC, VERSION, bytes)?;
+
+        let expected = Brain::neuron_sizes(&champion.eye, &champion.sensors)
+            .iter()
+            .sum();
+
+        if champion.chromosome.len() != expected {
+            return Err(CheckpointError::IncompatibleBrain {
+                expected,
+                found: champion.chromosome.len(),
+            });
+        }
+
+        Ok(champion)
+    }
+
+    /// Creates a bird with champion's brain and senses described by
+    /// `config`, placed at random.
+    pub(crate) fn try_to_animal_with(
+        &self,
+        config: &Config,
+        rng: &mut dyn RngCore,
+    ) -> Result<Animal, CheckpointError> {
+        if config.sensors != self.sensors {
+            return Err(CheckpointError::IncompatibleSensors {
+                expected: config.sensors.clone(),
+                found: self.sensors.clone(),
+            });
+        }
+
+        let expected = config.neuron_sizes().iter().sum();
+
+        if self.chromosome.len() != expected {
+            return Err(CheckpointError::IncompatibleBrain {
+                expected,
+                found: self.chromosome.len(),
+            });
+        }
+
+        Ok(Animal::from_chromosome(
+            self.chromosome.clone(),
+            config.eye.clone(),
+            config.sensors.clone(),
+            rng,
+        ))
+    }
+
+    /// Creates a bird with champion's brain & senses, placed at random.
+    pub(crate) fn to_animal(&self, rng: &mut dyn RngCore) -> Animal {
+        Animal::from_chromosome(
+            self.chromosome.clone(),
+            self.eye.clone(),
+            self.sensors.clone(),
+            rng,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Simulation, World};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn export_and_import() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(&mut rng);
+
+        assert!(sim.champion().is_none());
+
+        let stats = sim.train(&mut rng);
+        let champion = sim.champion().unwrap();
+
+        assert_eq!(sim.hall_of_fame().len(), 10);
+        assert_eq!(champion.fitness(), stats.max_fitness);
+        assert_eq!(champion.generation(), 0);
+
+        let champion = Champion::from_bytes(&champion.to_bytes()).unwrap();
+        let brain = |animal: &Animal| animal.as_chromosome().genes;
+
+        // As a demo bird...
+        let world = World::demo(&mut rng, &champion);
+
+        assert_eq!(world.animals().len(), 1);
+        assert_eq!(brain(&world.animals()[0]), champion.chromosome.genes);
+
+        // ... or as the ancestor of a whole new population
+        let world = World::seeded(&mut rng, &champion, &Config::default());
+
+        assert_eq!(world.animals().len(), 10);
+        assert!(world
+            .animals()
+            .iter()
+            .all(|animal| brain(animal) == champion.chromosome.genes));
+
+        let mut sim = Simulation::with_world(world, Default::default());
+        sim.step(&mut rng);
+    }
+
+    #[test]
+    fn rejects_incompatible_brains() {
+        let champion = Champion {
+            chromosome: vec![1.0, 2.0, 3.0].into_iter().collect(),
+            fitness: 1.0,
+            generation: 0,
+            eye: Eye::default(),
+            sensors: Vec::new(),
+        };
+
+        let err = Champion::from_bytes(&champion.to_bytes()).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "brain has 3 weights, but birds of this simulation need 237"
+        );
+    }
+
+    #[test]
+    fn seeds_configured_worlds() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            animals: 4,
+            foods: 50,
+            eye: Eye::new(0.5, 1.0, 9),
+            generation_length: 100,
+            ..Config::default()
+        };
+
+        let mut sim = Simulation::random_with_config(&mut rng, config.clone());
+        sim.train(&mut rng);
+
+        let champion = sim.champion().unwrap();
+        let world = World::seeded(&mut rng, &champion, &config);
+
+        assert_eq!(world.animals().len(), 4);
+        assert_eq!(world.foods().len(), 50);
+        assert!(world
+            .animals()
+            .iter()
+            .all(|animal| animal.eye() == &config.eye));
+
+        let mut sim = Simulation::with_world(world, config);
+
+        for _ in 0..101 {
+            sim.step(&mut rng);
+        }
+
+        assert_eq!(sim.generation(), 1);
+        assert_eq!(sim.world().animals().len(), 4);
+
+        // Champion's brain has to fit the new birds, though
+        let err = World::try_seeded(
+            &mut rng,
+            &champion,
+            &Config {
+                sensors: vec![SensorConfig::Wall],
+                ..Config::default()
+            },
+        )
+        .map(drop)
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "champion has sensors [], but birds of this simulation have [Wall]"
+        );
+
+        let err = World::try_seeded(
+            &mut rng,
+            &champion,
+            &Config {
+                eye: Eye::new(0.5, 1.0, 5),
+                ..Config::default()
+            },
+        )
+        .map(drop)
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "brain has 237 weights, but birds of this simulation need 93"
+        );
+    }
+
+    #[test]
+    fn keeps_senses() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            eye: Eye::new(0.5, 1.0, 5),
+            sensors: vec![SensorConfig::Speed, SensorConfig::Clock { period: 20 }],
+            generation_length: 100,
+            ..Config::default()
+        };
+
+        let mut sim = Simulation::random_with_config(&mut rng, config.clone());
+        sim.train(&mut rng);
+
+        let champion = Champion::from_bytes(&sim.champion().unwrap().to_bytes()).unwrap();
+
+        assert_eq!(champion.eye(), &config.eye);
+        assert_eq!(champion.sensors(), config.sensors.as_slice());
+
+        let world = World::demo(&mut rng, &champion);
+        let animal = &world.animals()[0];
+
+        assert_eq!(animal.eye(), &config.eye);
+        assert_eq!(animal.sensors(), config.sensors.as_slice());
+        assert_eq!(animal.brain().network().layer_sizes(), [7, 14, 3]);
+        assert_eq!(animal.as_chromosome().genes, champion.chromosome.genes);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::{SensorConfig, Simulation};
+use rand_chacha::ChaCha8Rng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Marks the beginning of every checkpoint.
+const MAGIC: &[u8; 4] = b"SLCK";
+
+/// Version of the checkpoint format; bump it (and teach `load()` how to
+/// read - or at least how to reject - the older versions) whenever the
+/// serialized state changes.
+const VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct CheckpointRef<'a> {
+    simulation: &'a Simulation,
+    rng: &'a ChaCha8Rng,
+}
+
+#[derive(Deserialize)]
+struct Checkpoint {
+    simulation: Simulation,
+    rng: ChaCha8Rng,
+}
+
+/// Error returned when loading checkpoints & champions.
+#[derive(Debug)]
+pub enum CheckpointError {
+    /// Data doesn't start with the expected header.
+    NotACheckpoint,
+
+    /// Checkpoint has been written by an incompatible version of the
+    /// simulation.
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    /// Header is fine, but the state itself couldn't be decoded.
+    Corrupted(bincode::Error),
+
+    /// Champion's brain doesn't fit birds of this simulation.
+    IncompatibleBrain { expected: usize, found: usize },
+
+    /// Champion's brain has been evolved for different sensors than birds
+    /// of this simulation have.
+    IncompatibleSensors {
+        expected: Vec<SensorConfig>,
+        found: Vec<SensorConfig>,
+    },
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotACheckpoint => write!(f, "not a checkpoint"),
+
+            Self::UnsupportedVersion { found, supported } => write!(
+                f,
+                "checkpoint has version {}, but only version {} is supported",
+                found, supported
+            ),
+
+            Self::Corrupted(err) => write!(f, "checkpoint is corrupted: {}", err),
+
+            Self::IncompatibleBrain { expected, found } => write!(
+                f,
+                "brain has {} weights, but birds of this simulation need {}",
+                found, expected
+            ),
+
+            Self::IncompatibleSensors { expected, found } => write!(
+                f,
+                "champion has sensors {:?}, but birds of this simulation have {:?}",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Corrupted(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Serializes `value`, preceded by given magic number & version.
+pub(crate) fn encode<T>(magic: &[u8; 4], version: u32, value: &T) -> Vec<u8>
+where
+    T: Serialize,
+{
+    let mut bytes = Vec::new();
+
+    bytes.extend_from_slice(magic);
+    bytes.extend_from_slice(&version.to_le_bytes());
+
+    bincode::serialize_into(&mut bytes, value).expect("simulation's state is always serializable");
+
+    bytes
+}
+
+/// Deserializes value serialized by `encode()`, checking that it's been
+/// written with given magic number & version.
+pub(crate) fn decode<T>(magic: &[u8; 4], version: u32, bytes: &[u8]) -> Result<T, CheckpointError>
+where
+    T: DeserializeOwned,
+{
+    let header = magic.len() + 4;
+
+    if bytes.len() < header || &bytes[..magic.len()] != magic {
+        return Err(CheckpointError::NotACheckpoint);
+    }
+
+    let found = u32::from_le_bytes(bytes[magic.len()..header].try_into().unwrap());
+
+    if found != version {
+        return Err(CheckpointError::UnsupportedVersion {
+            found,
+            supported: version,
+        });
+    }
+
+    bincode::deserialize(&bytes[header..]).map_err(CheckpointError::Corrupted)
+}
+
+pub(crate) fn save(simulation: &Simulation, rng: &ChaCha8Rng) -> Vec<u8> {
+    encode(MAGIC, VERSION, &CheckpointRef { simulation, rng })
+}
+
+pub(crate) fn load(bytes: &[u8]) -> Result<(Simulation, ChaCha8Rng), CheckpointError> {
+    let checkpoint: Checkpoint = decode(MAGIC, VERSION, bytes)?;
+
+    Ok((checkpoint.simulation, checkpoint.rng))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Config, OptimizerConfig};
+    use rand::SeedableRng;
+
+    fn simulation() -> (Simulation, ChaCha8Rng) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let sim = Simulation::random_with_config(
+            &mut rng,
+            Config {
+                optimizer: OptimizerConfig::CmaEs { sigma: 0.1 },
+                ..Config::default()
+            },
+        );
+
+        (sim, rng)
+    }
+
+    #[test]
+    fn resumed_run_follows_the_same_trajectory() {
+        let (mut sim, mut rng) = simulation();
+
+        // Get the optimizer going and stop somewhere mid-generation
+        sim.train(&mut rng);
+
+        for _ in 0..100 {
+            sim.step(&mut rng);
+        }
+
+        let checkpoint = sim.save_checkpoint(&rng);
+
+        sim.train(&mut rng);
+
+        let (mut resumed, mut resumed_rng) = Simulation::load_checkpoint(&checkpoint).unwrap();
+
+        assert_eq!(resumed.generation(), 1);
+        assert_eq!(resumed.history().len(), 1);
+
+        resumed.train(&mut resumed_rng);
+
+        assert_eq!(resumed.generation(), 2);
+
+        // Same world, same optimizer state, same statistics, same RNG
+        assert_eq!(
+            sim.save_checkpoint(&rng),
+            resumed.save_checkpoint(&resumed_rng)
+        );
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(matches!(
+            Simulation::load_checkpoint(b"definitely not a checkpoint"),
+            Err(CheckpointError::NotACheckpoint)
+        ));
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let (sim, rng) = simulation();
+        let mut checkpoint = sim.save_checkpoint(&rng);
+
+        checkpoint[MAGIC.len()..MAGIC.len() + 4].copy_from_slice(&2u32.to_le_bytes());
+
+        let err = Simulation::load_checkpoint(&checkpoint).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "checkpoint has version 2, but only version 1 is supported"
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_checkpoints() {
+        let (sim, rng) = simulation();
+        let checkpoint = sim.save_checkpoint(&rng);
+
+        assert!(matches!(
+            Simulation::load_checkpoint(&checkpoint[..checkpoint.len() / 2]),
+            Err(CheckpointError::Corrupted(_))
+        ));
+    }
+}
@@ -0,0 +1,236 @@
+use crate::optimizer::Optimizer;
+use crate::simulation::{GENERATION_LENGTH, SPEED_MAX, SPEED_MIN};
+use crate::{Brain, Error, Eye};
+use lib_genetic_algorithm::{DifferentialEvolutionStrategy, MutationSchedule};
+use serde::{Deserialize, Serialize};
+use std::ops::Bound;
+
+/// Knobs of a `Simulation`; `Config::default()` reproduces the original
+/// setup.
+///
+/// Everything except `animals`, `eye` and `sensors` can be changed while
+/// the simulation is running (see `Simulation::reconfigure()`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    /// Number of birds.
+    pub animals: usize,
+
+    /// Number of foods.
+    pub foods: usize,
+
+    /// Eye every bird is born with; its number of cells determines the
+    /// shape of birds' brains.
+    pub eye: Eye,
+
+    /// Sensors every bird is born with, on top of its eye; each of them
+    /// makes birds' brains larger.
+    pub sensors: Vec<SensorConfig>,
+
+    /// Slowest a bird can fly.
+    pub speed_min: f32,
+
+    /// Fastest a bird can fly.
+    pub speed_max: f32,
+
+    /// Number of steps each generation lives for.
+    pub generation_length: usize,
+
+    pub optimizer: OptimizerConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            animals: 10,
+            foods: 60,
+            eye: Eye::default(),
+            sensors: Vec::new(),
+            speed_min: SPEED_MIN,
+            speed_max: SPEED_MAX,
+            generation_length: GENERATION_LENGTH,
+            optimizer: OptimizerConfig::GeneticAlgorithm {
+                selection: SelectionConfig::RouletteWheel,
+                crossover: CrossoverConfig::Uniform,
+                mutation: MutationConfig::UniformPerturbation {
+                    chance: 0.01,
+                    coeff: 0.2,
+                },
+            },
+        }
+    }
+}
+
+impl Config {
+    /// Checks whether a simulation can be run with this config.
+    pub fn validate(&self) -> Result<(), Error> {
+        Error::check("animals", self.animals as f32, 1.0.., "1..")?;
+        Error::check("speed_min", self.speed_min, 0.0.., "0.0..")?;
+
+        // Birds that can't fly at all would make `SpeedSensor` divide by zero
+        let positive = (Bound::Excluded(0.0), Bound::Unbounded);
+        Error::check("speed_max", self.speed_max, positive, "(0.0, inf)")?;
+        Error::check("speed_max", self.speed_max, self.speed_min.., "speed_min..")?;
+
+        // Eyes can be deserialized bypassing `Eye::try_new()`
+        Eye::try_new(self.eye.fov_range(), self.eye.fov_angle(), self.eye.cells())?;
+
+        for sensor in &self.sensors {
+            if let SensorConfig::Clock { period } = sensor {
+                Error::check("clock period", *period as f32, 1.0.., "1..")?;
+            }
+        }
+
+        Optimizer::try_new(&self.optimizer, &self.neuron_sizes())?;
+
+        Ok(())
+    }
+
+    /// Sizes of birds' neurons inside their chromosomes (see
+    /// `Brain::neuron_sizes()`).
+    pub(crate) fn neuron_sizes(&self) -> Vec<usize> {
+        Brain::neuron_sizes(&self.eye, &self.sensors)
+    }
+}
+
+/// Something a bird can feel besides what it sees (see `Sensor`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SensorConfig {
+    /// See: `SpeedSensor`.
+    Speed,
+
+    /// See: `CompassSensor`.
+    Compass,
+
+    /// See: `HungerSensor`.
+    Hunger,
+
+    /// See: `WallSensor`.
+    Wall,
+
+    /// See: `ClockSensor`.
+    Clock { period: usize },
+}
+
+/// Which optimizer evolves birds' brains.
+///
+/// Since a simulation's world is generated before its optimizer, two
+/// simulations created from the same seed fly in the same world - which
+/// makes it easy to benchmark optimizers against each other.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum OptimizerConfig {
+    /// Classic genetic algorithm.
+    GeneticAlgorithm {
+        selection: SelectionConfig,
+        crossover: CrossoverConfig,
+        mutation: MutationConfig,
+    },
+
+    /// CMA-ES with full covariance matrix.
+    CmaEs { sigma: f32 },
+
+    /// CMA-ES learning only the diagonal of covariance matrix.
+    SepCmaEs { sigma: f32 },
+
+    /// Differential evolution; `f` is the differential weight, `cr` the
+    /// crossover probability.
+    DifferentialEvolution {
+        strategy: DifferentialEvolutionStrategy,
+        f: f32,
+        cr: f32,
+    },
+
+    /// Particle swarm optimization.
+    ParticleSwarm {
+        inertia: f32,
+        cognitive: f32,
+        social: f32,
+        max_velocity: f32,
+    },
+}
+
+/// How `OptimizerConfig::GeneticAlgorithm` picks parents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelectionConfig {
+    /// See: `ga::RouletteWheelSelection`.
+    RouletteWheel,
+
+    /// See: `ga::TournamentSelection`.
+    Tournament { size: usize },
+}
+
+/// How `OptimizerConfig::GeneticAlgorithm` mixes parents' brains.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CrossoverConfig {
+    /// See: `ga::UniformCrossover`.
+    Uniform,
+
+    /// See: `ga::SinglePointCrossover`.
+    SinglePoint,
+
+    /// See: `ga::KPointCrossover`.
+    KPoint { k: usize },
+
+    /// See: `ga::BlendCrossover`.
+    Blend { alpha: f32 },
+
+    /// See: `ga::SimulatedBinaryCrossover`.
+    SimulatedBinary { eta: f32 },
+
+    /// See: `ga::NeuronCrossover`.
+    Neuron,
+}
+
+/// How `OptimizerConfig::GeneticAlgorithm` mutates birds' brains.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MutationConfig {
+    /// See: `ga::UniformPerturbationMutation`.
+    UniformPerturbation { chance: f32, coeff: f32 },
+
+    /// See: `ga::NormalMutation`.
+    Normal { chance: f32, sigma: f32 },
+
+    /// See: `ga::CauchyMutation`.
+    Cauchy { chance: f32, scale: f32 },
+
+    /// See: `ga::SelfAdaptiveMutation`.
+    SelfAdaptive { initial_sigma: f32, min_sigma: f32 },
+
+    /// Mutation whose chance and strength change as the evolution goes
+    /// on; see: `ga::ScheduledMutation`.
+    Scheduled {
+        mutation: TunableMutationConfig,
+        schedule: MutationSchedule,
+    },
+
+    /// Picks one of given mutations each generation, preferring the ones
+    /// that have recently helped the most; see: `ga::AdaptiveMutation`.
+    Adaptive {
+        mutations: Vec<MutationConfig>,
+        exploration: f32,
+    },
+}
+
+/// Mutations `MutationConfig::Scheduled` can tune.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TunableMutationConfig {
+    /// See: `ga::UniformPerturbationMutation`.
+    UniformPerturbation { chance: f32, coeff: f32 },
+
+    /// See: `ga::NormalMutation`.
+    Normal { chance: f32, sigma: f32 },
+
+    /// See: `ga::CauchyMutation`.
+    Cauchy { chance: f32, scale: f32 },
+}
+
+impl From<TunableMutationConfig> for MutationConfig {
+    fn from(config: TunableMutationConfig) -> Self {
+        match config {
+            TunableMutationConfig::UniformPerturbation { chance, coeff } => {
+                Self::UniformPerturbation { chance, coeff }
+            }
+            TunableMutationConfig::Normal { chance, sigma } => Self::Normal { chance, sigma },
+            TunableMutationConfig::Cauchy { chance, scale } => Self::Cauchy { chance, scale },
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::simulation::{step_world_with, ROTATION_ACCEL, SPEED_ACCEL};
+use crate::{Brain, Config, Eye, World};
+use lib_neural_network as nn;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Decides how a bird flies, given what its eye sees (followed by what its
+/// sensors feel, see `Config::sensors`).
+///
+/// Birds are normally driven by their own brains, but they can be driven
+/// by scripted controllers as well - e.g. to see how well a hand-written
+/// strategy does (see `baseline()`) or to teach brains to imitate it (see
+/// `demonstrate()`).
+pub trait Controller {
+    /// Returns change of speed and change of rotation (both unclamped),
+    /// same as `Brain::response()`.
+    fn act(&mut self, inputs: &[f32]) -> [f32; 2];
+}
+
+impl Controller for Brain {
+    fn act(&mut self, inputs: &[f32]) -> [f32; 2] {
+        Brain::response(&self.propagate(inputs.to_vec()))
+    }
+}
+
+/// Flies at full speed, turning towards the strongest eye cell - i.e.
+/// towards the most (or the closest) food.
+#[derive(Clone, Debug)]
+pub struct TurnTowardsFood {
+    fov_angle: f32,
+    cells: usize,
+}
+
+impl TurnTowardsFood {
+    pub fn new(eye: &Eye) -> Self {
+        Self {
+            fov_angle: eye.fov_angle(),
+            cells: eye.cells(),
+        }
+    }
+}
+
+impl Controller for TurnTowardsFood {
+    fn act(&mut self, inputs: &[f32]) -> [f32; 2] {
+        // Sensors' readings (if any) follow the eye's cells
+        let vision = &inputs[..inputs.len().min(self.cells)];
+
+        let strongest = vision
+            .iter()
+            .enumerate()
+            .filter(|(_, energy)| **energy > 0.0)
+            .max_by(|(_, lhs), (_, rhs)| lhs.total_cmp(rhs));
+
+        let rotation = match strongest {
+            // Cells cover the field of view counterclockwise, so this is
+            // the angle between bird's heading and the middle of the cell
+            Some((cell, _)) => ((cell as f32 + 0.5) / vision.len() as f32 - 0.5) * self.fov_angle,
+
+            // Nothing in sight, keep looking
+            None => 0.0,
+        };
+
+        [SPEED_ACCEL, rotation]
+    }
+}
+
+/// Changes speed and rotation at random.
+#[derive(Clone, Debug)]
+pub struct RandomWalk {
+    rng: ChaCha8Rng,
+}
+
+impl RandomWalk {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Controller for RandomWalk {
+    fn act(&mut self, _: &[f32]) -> [f32; 2] {
+        [
+            self.rng.gen_range(-SPEED_ACCEL..=SPEED_ACCEL),
+            self.rng.gen_range(-ROTATION_ACCEL..=ROTATION_ACCEL),
+        ]
+    }
+}
+
+/// Lets `controller` drive all birds of a random world (described by
+/// `config`) for a single generation and returns their average fitness -
+/// to compare with `ga::Statistics::avg_fitness` of the evolved ones.
+pub fn baseline(rng: &mut dyn RngCore, config: &Config, controller: &mut dyn Controller) -> f32 {
+    let world = fly(rng, config, controller);
+
+    let fitness: usize = world.animals.iter().map(|animal| animal.satiation).sum();
+
+    fitness as f32 / world.animals.len().max(1) as f32
+}
+
+/// Lets `expert` drive all birds of a random world (described by
+/// `config`) for a single generation and records what the birds have
+/// seen & felt, together with what the expert has done about it (clamped the
+/// same way brains' responses are) - ready to be fed into
+/// `Brain::pretrain()`.
+pub fn demonstrate(
+    rng: &mut dyn RngCore,
+    config: &Config,
+    expert: &mut dyn Controller,
+) -> Vec<nn::Sample> {
+    let mut demonstrator = Demonstrator {
+        expert,
+        samples: Vec::new(),
+    };
+
+    fly(rng, config, &mut demonstrator);
+
+    demonstrator.samples
+}
+
+fn fly(rng: &mut dyn RngCore, config: &Config, controller: &mut dyn Controller) -> World {
+    let mut world = World::random_with_senses(
+        rng,
+        config.animals,
+        config.foods,
+        &config.eye,
+        &config.sensors,
+    );
+
+    for _ in 0..config.generation_length {
+        step_world_with(&mut world, rng, config, controller);
+    }
+
+    world
+}
+
+struct Demonstrator<'a> {
+    expert: &'a mut dyn Controller,
+    samples: Vec<nn::Sample>,
+}
+
+impl Controller for Demonstrator<'_> {
+    fn act(&mut self, inputs: &[f32]) -> [f32; 2] {
+        let [speed, rotation] = self.expert.act(inputs);
+
+        let response = [
+            speed.clamp(-SPEED_ACCEL, SPEED_ACCEL),
+            rotation.clamp(-ROTATION_ACCEL, ROTATION_ACCEL),
+        ];
+
+        self.samples.push(nn::Sample {
+            inputs: inputs.to_vec(),
+            targets: Brain::outputs(response),
+        });
+
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Simulation;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn config() -> Config {
+        Config {
+            generation_length: 300,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn turn_towards_food() {
+        let mut controller = TurnTowardsFood::new(&Eye::new(0.25, FRAC_PI_2, 5));
+
+        let [speed, rotation] = controller.act(&[0.0, 0.0, 0.0, 0.5, 0.1]);
+
+        assert_eq!(speed, SPEED_ACCEL);
+        assert!((rotation - 0.2 * FRAC_PI_2).abs() < 1e-6);
+
+        let [_, rotation] = controller.act(&[0.7, 0.0, 0.2, 0.0, 0.0]);
+
+        assert!((rotation + 0.4 * FRAC_PI_2).abs() < 1e-6);
+
+        assert_eq!(controller.act(&[0.0; 5]), [SPEED_ACCEL, 0.0]);
+    }
+
+    #[test]
+    fn baselines() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let expert = baseline(
+            &mut rng,
+            &config(),
+            &mut TurnTowardsFood::new(&Eye::default()),
+        );
+        let random = baseline(&mut rng, &config(), &mut RandomWalk::new(0));
+
+        assert!(
+            expert > 2.0 * random,
+            "expert={}, random={}",
+            expert,
+            random
+        );
+    }
+
+    #[test]
+    fn pretrain() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut expert = TurnTowardsFood::new(&Eye::default());
+
+        let samples = demonstrate(&mut rng, &config(), &mut expert);
+
+        assert_eq!(samples.len(), 10 * 300);
+        assert!(samples.iter().all(|sample| sample.inputs.len() == 9));
+        assert!(samples
+            .iter()
+            .all(|sample| sample.targets[0] == SPEED_ACCEL));
+
+        let mut sim = Simulation::random_with_config(&mut rng, config());
+        let mut brain = Brain::random(&mut rng, &Eye::default(), &[]);
+
+        let before = baseline(&mut rng, &config(), &mut brain);
+        let first = brain.pretrain(&mut rng, &samples, 1);
+        let last = brain.pretrain(&mut rng, &samples, 10);
+        let after = baseline(&mut rng, &config(), &mut brain);
+
+        assert!(last < first, "first={}, last={}", first, last);
+        assert!(after > before, "before={}, after={}", before, after);
+
+        // Expert turns both ways, so should its pupil
+        let mean_rotation = |expert_turns: fn(f32) -> bool| {
+            let rotations: Vec<_> = samples
+                .iter()
+                .filter(|sample| expert_turns(Brain::response(&sample.targets)[1]))
+                .map(|sample| Brain::response(&brain.propagate(sample.inputs.clone()))[1])
+                .collect();
+
+            rotations.iter().sum::<f32>() / rotations.len() as f32
+        };
+
+        let right = mean_rotation(|rotation| rotation < 0.0);
+        let left = mean_rotation(|rotation| rotation > 0.0);
+
+        assert!(right < 0.0 && left > 0.0, "right={}, left={}", right, left);
+
+        // Pretrained birds can be evolved further as usual
+        assert!(sim.pretrain(&mut rng, &samples, 2) > 0.0);
+
+        let stats = sim.train(&mut rng);
+
+        assert!(stats.avg_fitness > 0.0);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::CheckpointError;
+use lib_genetic_algorithm as ga;
+use lib_neural_network as nn;
+use std::fmt;
+
+/// Error returned by fallible (`try_*`) operations on simulations.
+#[derive(Debug)]
+pub enum Error {
+    /// Config's (or eye's) parameter is out of its range.
+    InvalidParameter {
+        name: &'static str,
+        value: f32,
+
+        /// Human-readable range, e.g. `0.0..=1.0`.
+        expected: &'static str,
+    },
+
+    Brain(nn::Error),
+    Optimizer(ga::Error),
+    Checkpoint(CheckpointError),
+}
+
+impl Error {
+    /// Checks that `value` lies within `range`.
+    pub(crate) fn check<R>(
+        name: &'static str,
+        value: f32,
+        range: R,
+        expected: &'static str,
+    ) -> Result<(), Self>
+    where
+        R: std::ops::RangeBounds<f32>,
+    {
+        if range.contains(&value) {
+            Ok(())
+        } else {
+            Err(Self::InvalidParameter {
+                name,
+                value,
+                expected,
+            })
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidParameter {
+                name,
+                value,
+                expected,
+            } => write!(f, "{} must be within {}, but got {}", name, expected, value),
+
+            Self::Brain(err) => write!(f, "brain: {}", err),
+            Self::Optimizer(err) => write!(f, "optimizer: {}", err),
+            Self::Checkpoint(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidParameter { .. } => None,
+            Self::Brain(err) => Some(err),
+            Self::Optimizer(err) => Some(err),
+            Self::Checkpoint(err) => Some(err),
+        }
+    }
+}
+
+impl From<nn::Error> for Error {
+    fn from(err: nn::Error) -> Self {
+        Self::Brain(err)
+    }
+}
+
+impl From<ga::Error> for Error {
+    fn from(err: ga::Error) -> Self {
+        Self::Optimizer(err)
+    }
+}
+
+impl From<CheckpointError> for Error {
+    fn from(err: CheckpointError) -> Self {
+        Self::Checkpoint(err)
+    }
+}
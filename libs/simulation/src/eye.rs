@@ -1,5 +1,7 @@
 use crate::*;
+use serde::{Deserialize, Serialize};
 use std::f32::consts::*;
+use std::ops::Bound;
 
 /// How far our eye can see:
 ///
@@ -94,7 +96,7 @@ const FOV_ANGLE: f32 = FRAC_PI_4;
 /// than ~20 photoreceptors yielding progressively worse results.
 const CELLS: usize = 9;
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Eye {
     fov_range: f32,
     fov_angle: f32,
@@ -102,19 +104,33 @@ pub struct Eye {
 }
 
 impl Eye {
-    // FOV_RANGE, FOV_ANGLE & CELLS are the values we'll use during
-    // simulation - but being able to create an arbitrary eye will
-    // come handy during the testing:
-    fn new(fov_range: f32, fov_angle: f32, cells: usize) -> Self {
-        assert!(fov_range > 0.0);
-        assert!(fov_angle > 0.0);
-        assert!(cells > 0);
-
-        Self {
+    /// FOV_RANGE, FOV_ANGLE & CELLS are the values we use by default
+    /// (see `Eye::default()`) - but being able to create an arbitrary eye
+    /// comes handy during the testing and when configuring simulations.
+    pub fn new(fov_range: f32, fov_angle: f32, cells: usize) -> Self {
+        Self::try_new(fov_range, fov_angle, cells).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(fov_range: f32, fov_angle: f32, cells: usize) -> Result<Self, Error> {
+        let positive = (Bound::Excluded(0.0), Bound::Unbounded);
+
+        Error::check("fov_range", fov_range, positive, "(0.0, inf)")?;
+        Error::check("fov_angle", fov_angle, positive, "(0.0, inf)")?;
+        Error::check("cells", cells as f32, 1.0.., "1..")?;
+
+        Ok(Self {
             fov_range,
             fov_angle,
             cells,
-        }
+        })
+    }
+
+    pub fn fov_range(&self) -> f32 {
+        self.fov_range
+    }
+
+    pub fn fov_angle(&self) -> f32 {
+        self.fov_angle
     }
 
     pub fn cells(&self) -> usize {
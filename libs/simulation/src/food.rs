@@ -1,7 +1,8 @@
 use rand::{Rng, RngCore};
 use crate::Point;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Food {
     pub(crate) position: Point,
 }
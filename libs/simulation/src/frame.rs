@@ -0,0 +1,67 @@
+use crate::checkpoint::{self, CheckpointError};
+use crate::{Point, Rotation, Simulation};
+use serde::{Deserialize, Serialize};
+
+/// Marks the beginning of every file with exported frames.
+const MAGIC: &[u8; 4] = b"SLFR";
+
+/// Version of the frames file format (see `checkpoint::VERSION`).
+const VERSION: u32 = 1;
+
+/// What the world looked like after given step of a recorded run - just
+/// enough to draw it, without birds' brains.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Frame {
+    /// Number of steps simulated since the beginning of the run.
+    pub tick: usize,
+
+    pub generation: usize,
+
+    /// Number of steps the current generation has lived so far.
+    pub age: usize,
+
+    pub birds: Vec<BirdState>,
+    pub foods: Vec<Point>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BirdState {
+    pub id: u64,
+    pub position: Point,
+    pub rotation: Rotation,
+    pub speed: f32,
+    pub satiation: usize,
+}
+
+impl Frame {
+    pub(crate) fn capture(tick: usize, simulation: &Simulation) -> Self {
+        let world = simulation.world();
+
+        Self {
+            tick,
+            generation: simulation.generation(),
+            age: simulation.age(),
+            birds: world
+                .animals
+                .iter()
+                .map(|animal| BirdState {
+                    id: animal.id,
+                    position: animal.position,
+                    rotation: animal.rotation,
+                    speed: animal.speed,
+                    satiation: animal.satiation,
+                })
+                .collect(),
+            foods: world.foods.iter().map(|food| food.position).collect(),
+        }
+    }
+
+    /// Loads frames exported by `Player::export()`.
+    pub fn import(bytes: &[u8]) -> Result<Vec<Self>, CheckpointError> {
+        checkpoint::decode(MAGIC, VERSION, bytes)
+    }
+
+    pub(crate) fn export(frames: &[Self]) -> Vec<u8> {
+        checkpoint::encode(MAGIC, VERSION, &frames)
+    }
+}
@@ -0,0 +1,312 @@
+use crate::simulation::{animals, population, scatter_foods, step_world};
+use crate::{Animal, AnimalIndividual, Config, Error, World};
+use lib_genetic_algorithm as ga;
+use rand::RngCore;
+
+/// Where the islands' birds live.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IslandLayout {
+    /// All islands fly in one world, competing for the same food - handy
+    /// for comparing strategies side by side.
+    SharedWorld,
+
+    /// Each island gets a world of its own.
+    SeparateWorlds,
+}
+
+/// Like `Simulation`, but the birds are split into islands, each one
+/// evolved by its own optimizer (see `ga::IslandModel`).
+pub struct IslandSimulation {
+    worlds: Vec<World>,
+    layout: IslandLayout,
+    model: ga::IslandModel<AnimalIndividual>,
+
+    /// Rules every island lives by; `animals` and `foods` are counted per
+    /// island.
+    config: Config,
+
+    age: usize,
+}
+
+impl IslandSimulation {
+    /// Panics if the config is invalid or if there are no optimizers (see
+    /// `try_random()`).
+    pub fn random(
+        rng: &mut dyn RngCore,
+        config: Config,
+        optimizers: Vec<Box<dyn ga::Optimizer<AnimalIndividual>>>,
+        policy: ga::MigrationPolicy,
+        layout: IslandLayout,
+    ) -> Self {
+        Self::try_random(rng, config, optimizers, policy, layout)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Every island gets `config.animals` birds and `config.foods` foods;
+    /// since islands bring optimizers of their own, `config.optimizer` is
+    /// ignored (but still has to be valid).
+    pub fn try_random(
+        rng: &mut dyn RngCore,
+        config: Config,
+        optimizers: Vec<Box<dyn ga::Optimizer<AnimalIndividual>>>,
+        policy: ga::MigrationPolicy,
+        layout: IslandLayout,
+    ) -> Result<Self, Error> {
+        config.validate()?;
+
+        let islands = optimizers.len();
+        let model = ga::IslandModel::try_new(optimizers, policy)?;
+
+        let world = |rng: &mut dyn RngCore, islands: usize| {
+            World::random_with_senses(
+                rng,
+                islands * config.animals,
+                islands * config.foods,
+                &config.eye,
+                &config.sensors,
+            )
+        };
+
+        let worlds = match layout {
+            IslandLayout::SharedWorld => vec![world(rng, islands)],
+            IslandLayout::SeparateWorlds => (0..islands).map(|_| world(rng, 1)).collect(),
+        };
+
+        Ok(Self {
+            worlds,
+            layout,
+            model,
+            config,
+            age: 0,
+        })
+    }
+
+    pub fn worlds(&self) -> &[World] {
+        &self.worlds
+    }
+
+    pub fn layout(&self) -> IslandLayout {
+        self.layout
+    }
+
+    pub fn islands(&self) -> usize {
+        self.model.len()
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Birds belonging to given island.
+    pub fn island(&self, island: usize) -> &[Animal] {
+        match self.layout {
+            IslandLayout::SharedWorld => {
+                let from = island * self.config.animals;
+                &self.worlds[0].animals[from..from + self.config.animals]
+            }
+
+            IslandLayout::SeparateWorlds => &self.worlds[island].animals,
+        }
+    }
+
+    /// Panics if the birds can't be evolved (see `try_step()`).
+    pub fn step(&mut self, rng: &mut dyn RngCore) -> Option<Vec<ga::Statistics>> {
+        self.try_step(rng).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Returns per-island statistics when a generation has just ended.
+    ///
+    /// If the evolution fails, birds keep living and the evolution is
+    /// retried during the next step (see `Simulation::try_step()`).
+    pub fn try_step(
+        &mut self,
+        rng: &mut dyn RngCore,
+    ) -> Result<Option<Vec<ga::Statistics>>, Error> {
+        for world in &mut self.worlds {
+            step_world(world, rng, &self.config);
+        }
+
+        self.age += 1;
+        if self.age > self.config.generation_length {
+            self.try_evolve(rng).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn train(&mut self, rng: &mut dyn RngCore) -> Vec<ga::Statistics> {
+        self.try_train(rng).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_train(&mut self, rng: &mut dyn RngCore) -> Result<Vec<ga::Statistics>, Error> {
+        loop {
+            if let Some(summary) = self.try_step(rng)? {
+                return Ok(summary);
+            }
+        }
+    }
+
+    fn try_evolve(&mut self, rng: &mut dyn RngCore) -> Result<Vec<ga::Statistics>, Error> {
+        let populations: Vec<_> = (0..self.islands())
+            .map(|island| population(self.island(island)))
+            .collect();
+
+        let (evolved_populations, stats) = self.model.try_evolve(rng, &populations)?;
+
+        self.age = 0;
+
+        match self.layout {
+            IslandLayout::SharedWorld => {
+                let evolved_animals = evolved_populations
+                    .into_iter()
+                    .flat_map(|population| {
+                        animals(population, &self.config.eye, &self.config.sensors, rng)
+                    })
+                    .collect();
+
+                self.worlds[0].populate(evolved_animals);
+            }
+
+            IslandLayout::SeparateWorlds => {
+                for (world, population) in self.worlds.iter_mut().zip(evolved_populations) {
+                    world.populate(animals(
+                        population,
+                        &self.config.eye,
+                        &self.config.sensors,
+                        rng,
+                    ));
+                }
+            }
+        }
+
+        for world in &mut self.worlds {
+            scatter_foods(world, rng);
+        }
+
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Eye, SensorConfig};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn optimizer() -> Box<dyn ga::Optimizer<AnimalIndividual>> {
+        Box::new(ga::GeneticAlgorithm::new(
+            ga::RouletteWheelSelection,
+            ga::UniformCrossover,
+            ga::UniformPerturbationMutation::new(0.01, 0.2),
+        ))
+    }
+
+    #[test]
+    fn shared_world() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut sim = IslandSimulation::random(
+            &mut rng,
+            Config::default(),
+            vec![optimizer(), optimizer()],
+            ga::MigrationPolicy::new(ga::Topology::Ring, 1, 2),
+            IslandLayout::SharedWorld,
+        );
+
+        let stats = sim.train(&mut rng);
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(sim.worlds().len(), 1);
+        assert_eq!(sim.island(0).len(), 10);
+        assert_eq!(sim.island(1).len(), 10);
+    }
+
+    #[test]
+    fn separate_worlds() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut sim = IslandSimulation::random(
+            &mut rng,
+            Config::default(),
+            vec![optimizer(), optimizer(), optimizer()],
+            ga::MigrationPolicy::new(ga::Topology::FullyConnected, 1, 1),
+            IslandLayout::SeparateWorlds,
+        );
+
+        let stats = sim.train(&mut rng);
+
+        assert_eq!(stats.len(), 3);
+        assert_eq!(sim.worlds().len(), 3);
+        assert!(sim.worlds().iter().all(|world| world.animals().len() == 10));
+    }
+
+    #[test]
+    fn custom_config() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            animals: 4,
+            foods: 7,
+            eye: Eye::new(0.5, 1.0, 3),
+            sensors: vec![SensorConfig::Speed, SensorConfig::Wall],
+            generation_length: 20,
+            ..Config::default()
+        };
+
+        let mut sim = IslandSimulation::random(
+            &mut rng,
+            config.clone(),
+            // Birds barely eat anything within 20 steps, which roulette
+            // wheel can't cope with
+            (0..2)
+                .map(|_| {
+                    Box::new(ga::GeneticAlgorithm::new(
+                        ga::TournamentSelection::new(2),
+                        ga::UniformCrossover,
+                        ga::UniformPerturbationMutation::new(0.01, 0.2),
+                    )) as Box<dyn ga::Optimizer<_>>
+                })
+                .collect(),
+            ga::MigrationPolicy::new(ga::Topology::Ring, 1, 1),
+            IslandLayout::SharedWorld,
+        );
+
+        assert_eq!(sim.worlds()[0].animals().len(), 8);
+        assert_eq!(sim.worlds()[0].foods().len(), 14);
+
+        for _ in 0..20 {
+            assert!(sim.step(&mut rng).is_none());
+        }
+
+        assert_eq!(sim.step(&mut rng).unwrap().len(), 2);
+
+        for island in 0..2 {
+            assert_eq!(sim.island(island).len(), 4);
+
+            assert!(sim.island(island).iter().all(|animal| {
+                animal.eye() == &config.eye && animal.sensors() == config.sensors.as_slice()
+            }));
+        }
+    }
+
+    #[test]
+    fn invalid_config() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let err = IslandSimulation::try_random(
+            &mut rng,
+            Config {
+                animals: 0,
+                ..Config::default()
+            },
+            vec![optimizer()],
+            ga::MigrationPolicy::new(ga::Topology::Ring, 1, 1),
+            IslandLayout::SeparateWorlds,
+        )
+        .map(drop)
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "animals must be within 1.., but got 0");
+    }
+}
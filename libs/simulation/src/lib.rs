@@ -3,16 +3,44 @@ use nalgebra as na;
 mod animal;
 mod animal_individual;
 mod brain;
+mod champion;
+mod checkpoint;
+mod config;
+mod controller;
+mod error;
 mod eye;
 mod food;
+mod frame;
+mod island_simulation;
+mod lineage;
+mod optimizer;
+mod player;
+mod recording;
+mod sensor;
 mod simulation;
 mod word;
 
 pub use animal::Animal;
 pub use animal_individual::AnimalIndividual;
 pub use brain::Brain;
+pub use champion::Champion;
+pub use checkpoint::CheckpointError;
+pub use config::{
+    Config, CrossoverConfig, MutationConfig, OptimizerConfig, SelectionConfig, SensorConfig,
+    TunableMutationConfig,
+};
+pub use controller::{baseline, demonstrate, Controller, RandomWalk, TurnTowardsFood};
+pub use error::Error;
 pub use eye::Eye;
 pub use food::Food;
+pub use frame::{BirdState, Frame};
+pub use island_simulation::{IslandLayout, IslandSimulation};
+pub use lib_genetic_algorithm::{DifferentialEvolutionStrategy, MutationSchedule};
+pub use lib_neural_network::Sample;
+pub use lineage::{Lineage, LineageEntry};
+pub use player::Player;
+pub use recording::{Recorder, Recording};
+pub use sensor::{ClockSensor, CompassSensor, HungerSensor, Sensor, SpeedSensor, WallSensor};
 pub use simulation::Simulation;
 pub use word::World;
 
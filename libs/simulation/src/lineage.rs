@@ -0,0 +1,315 @@
+use crate::Animal;
+use lib_genetic_algorithm as ga;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+/// Everything known about how a single bird has come into existence.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LineageEntry {
+    pub id: u64,
+
+    /// Generation the bird has lived in.
+    pub generation: usize,
+
+    /// Ids of both parents; `None` for founders of the population and for
+    /// birds sampled rather than bred (e.g. by CMA-ES).
+    pub parents: Option<[u64; 2]>,
+
+    /// How many genes have been inherited unchanged from each parent.
+    pub inherited: [usize; 2],
+
+    /// Indices of genes changed by mutation.
+    pub mutated: Vec<usize>,
+
+    /// Bird's fitness; known only once its generation has ended.
+    pub fitness: Option<f32>,
+}
+
+/// Family tree of every bird that has ever lived in a `Simulation` - handy
+/// for tracing where a useful behaviour has first appeared.
+///
+/// Nothing is ever forgotten, so the store grows by one entry per bird per
+/// generation - that's why simulations track it only on demand (see
+/// `Simulation::track_lineage()`).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Lineage {
+    entries: BTreeMap<u64, LineageEntry>,
+}
+
+impl Lineage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, id: u64) -> Option<&LineageEntry> {
+        self.entries.get(&id)
+    }
+
+    /// All the entries, ordered by id.
+    pub fn entries(&self) -> impl Iterator<Item = &LineageEntry> {
+        self.entries.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Ids of all the ancestors of given bird (parents, grandparents
+    /// etc.), ordered by id.
+    pub fn ancestors(&self, id: u64) -> Vec<u64> {
+        let mut ancestors = BTreeSet::new();
+        let mut pending = vec![id];
+
+        while let Some(id) = pending.pop() {
+            let Some(parents) = self.get(id).and_then(|entry| entry.parents) else {
+                continue;
+            };
+
+            for parent in parents {
+                if ancestors.insert(parent) {
+                    pending.push(parent);
+                }
+            }
+        }
+
+        ancestors.into_iter().collect()
+    }
+
+    /// Ids of all the descendants of given bird (children, grandchildren
+    /// etc.), ordered by id.
+    pub fn descendants(&self, id: u64) -> Vec<u64> {
+        // Children always get higher ids than their parents, so a single
+        // pass suffices
+        let mut descendants = BTreeSet::new();
+
+        for entry in self.entries.range(id + 1..).map(|(_, entry)| entry) {
+            let is_descendant = entry.parents.map_or(false, |parents| {
+                parents
+                    .iter()
+                    .any(|parent| *parent == id || descendants.contains(parent))
+            });
+
+            if is_descendant {
+                descendants.insert(entry.id);
+            }
+        }
+
+        descendants.into_iter().collect()
+    }
+
+    /// The youngest bird both given birds descend from (a bird counts as
+    /// its own ancestor here, so that the parent is the common ancestor of
+    /// itself and its child); on ties, the one with the highest id wins.
+    pub fn most_recent_common_ancestor(&self, a: u64, b: u64) -> Option<u64> {
+        let with_self = |id| {
+            let mut ancestors: BTreeSet<_> = self.ancestors(id).into_iter().collect();
+            ancestors.insert(id);
+            ancestors
+        };
+
+        with_self(a)
+            .intersection(&with_self(b))
+            .filter_map(|id| self.get(*id))
+            .max_by_key(|entry| (entry.generation, entry.id))
+            .map(|entry| entry.id)
+    }
+
+    /// Exports the family tree in GraphViz's DOT format; edges point from
+    /// parents to children and are labelled with the number of genes
+    /// inherited.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph lineage {\n");
+
+        for entry in self.entries() {
+            let mut label = format!("#{}\\ngeneration {}", entry.id, entry.generation);
+
+            if let Some(fitness) = entry.fitness {
+                _ = write!(label, "\\nfitness {}", fitness);
+            }
+
+            if !entry.mutated.is_empty() {
+                _ = write!(label, "\\n{} mutated", entry.mutated.len());
+            }
+
+            _ = writeln!(dot, "    {} [label=\"{}\"];", entry.id, label);
+        }
+
+        for entry in self.entries() {
+            let Some(parents) = entry.parents else {
+                continue;
+            };
+
+            for (parent, inherited) in parents.iter().zip(entry.inherited) {
+                _ = writeln!(
+                    dot,
+                    "    {} -> {} [label=\"{}\"];",
+                    parent, entry.id, inherited
+                );
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Exports all the entries as a JSON array, ordered by id.
+    pub fn to_json(&self) -> String {
+        let entries: Vec<_> = self.entries().collect();
+
+        serde_json::to_string(&entries).expect("lineage is always serializable")
+    }
+
+    /// Records birds that haven't been bred by us.
+    pub(crate) fn add_founders(&mut self, generation: usize, animals: &[Animal]) {
+        for animal in animals {
+            self.insert(LineageEntry {
+                id: animal.id,
+                generation,
+                parents: None,
+                inherited: [0, 0],
+                mutated: Vec::new(),
+                fitness: None,
+            });
+        }
+    }
+
+    /// Records children born out of `parents`; `births` come from
+    /// `ga::Optimizer::evolve_traced()` and refer to parents by their
+    /// indices.
+    pub(crate) fn add_children(
+        &mut self,
+        generation: usize,
+        parents: &[u64],
+        children: &[Animal],
+        births: Vec<ga::Birth>,
+    ) {
+        assert_eq!(children.len(), births.len());
+
+        for (child, birth) in children.iter().zip(births) {
+            self.insert(LineageEntry {
+                id: child.id,
+                generation,
+                parents: birth.parents.map(|[a, b]| [parents[a], parents[b]]),
+                inherited: birth.inherited,
+                mutated: birth.mutated,
+                fitness: None,
+            });
+        }
+    }
+
+    /// Records fitness of birds whose generation has just ended.
+    pub(crate) fn set_fitness(&mut self, animals: &[Animal]) {
+        for animal in animals {
+            if let Some(entry) = self.entries.get_mut(&animal.id) {
+                entry.fitness = Some(animal.satiation as f32);
+            }
+        }
+    }
+
+    fn insert(&mut self, entry: LineageEntry) {
+        self.entries.insert(entry.id, entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: u64, generation: usize, parents: Option<[u64; 2]>) -> LineageEntry {
+        LineageEntry {
+            id,
+            generation,
+            parents,
+            inherited: [1, 2],
+            mutated: vec![0],
+            fitness: None,
+        }
+    }
+
+    /// Founders 0, 1 and 2, their children 3 (of 0 & 0), 4 (of 0 & 1)
+    /// and 5 (of 1 & 2), and grandchildren 6 (of 3 & 4) and 7 (of 5 & 5).
+    fn lineage() -> Lineage {
+        let mut lineage = Lineage::new();
+
+        lineage.insert(entry(0, 0, None));
+        lineage.insert(entry(1, 0, None));
+        lineage.insert(entry(2, 0, None));
+        lineage.insert(entry(3, 1, Some([0, 0])));
+        lineage.insert(entry(4, 1, Some([0, 1])));
+        lineage.insert(entry(5, 1, Some([1, 2])));
+        lineage.insert(entry(6, 2, Some([3, 4])));
+        lineage.insert(entry(7, 2, Some([5, 5])));
+
+        lineage
+    }
+
+    #[test]
+    fn ancestors() {
+        let lineage = lineage();
+
+        assert_eq!(lineage.ancestors(0), Vec::<u64>::new());
+        assert_eq!(lineage.ancestors(3), vec![0]);
+        assert_eq!(lineage.ancestors(6), vec![0, 1, 3, 4]);
+        assert_eq!(lineage.ancestors(7), vec![1, 2, 5]);
+    }
+
+    #[test]
+    fn descendants() {
+        let lineage = lineage();
+
+        assert_eq!(lineage.descendants(0), vec![3, 4, 6]);
+        assert_eq!(lineage.descendants(1), vec![4, 5, 6, 7]);
+        assert_eq!(lineage.descendants(2), vec![5, 7]);
+        assert_eq!(lineage.descendants(7), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn most_recent_common_ancestor() {
+        let lineage = lineage();
+
+        assert_eq!(lineage.most_recent_common_ancestor(6, 7), Some(1));
+        assert_eq!(lineage.most_recent_common_ancestor(3, 6), Some(3));
+        assert_eq!(lineage.most_recent_common_ancestor(6, 6), Some(6));
+        assert_eq!(lineage.most_recent_common_ancestor(0, 2), None);
+    }
+
+    #[test]
+    fn to_dot() {
+        let mut lineage = Lineage::new();
+
+        lineage.insert(entry(0, 0, None));
+        lineage.insert(entry(1, 1, Some([0, 0])));
+        lineage.entries.get_mut(&0).unwrap().fitness = Some(3.0);
+
+        assert_eq!(
+            lineage.to_dot(),
+            "digraph lineage {\n    \
+                 0 [label=\"#0\\ngeneration 0\\nfitness 3\\n1 mutated\"];\n    \
+                 1 [label=\"#1\\ngeneration 1\\n1 mutated\"];\n    \
+                 0 -> 1 [label=\"1\"];\n    \
+                 0 -> 1 [label=\"2\"];\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn to_json() {
+        let mut lineage = Lineage::new();
+
+        lineage.insert(entry(0, 0, None));
+        lineage.insert(entry(1, 1, Some([0, 0])));
+
+        assert_eq!(
+            lineage.to_json(),
+            "[\
+                {\"id\":0,\"generation\":0,\"parents\":null,\"inherited\":[1,2],\"mutated\":[0],\"fitness\":null},\
+                {\"id\":1,\"generation\":1,\"parents\":[0,0],\"inherited\":[1,2],\"mutated\":[0],\"fitness\":null}\
+             ]"
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use crate::{
+    AnimalIndividual, CrossoverConfig, MutationConfig, OptimizerConfig, SelectionConfig,
+    TunableMutationConfig,
+};
+use lib_genetic_algorithm as ga;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+/// All the optimizers `Simulation` can be configured with.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum Optimizer {
+    GeneticAlgorithm(ga::GeneticAlgorithm<Selection, Crossover, Mutation>),
+    CmaEs(Box<ga::CmaEs>),
+    DifferentialEvolution(ga::DifferentialEvolution),
+    ParticleSwarm(ga::ParticleSwarm),
+}
+
+impl Optimizer {
+    /// Creates optimizer for birds with brains of given shape (see
+    /// `Config::neuron_sizes()`).
+    pub(crate) fn try_new(
+        config: &OptimizerConfig,
+        neuron_sizes: &[usize],
+    ) -> Result<Self, ga::Error> {
+        Ok(match *config {
+            OptimizerConfig::GeneticAlgorithm {
+                selection,
+                crossover,
+                ref mutation,
+            } => Self::GeneticAlgorithm(ga::GeneticAlgorithm::new(
+                Selection::try_new(selection)?,
+                Crossover::try_new(crossover, neuron_sizes)?,
+                Mutation::try_new(mutation)?,
+            )),
+
+            OptimizerConfig::CmaEs { sigma } => Self::CmaEs(Box::new(ga::CmaEs::try_new(sigma)?)),
+            OptimizerConfig::SepCmaEs { sigma } => {
+                Self::CmaEs(Box::new(ga::CmaEs::try_separable(sigma)?))
+            }
+
+            OptimizerConfig::DifferentialEvolution { strategy, f, cr } => {
+                Self::DifferentialEvolution(ga::DifferentialEvolution::try_new(strategy, f, cr)?)
+            }
+
+            OptimizerConfig::ParticleSwarm {
+                inertia,
+                cognitive,
+                social,
+                max_velocity,
+            } => Self::ParticleSwarm(ga::ParticleSwarm::try_new(
+                inertia,
+                cognitive,
+                social,
+                max_velocity,
+            )?),
+        })
+    }
+}
+
+impl ga::Optimizer<AnimalIndividual> for Optimizer {
+    fn evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[AnimalIndividual],
+    ) -> (Vec<AnimalIndividual>, ga::Statistics) {
+        match self {
+            Self::GeneticAlgorithm(optimizer) => ga::Optimizer::evolve(optimizer, rng, population),
+            Self::CmaEs(optimizer) => optimizer.evolve(rng, population),
+            Self::DifferentialEvolution(optimizer) => optimizer.evolve(rng, population),
+            Self::ParticleSwarm(optimizer) => optimizer.evolve(rng, population),
+        }
+    }
+
+    fn evolve_traced(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[AnimalIndividual],
+    ) -> (Vec<AnimalIndividual>, Vec<ga::Birth>, ga::Statistics) {
+        match self {
+            Self::GeneticAlgorithm(optimizer) => {
+                ga::Optimizer::evolve_traced(optimizer, rng, population)
+            }
+            Self::CmaEs(optimizer) => optimizer.evolve_traced(rng, population),
+            Self::DifferentialEvolution(optimizer) => optimizer.evolve_traced(rng, population),
+            Self::ParticleSwarm(optimizer) => optimizer.evolve_traced(rng, population),
+        }
+    }
+
+    fn try_evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[AnimalIndividual],
+    ) -> Result<(Vec<AnimalIndividual>, ga::Statistics), ga::Error> {
+        match self {
+            Self::GeneticAlgorithm(optimizer) => {
+                ga::Optimizer::try_evolve(optimizer, rng, population)
+            }
+            Self::CmaEs(optimizer) => optimizer.try_evolve(rng, population),
+            Self::DifferentialEvolution(optimizer) => optimizer.try_evolve(rng, population),
+            Self::ParticleSwarm(optimizer) => optimizer.try_evolve(rng, population),
+        }
+    }
+
+    fn try_evolve_traced(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[AnimalIndividual],
+    ) -> Result<(Vec<AnimalIndividual>, Vec<ga::Birth>, ga::Statistics), ga::Error> {
+        match self {
+            Self::GeneticAlgorithm(optimizer) => {
+                ga::Optimizer::try_evolve_traced(optimizer, rng, population)
+            }
+            Self::CmaEs(optimizer) => optimizer.try_evolve_traced(rng, population),
+            Self::DifferentialEvolution(optimizer) => optimizer.try_evolve_traced(rng, population),
+            Self::ParticleSwarm(optimizer) => optimizer.try_evolve_traced(rng, population),
+        }
+    }
+}
+
+/// All the selection methods `OptimizerConfig::GeneticAlgorithm` can be
+/// configured with.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum Selection {
+    RouletteWheel(ga::RouletteWheelSelection),
+    Tournament(ga::TournamentSelection),
+}
+
+impl Selection {
+    fn try_new(config: SelectionConfig) -> Result<Self, ga::Error> {
+        Ok(match config {
+            SelectionConfig::RouletteWheel => Self::RouletteWheel(ga::RouletteWheelSelection),
+            SelectionConfig::Tournament { size } => {
+                Self::Tournament(ga::TournamentSelection::try_new(size)?)
+            }
+        })
+    }
+}
+
+impl ga::SelectionMethod for Selection {
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: ga::Individual<G>,
+    {
+        match self {
+            Self::RouletteWheel(method) => method.select(rng, population),
+            Self::Tournament(method) => method.select(rng, population),
+        }
+    }
+
+    fn try_select<'a, I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+    ) -> Result<&'a I, ga::Error>
+    where
+        I: ga::Individual<G>,
+    {
+        match self {
+            Self::RouletteWheel(method) => method.try_select(rng, population),
+            Self::Tournament(method) => method.try_select(rng, population),
+        }
+    }
+}
+
+/// All the crossover methods `OptimizerConfig::GeneticAlgorithm` can be
+/// configured with.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum Crossover {
+    Uniform(ga::UniformCrossover),
+    SinglePoint(ga::SinglePointCrossover),
+    KPoint(ga::KPointCrossover),
+    Blend(ga::BlendCrossover),
+    SimulatedBinary(ga::SimulatedBinaryCrossover),
+    Neuron(ga::NeuronCrossover),
+}
+
+impl Crossover {
+    fn try_new(config: CrossoverConfig, neuron_sizes: &[usize]) -> Result<Self, ga::Error> {
+        Ok(match config {
+            CrossoverConfig::Uniform => Self::Uniform(ga::UniformCrossover),
+            CrossoverConfig::SinglePoint => Self::SinglePoint(ga::SinglePointCrossover),
+            CrossoverConfig::KPoint { k } => Self::KPoint(ga::KPointCrossover::try_new(k)?),
+            CrossoverConfig::Blend { alpha } => Self::Blend(ga::BlendCrossover::try_new(alpha)?),
+            CrossoverConfig::SimulatedBinary { eta } => {
+                Self::SimulatedBinary(ga::SimulatedBinaryCrossover::try_new(eta)?)
+            }
+            CrossoverConfig::Neuron => {
+                Self::Neuron(ga::NeuronCrossover::try_new(neuron_sizes.to_vec())?)
+            }
+        })
+    }
+}
+
+impl ga::CrossoverMethod for Crossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &ga::Chromosome,
+        parent_b: &ga::Chromosome,
+    ) -> ga::Chromosome {
+        match self {
+            Self::Uniform(method) => method.crossover(rng, parent_a, parent_b),
+            Self::SinglePoint(method) => method.crossover(rng, parent_a, parent_b),
+            Self::KPoint(method) => method.crossover(rng, parent_a, parent_b),
+            Self::Blend(method) => method.crossover(rng, parent_a, parent_b),
+            Self::SimulatedBinary(method) => method.crossover(rng, parent_a, parent_b),
+            Self::Neuron(method) => method.crossover(rng, parent_a, parent_b),
+        }
+    }
+}
+
+/// All the mutation methods `OptimizerConfig::GeneticAlgorithm` can be
+/// configured with.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum Mutation {
+    UniformPerturbation(ga::UniformPerturbationMutation),
+    Normal(ga::NormalMutation),
+    Cauchy(ga::CauchyMutation),
+    SelfAdaptive(ga::SelfAdaptiveMutation),
+    Scheduled(ga::ScheduledMutation<TunableMutation>),
+    Adaptive(ga::AdaptiveMutation<Mutation>),
+}
+
+impl Mutation {
+    fn try_new(config: &MutationConfig) -> Result<Self, ga::Error> {
+        Ok(match *config {
+            MutationConfig::UniformPerturbation { chance, coeff } => {
+                Self::UniformPerturbation(ga::UniformPerturbationMutation::try_new(chance, coeff)?)
+            }
+            MutationConfig::Normal { chance, sigma } => {
+                Self::Normal(ga::NormalMutation::try_new(chance, sigma)?)
+            }
+            MutationConfig::Cauchy { chance, scale } => {
+                Self::Cauchy(ga::CauchyMutation::try_new(chance, scale)?)
+            }
+            MutationConfig::SelfAdaptive {
+                initial_sigma,
+                min_sigma,
+            } => Self::SelfAdaptive(ga::SelfAdaptiveMutation::try_new(initial_sigma, min_sigma)?),
+            MutationConfig::Scheduled { mutation, schedule } => Self::Scheduled(
+                ga::ScheduledMutation::try_new(TunableMutation::try_new(mutation)?, schedule)?,
+            ),
+            MutationConfig::Adaptive {
+                ref mutations,
+                exploration,
+            } => Self::Adaptive(ga::AdaptiveMutation::try_new(
+                mutations
+                    .iter()
+                    .map(Self::try_new)
+                    .collect::<Result<_, _>>()?,
+                exploration,
+            )?),
+        })
+    }
+}
+
+impl ga::MutationMethod for Mutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut ga::Chromosome) {
+        match self {
+            Self::UniformPerturbation(method) => method.mutate(rng, child),
+            Self::Normal(method) => method.mutate(rng, child),
+            Self::Cauchy(method) => method.mutate(rng, child),
+            Self::SelfAdaptive(method) => method.mutate(rng, child),
+            Self::Scheduled(method) => method.mutate(rng, child),
+            Self::Adaptive(method) => method.mutate(rng, child),
+        }
+    }
+
+    fn adapt(&mut self, stats: &ga::Statistics) {
+        match self {
+            Self::UniformPerturbation(method) => method.adapt(stats),
+            Self::Normal(method) => method.adapt(stats),
+            Self::Cauchy(method) => method.adapt(stats),
+            Self::SelfAdaptive(method) => method.adapt(stats),
+            Self::Scheduled(method) => method.adapt(stats),
+            Self::Adaptive(method) => method.adapt(stats),
+        }
+    }
+}
+
+/// All the mutation methods `MutationConfig::Scheduled` can tune.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum TunableMutation {
+    UniformPerturbation(ga::UniformPerturbationMutation),
+    Normal(ga::NormalMutation),
+    Cauchy(ga::CauchyMutation),
+}
+
+impl TunableMutation {
+    fn try_new(config: TunableMutationConfig) -> Result<Self, ga::Error> {
+        Ok(match config {
+            TunableMutationConfig::UniformPerturbation { chance, coeff } => {
+                Self::UniformPerturbation(ga::UniformPerturbationMutation::try_new(chance, coeff)?)
+            }
+            TunableMutationConfig::Normal { chance, sigma } => {
+                Self::Normal(ga::NormalMutation::try_new(chance, sigma)?)
+            }
+            TunableMutationConfig::Cauchy { chance, scale } => {
+                Self::Cauchy(ga::CauchyMutation::try_new(chance, scale)?)
+            }
+        })
+    }
+}
+
+impl ga::MutationMethod for TunableMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut ga::Chromosome) {
+        match self {
+            Self::UniformPerturbation(method) => method.mutate(rng, child),
+            Self::Normal(method) => method.mutate(rng, child),
+            Self::Cauchy(method) => method.mutate(rng, child),
+        }
+    }
+}
+
+impl ga::TunableMutation for TunableMutation {
+    fn parameters(&self) -> (f32, f32) {
+        match self {
+            Self::UniformPerturbation(method) => method.parameters(),
+            Self::Normal(method) => method.parameters(),
+            Self::Cauchy(method) => method.parameters(),
+        }
+    }
+
+    fn parameters_mut(&mut self) -> (&mut f32, &mut f32) {
+        match self {
+            Self::UniformPerturbation(method) => method.parameters_mut(),
+            Self::Normal(method) => method.parameters_mut(),
+            Self::Cauchy(method) => method.parameters_mut(),
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{Frame, Recording, Simulation};
+use rand_chacha::ChaCha8Rng;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// Re-simulates a `Recording`, frame by frame.
+///
+/// Player can be moved to any step of the recording - to make going back
+/// cheap, it remembers a checkpoint of every generation it has passed
+/// through, so that it never has to re-simulate more than a single
+/// generation.
+pub struct Player {
+    recording: Recording,
+    simulation: Simulation,
+    rng: ChaCha8Rng,
+    tick: usize,
+
+    /// Checkpoints taken at the beginning of generations, by tick.
+    keyframes: BTreeMap<usize, Vec<u8>>,
+}
+
+impl Player {
+    pub fn new(recording: Recording) -> Self {
+        let (simulation, rng) = recording.start();
+        let keyframes = BTreeMap::from([(0, simulation.save_checkpoint(&rng))]);
+
+        Self {
+            recording,
+            simulation,
+            rng,
+            tick: 0,
+            keyframes,
+        }
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    pub fn simulation(&self) -> &Simulation {
+        &self.simulation
+    }
+
+    /// Number of steps simulated so far; frames range from 0 (the world
+    /// before the first step) up to `recording().ticks()`.
+    pub fn tick(&self) -> usize {
+        self.tick
+    }
+
+    pub fn frame(&self) -> Frame {
+        Frame::capture(self.tick, &self.simulation)
+    }
+
+    /// Simulates the next step; returns `None` once the recording is over.
+    pub fn step(&mut self) -> Option<Frame> {
+        if self.tick == self.recording.ticks() {
+            return None;
+        }
+
+        self.advance();
+
+        Some(self.frame())
+    }
+
+    /// Moves to given step, going back to the nearest checkpoint if
+    /// needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tick` lies past the end of the recording.
+    pub fn seek(&mut self, tick: usize) {
+        assert!(
+            tick <= self.recording.ticks(),
+            "tick {} lies past the end of the recording ({} ticks)",
+            tick,
+            self.recording.ticks()
+        );
+
+        // Going forward, a checkpoint is worth restoring only if it lies
+        // ahead of us
+        let from = if tick < self.tick { 0 } else { self.tick + 1 };
+
+        let keyframe = (from <= tick)
+            .then(|| self.keyframes.range(from..=tick).next_back())
+            .flatten();
+
+        if let Some((&keyframe_tick, keyframe)) = keyframe {
+            (self.simulation, self.rng) =
+                Simulation::load_checkpoint(keyframe).expect("keyframes are always valid");
+
+            self.tick = keyframe_tick;
+        }
+
+        while self.tick < tick {
+            self.advance();
+        }
+    }
+
+    /// Moves to the first step of given generation; returns that step, or
+    /// `None` if the recording ends before the generation begins.
+    pub fn seek_generation(&mut self, generation: usize) -> Option<usize> {
+        // Each generation lives for `generation_length` steps and then
+        // gets evolved during one more
+        let tick = generation * (self.recording.config().generation_length + 1);
+
+        if tick > self.recording.ticks() {
+            return None;
+        }
+
+        self.seek(tick);
+
+        Some(tick)
+    }
+
+    /// Frames of given range of steps.
+    pub fn frames(&mut self, ticks: Range<usize>) -> Vec<Frame> {
+        if ticks.is_empty() {
+            return Vec::new();
+        }
+
+        self.seek(ticks.start);
+
+        let mut frames = vec![self.frame()];
+
+        while self.tick + 1 < ticks.end {
+            frames.push(
+                self.step()
+                    .expect("range lies past the end of the recording"),
+            );
+        }
+
+        frames
+    }
+
+    /// Frames of given range of steps, encoded for `Frame::import()`.
+    pub fn export(&mut self, ticks: Range<usize>) -> Vec<u8> {
+        Frame::export(&self.frames(ticks))
+    }
+
+    fn advance(&mut self) {
+        self.simulation.step(&mut self.rng);
+        self.tick += 1;
+
+        if self.simulation.age() == 0 && !self.keyframes.contains_key(&self.tick) {
+            let keyframe = self.simulation.save_checkpoint(&self.rng);
+            self.keyframes.insert(self.tick, keyframe);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::GENERATION_LENGTH;
+    use crate::{Config, Recorder};
+
+    #[test]
+    fn replays_recorded_runs() {
+        let mut recorder = Recorder::new(Default::default(), Config::default());
+        let mut expected = BTreeMap::new();
+
+        for tick in 1..=GENERATION_LENGTH + 100 {
+            recorder.step();
+
+            if tick % 500 == 0 || tick > GENERATION_LENGTH {
+                expected.insert(tick, Frame::capture(tick, recorder.simulation()));
+            }
+        }
+
+        let recording = Recording::from_bytes(&recorder.finish().to_bytes()).unwrap();
+        let mut player = Player::new(recording);
+
+        // Forwards ...
+        for (&tick, frame) in &expected {
+            player.seek(tick);
+            assert_eq!(&player.frame(), frame);
+        }
+
+        // ... and backwards
+        for (&tick, frame) in expected.iter().rev() {
+            player.seek(tick);
+            assert_eq!(&player.frame(), frame);
+        }
+
+        assert_eq!(player.seek_generation(1), Some(GENERATION_LENGTH + 1));
+        assert_eq!(player.frame().generation, 1);
+        assert_eq!(player.frame().age, 0);
+        assert_eq!(player.seek_generation(2), None);
+
+        player.seek(GENERATION_LENGTH + 100);
+        assert_eq!(player.step(), None);
+    }
+
+    #[test]
+    fn exports_frames() {
+        let mut player = Player::new(Recording::new(Default::default(), Config::default(), 50));
+
+        let frames = Frame::import(&player.export(10..20)).unwrap();
+
+        assert_eq!(
+            frames.iter().map(|frame| frame.tick).collect::<Vec<_>>(),
+            (10..20).collect::<Vec<_>>()
+        );
+
+        assert_eq!(frames[0], {
+            player.seek(10);
+            player.frame()
+        });
+    }
+
+    /// Golden trajectory - if this test fails, the simulation doesn't
+    /// behave the way it used to anymore and all the existing recordings
+    /// have become worthless.
+    #[test]
+    fn golden_trajectory() {
+        let ticks = GENERATION_LENGTH + 101;
+        let mut player = Player::new(Recording::new(Default::default(), Config::default(), ticks));
+
+        // First steps of both the initial and the evolved generation
+        let trajectory: Vec<_> = [0..101, GENERATION_LENGTH + 1..ticks + 1]
+            .into_iter()
+            .flat_map(|range| player.frames(range).into_iter().step_by(25))
+            .map(|frame| {
+                let bird = &frame.birds[0];
+                (
+                    frame.generation,
+                    bird.position.x,
+                    bird.position.y,
+                    bird.satiation,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            trajectory,
+            vec![
+                (0, 0.043615103, 0.2027024, 0),
+                (0, 0.03291143, 0.19130287, 0),
+                (0, 0.033961263, 0.19388837, 1),
+                (0, 0.018662333, 0.1978112, 1),
+                (0, 0.005429987, 0.19184624, 1),
+                (1, 0.21958601, 0.9944865, 0),
+                (1, 0.22013515, 0.99256337, 0),
+                (1, 0.2191636, 0.9908152, 0),
+                (1, 0.21724047, 0.9902661, 0),
+                (1, 0.21549231, 0.99123764, 0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::checkpoint::{self, CheckpointError};
+use crate::{Config, Simulation};
+use lib_genetic_algorithm as ga;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+/// Marks the beginning of every recording.
+const MAGIC: &[u8; 4] = b"SLRC";
+
+/// Version of the recording format (see `checkpoint::VERSION`).
+const VERSION: u32 = 1;
+
+/// Compact log of a simulation run - since the simulation is deterministic,
+/// the seed, the config and the number of steps taken are enough to
+/// re-simulate every single frame of it (see `Player`).
+///
+/// Recordings stay valid only as long as the simulation itself doesn't
+/// change - golden recordings are what catches such changes.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Recording {
+    seed: [u8; 32],
+    config: Config,
+
+    /// Number of steps taken.
+    ticks: usize,
+}
+
+impl Recording {
+    pub fn new(seed: [u8; 32], config: Config, ticks: usize) -> Self {
+        Self {
+            seed,
+            config,
+            ticks,
+        }
+    }
+
+    pub fn seed(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn ticks(&self) -> usize {
+        self.ticks
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        checkpoint::encode(MAGIC, VERSION, self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CheckpointError> {
+        checkpoint::decode(MAGIC, VERSION, bytes)
+    }
+
+    /// Creates simulation (and its random number generator) the way it
+    /// was at the beginning of the run.
+    pub(crate) fn start(&self) -> (Simulation, ChaCha8Rng) {
+        let mut rng = ChaCha8Rng::from_seed(self.seed);
+        let sim = Simulation::random_with_config(&mut rng, self.config.clone());
+
+        (sim, rng)
+    }
+}
+
+/// Drives a simulation while recording it.
+pub struct Recorder {
+    recording: Recording,
+    simulation: Simulation,
+    rng: ChaCha8Rng,
+}
+
+impl Recorder {
+    pub fn new(seed: [u8; 32], config: Config) -> Self {
+        let recording = Recording::new(seed, config, 0);
+        let (simulation, rng) = recording.start();
+
+        Self {
+            recording,
+            simulation,
+            rng,
+        }
+    }
+
+    pub fn simulation(&self) -> &Simulation {
+        &self.simulation
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    pub fn step(&mut self) -> Option<ga::Statistics> {
+        self.recording.ticks += 1;
+        self.simulation.step(&mut self.rng)
+    }
+
+    pub fn train(&mut self) -> ga::Statistics {
+        loop {
+            if let Some(summary) = self.step() {
+                return summary;
+            }
+        }
+    }
+
+    pub fn finish(self) -> Recording {
+        self.recording
+    }
+}
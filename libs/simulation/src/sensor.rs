@@ -0,0 +1,175 @@
+use crate::{Animal, Config, SensorConfig};
+use std::f32::consts::PI;
+
+/// Something a bird can feel besides what it sees.
+///
+/// Sensors' readings get fed into bird's brain right after the eye's
+/// cells, so each sensor makes the brain (and its chromosome) a bit
+/// larger - see `Config::sensors`.
+pub trait Sensor {
+    /// Number of readings (i.e. brain's inputs) this sensor provides.
+    fn size(&self) -> usize;
+
+    /// Appends sensor's readings, scaled roughly into `-1.0..=1.0`, to
+    /// `inputs`.
+    fn sense(&self, animal: &Animal, config: &Config, inputs: &mut Vec<f32>);
+}
+
+/// Bird's current speed, relative to `Config::speed_max`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SpeedSensor;
+
+impl Sensor for SpeedSensor {
+    fn size(&self) -> usize {
+        1
+    }
+
+    fn sense(&self, animal: &Animal, config: &Config, inputs: &mut Vec<f32>) {
+        inputs.push(animal.speed / config.speed_max);
+    }
+}
+
+/// Bird's heading, as its sine & cosine (so that there's no jump between
+/// `2 * PI` and zero).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CompassSensor;
+
+impl Sensor for CompassSensor {
+    fn size(&self) -> usize {
+        2
+    }
+
+    fn sense(&self, animal: &Animal, _: &Config, inputs: &mut Vec<f32>) {
+        let angle = animal.rotation.angle();
+
+        inputs.push(angle.sin());
+        inputs.push(angle.cos());
+    }
+}
+
+/// Time since bird's last meal: zero right after eating, growing up to one
+/// after a whole generation of starving.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HungerSensor;
+
+impl Sensor for HungerSensor {
+    fn size(&self) -> usize {
+        1
+    }
+
+    fn sense(&self, animal: &Animal, config: &Config, inputs: &mut Vec<f32>) {
+        let hunger = animal.hunger as f32 / config.generation_length.max(1) as f32;
+
+        inputs.push(hunger.min(1.0));
+    }
+}
+
+/// Distance to the nearest edge of the map: zero at the edge, one in the
+/// middle of the map.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WallSensor;
+
+impl Sensor for WallSensor {
+    fn size(&self) -> usize {
+        1
+    }
+
+    fn sense(&self, animal: &Animal, _: &Config, inputs: &mut Vec<f32>) {
+        let position = animal.position;
+
+        let distance = position
+            .x
+            .min(1.0 - position.x)
+            .min(position.y)
+            .min(1.0 - position.y);
+
+        inputs.push(2.0 * distance);
+    }
+}
+
+/// Oscillator ticking with bird's age, completing a full cycle every
+/// `period` steps - lets brains do things periodically.
+#[derive(Clone, Copy, Debug)]
+pub struct ClockSensor {
+    pub period: usize,
+}
+
+impl Sensor for ClockSensor {
+    fn size(&self) -> usize {
+        1
+    }
+
+    fn sense(&self, animal: &Animal, _: &Config, inputs: &mut Vec<f32>) {
+        let phase = (animal.age % self.period) as f32 / self.period as f32;
+
+        inputs.push((2.0 * PI * phase).sin());
+    }
+}
+
+impl Sensor for SensorConfig {
+    fn size(&self) -> usize {
+        match *self {
+            Self::Speed => SpeedSensor.size(),
+            Self::Compass => CompassSensor.size(),
+            Self::Hunger => HungerSensor.size(),
+            Self::Wall => WallSensor.size(),
+            Self::Clock { period } => ClockSensor { period }.size(),
+        }
+    }
+
+    fn sense(&self, animal: &Animal, config: &Config, inputs: &mut Vec<f32>) {
+        match *self {
+            Self::Speed => SpeedSensor.sense(animal, config, inputs),
+            Self::Compass => CompassSensor.sense(animal, config, inputs),
+            Self::Hunger => HungerSensor.sense(animal, config, inputs),
+            Self::Wall => WallSensor.sense(animal, config, inputs),
+            Self::Clock { period } => ClockSensor { period }.sense(animal, config, inputs),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Point, Rotation};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn sense(sensor: SensorConfig, animal: &Animal) -> Vec<f32> {
+        let mut inputs = Vec::new();
+        sensor.sense(animal, &Config::default(), &mut inputs);
+
+        assert_eq!(inputs.len(), sensor.size());
+
+        inputs
+    }
+
+    #[test]
+    fn readings() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut animal = Animal::random(&mut rng);
+
+        animal.position = Point::new(0.2, 0.7);
+        animal.rotation = Rotation::new(FRAC_PI_2);
+        animal.speed = 0.001;
+        animal.age = 75;
+        animal.hunger = 1250;
+
+        assert_eq!(sense(SensorConfig::Speed, &animal), vec![0.5]);
+        assert_eq!(sense(SensorConfig::Hunger, &animal), vec![0.5]);
+
+        let wall = sense(SensorConfig::Wall, &animal);
+        assert!((wall[0] - 0.4).abs() < 1e-6);
+
+        let compass = sense(SensorConfig::Compass, &animal);
+        assert!((compass[0] - 1.0).abs() < 1e-6);
+        assert!(compass[1].abs() < 1e-6);
+
+        let clock = sense(SensorConfig::Clock { period: 100 }, &animal);
+        assert!((clock[0] + 1.0).abs() < 1e-6);
+
+        animal.hunger = 1_000_000;
+        assert_eq!(sense(SensorConfig::Hunger, &animal), vec![1.0]);
+    }
+}
@@ -1,21 +1,29 @@
-use crate::{AnimalIndividual, World};
-use lib_genetic_algorithm as ga;
+use crate::checkpoint;
+use crate::optimizer::Optimizer;
+use crate::{
+    Animal, AnimalIndividual, Brain, Champion, CheckpointError, Config, Controller, Error, Eye,
+    Food, Lineage, SensorConfig, World,
+};
+use lib_genetic_algorithm::{self as ga, Optimizer as _};
+use lib_neural_network as nn;
 use nalgebra as na;
 use rand::{Rng, RngCore};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
 
 // FRAC_PI_2 = PI / 2.0; a convenient shortcut
 use std::f32::consts::FRAC_PI_4;
 
-/// Minimum speed of a bird.
+/// Default minimum speed of a bird (see `Config::speed_min`).
 ///
 /// Keeping it above zero prevents birds from getting stuck in one place.
-const SPEED_MIN: f32 = 0.0001;
+pub(crate) const SPEED_MIN: f32 = 0.0001;
 
-/// Maximum speed of a bird.
+/// Default maximum speed of a bird (see `Config::speed_max`).
 ///
 /// Keeping it "sane" prevents birds from accelerating up to infinity,
 /// which makes the simulation... unrealistic :-)
-const SPEED_MAX: f32 = 0.002;
+pub(crate) const SPEED_MAX: f32 = 0.002;
 
 /// Speed acceleration; determines how much the brain can affect bird's
 /// speed during one step.
@@ -31,7 +39,7 @@ const SPEED_MAX: f32 = 0.002;
 /// This improves simulation faithfulness, because - as in real life -
 /// it's not possible to increase speed from 1km/h to 50km/h in one
 /// instant, even if your brain very much wants to.
-const SPEED_ACCEL: f32 = 0.02;
+pub(crate) const SPEED_ACCEL: f32 = 0.02;
 
 /// Ditto, but for rotation:
 ///
@@ -40,9 +48,9 @@ const SPEED_ACCEL: f32 = 0.02;
 ///
 /// I've chosen PI/2, because - as our motto goes - this value seems
 /// to play nice.
-const ROTATION_ACCEL: f32 = FRAC_PI_4;
+pub(crate) const ROTATION_ACCEL: f32 = FRAC_PI_4;
 
-/// How much `.step()`-s have to occur before we push data into the
+/// Default number of `.step()`-s that have to occur before we push data into the
 /// genetic algorithm.
 ///
 /// Value that's too low might prevent the birds from learning, while
@@ -51,137 +59,895 @@ const ROTATION_ACCEL: f32 = FRAC_PI_4;
 ///
 /// You can treat this number as "for how many steps each bird gets
 /// to live"; 2500 was chosen with a fair dice roll.
-const GENERATION_LENGTH: usize = 2500;
+pub(crate) const GENERATION_LENGTH: usize = 2500;
 
-#[derive(Debug)]
+/// How many of the best birds ever seen are remembered.
+const HALL_OF_FAME_SIZE: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Simulation {
     world: World,
-    ga: ga::GeneticAlgorithm<
-        ga::RouletteWheelSelection,
-        ga::UniformCrossover,
-        ga::GaussianMutation,
-    >,
+    optimizer: Optimizer,
     age: usize,
+
+    /// Number of generations evolved so far.
+    generation: usize,
+
+    /// Statistics of every generation evolved so far.
+    history: Vec<ga::Statistics>,
+
+    hall_of_fame: ga::HallOfFame,
+
+    /// Family tree of the birds, if it's being tracked (see
+    /// `track_lineage()`); it grows by one entry per bird per generation,
+    /// so it's kept out of checkpoints.
+    #[serde(skip)]
+    lineage: Option<Lineage>,
+
+    config: Config,
+
+    /// Config waiting for the current generation to end (see
+    /// `reconfigure()`).
+    pending_config: Option<Config>,
 }
 impl Simulation {
     pub fn random(rng: &mut dyn RngCore) -> Self {
-        let world = World::random(rng);
+        Self::random_with_config(rng, Config::default())
+    }
+
+    /// Panics if the config is invalid (see `try_random_with_config()`).
+    pub fn random_with_config(rng: &mut dyn RngCore, config: Config) -> Self {
+        Self::try_random_with_config(rng, config).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_random_with_config(rng: &mut dyn RngCore, config: Config) -> Result<Self, Error> {
+        config.validate()?;
 
-        let ga = ga::GeneticAlgorithm::new(
-            ga::RouletteWheelSelection,
-            ga::UniformCrossover,
-            ga::GaussianMutation::new(0.01, 0.2),
+        let world = World::random_with_senses(
+            rng,
+            config.animals,
+            config.foods,
+            &config.eye,
+            &config.sensors,
         );
 
-        Self { world, ga, age: 0 }
+        Self::try_with_world(world, config)
+    }
+
+    /// Starts simulation in given world - e.g. one seeded with a champion
+    /// (see `World::seeded()`).
+    pub fn with_world(world: World, config: Config) -> Self {
+        Self::try_with_world(world, config).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_with_world(world: World, config: Config) -> Result<Self, Error> {
+        config.validate()?;
+
+        Ok(Self {
+            world,
+            optimizer: Optimizer::try_new(&config.optimizer, &config.neuron_sizes())?,
+            age: 0,
+            generation: 0,
+            history: Vec::new(),
+            hall_of_fame: ga::HallOfFame::new(HALL_OF_FAME_SIZE),
+            lineage: None,
+            config,
+            pending_config: None,
+        })
     }
 
     pub fn world(&self) -> &World {
         &self.world
     }
 
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Number of steps the current generation has lived so far.
+    pub fn age(&self) -> usize {
+        self.age
+    }
+
+    /// Number of steps each generation lives for.
+    pub fn generation_length(&self) -> usize {
+        self.config.generation_length
+    }
+
+    /// Config the current generation lives by.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Config that will take effect once the current generation ends - or
+    /// the current one, if nothing's been changed.
+    pub fn next_config(&self) -> &Config {
+        self.pending_config.as_ref().unwrap_or(&self.config)
+    }
+
+    /// Schedules given config to take effect once the current generation
+    /// ends, so that the birds can finish their lives by the rules they
+    /// were born with.
+    ///
+    /// Birds' number, eyes and sensors stay as they are - brains are
+    /// shaped after eyes & sensors, and optimizers expect populations of
+    /// stable size - so `config.animals`, `config.eye` and `config.sensors`
+    /// are ignored.
+    pub fn reconfigure(&mut self, config: Config) {
+        self.try_reconfigure(config)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_reconfigure(&mut self, config: Config) -> Result<(), Error> {
+        let config = Config {
+            animals: self.config.animals,
+            eye: self.config.eye.clone(),
+            sensors: self.config.sensors.clone(),
+            ..config
+        };
+
+        config.validate()?;
+        self.pending_config = Some(config);
+
+        Ok(())
+    }
+
+    pub fn history(&self) -> &[ga::Statistics] {
+        &self.history
+    }
+
+    /// The best birds ever seen, along with the generations they've lived
+    /// in.
+    pub fn hall_of_fame(&self) -> &ga::HallOfFame {
+        &self.hall_of_fame
+    }
+
+    /// Starts tracking the family tree, beginning with birds of the
+    /// current generation (which become its founders); does nothing if the
+    /// tree is being tracked already.
+    ///
+    /// Lineage isn't saved in checkpoints, so a restored simulation has to
+    /// start tracking anew.
+    pub fn track_lineage(&mut self) {
+        self.lineage.get_or_insert_with(|| {
+            let mut lineage = Lineage::new();
+            lineage.add_founders(self.generation, &self.world.animals);
+            lineage
+        });
+    }
+
+    /// Family tree of every bird that has lived in this simulation since
+    /// `track_lineage()` was called.
+    pub fn lineage(&self) -> Option<&Lineage> {
+        self.lineage.as_ref()
+    }
+
+    /// The best bird ever seen.
+    pub fn champion(&self) -> Option<Champion> {
+        self.hall_of_fame
+            .best()
+            .map(|entry| Champion::from_entry(entry, &self.config))
+    }
+
+    /// Saves the whole state of this simulation, along with the state of
+    /// the random number generator driving it; continuing from a restored
+    /// checkpoint yields exactly the same results as if the simulation
+    /// hadn't been interrupted at all.
+    pub fn save_checkpoint(&self, rng: &ChaCha8Rng) -> Vec<u8> {
+        checkpoint::save(self, rng)
+    }
+
+    /// Restores simulation & random number generator saved by
+    /// `save_checkpoint()`.
+    pub fn load_checkpoint(bytes: &[u8]) -> Result<(Self, ChaCha8Rng), CheckpointError> {
+        checkpoint::load(bytes)
+    }
+
+    /// Panics if the birds can't be evolved (see `try_step()`).
     pub fn step(&mut self, rng: &mut dyn RngCore) -> Option<ga::Statistics> {
-        self.process_collisions(rng);
-        self.process_brains();
-        self.process_movements();
+        self.try_step(rng).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Advances the simulation by a single step, evolving the birds if
+    /// their generation has just ended.
+    ///
+    /// If the evolution fails (e.g. when no bird has eaten anything, so
+    /// there's no one to pick as a parent by the roulette), birds keep
+    /// living and the evolution is retried during the next step.
+    pub fn try_step(&mut self, rng: &mut dyn RngCore) -> Result<Option<ga::Statistics>, Error> {
+        step_world(&mut self.world, rng, &self.config);
 
         self.age += 1;
-        if self.age > GENERATION_LENGTH {
-            Some(self.evolve(rng))
+        if self.age > self.config.generation_length {
+            self.try_evolve(rng).map(Some)
         } else {
-            None
+            Ok(None)
         }
     }
 
     pub fn train(&mut self, rng: &mut dyn RngCore) -> ga::Statistics {
+        self.try_train(rng).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_train(&mut self, rng: &mut dyn RngCore) -> Result<ga::Statistics, Error> {
         loop {
-            if let Some(summary) = self.step(rng) {
-                return summary;
+            if let Some(summary) = self.try_step(rng)? {
+                return Ok(summary);
             }
         }
     }
 
-    fn process_movements(&mut self) {
-        for animal in &mut self.world.animals {
-            animal.position += animal.rotation * na::Vector2::new(0.0, animal.speed);
-            animal.position.x = na::wrap(animal.position.x, 0.0, 1.0);
-            animal.position.y = na::wrap(animal.position.y, 0.0, 1.0);
+    /// Trains generation after generation until `monitor` says to stop
+    /// (see `ga::Monitor`), notifying its observers after each one;
+    /// returns why the training has stopped.
+    ///
+    /// Without any termination criteria, this trains until cancelled.
+    pub fn train_until(
+        &mut self,
+        rng: &mut dyn RngCore,
+        monitor: &mut ga::Monitor,
+    ) -> ga::StopReason {
+        self.try_train_until(rng, monitor)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_train_until(
+        &mut self,
+        rng: &mut dyn RngCore,
+        monitor: &mut ga::Monitor,
+    ) -> Result<ga::StopReason, Error> {
+        if monitor.is_cancelled() {
+            return Ok(ga::StopReason::Cancelled);
+        }
+
+        monitor.start();
+
+        let mut generation = 0;
+
+        loop {
+            let stats = self.try_train(rng)?;
+            generation += 1;
+
+            if let Some(reason) = monitor.record(generation, &stats) {
+                return Ok(reason);
+            }
         }
     }
 
-    fn process_collisions(&mut self, rng: &mut dyn RngCore) {
-        for animal in &mut self.world.animals {
-            for food in &mut self.world.foods {
-                let distance = na::distance(&animal.position, &food.position);
+    /// Teaches brains of the current generation to imitate given samples
+    /// (see `Brain::pretrain()`), so that the evolution can start from
+    /// birds that already know a thing or two; returns their mean loss.
+    pub fn pretrain(
+        &mut self,
+        rng: &mut dyn RngCore,
+        samples: &[nn::Sample],
+        epochs: usize,
+    ) -> f32 {
+        let loss: f32 = self
+            .world
+            .animals
+            .iter_mut()
+            .map(|animal| animal.brain.pretrain(rng, samples, epochs))
+            .sum();
+
+        loss / self.world.animals.len().max(1) as f32
+    }
 
-                if distance <= 0.01 {
-                    animal.satiation += 1;
-                    food.position = rng.gen();
-                }
+    fn try_evolve(&mut self, rng: &mut dyn RngCore) -> Result<ga::Statistics, Error> {
+        if let Some(config) = self.pending_config.take() {
+            if config.optimizer != self.config.optimizer {
+                self.optimizer = Optimizer::try_new(&config.optimizer, &config.neuron_sizes())
+                    .expect("config has been validated by `try_reconfigure()`");
             }
+
+            self.world
+                .foods
+                .resize_with(config.foods, || Food::random(rng));
+
+            self.config = config;
+        }
+
+        // Transforms `Vec<Animal>` to `Vec<AnimalIndividual>`
+        let current_population = population(&self.world.animals);
+
+        // Evolves this `Vec<AnimalIndividual>`; births are worth tracing
+        // only when someone's interested in the family tree
+        let (evolved_population, births, stats) = if self.lineage.is_some() {
+            let (evolved_population, births, stats) =
+                self.optimizer.try_evolve_traced(rng, &current_population)?;
+
+            (evolved_population, Some(births), stats)
+        } else {
+            let (evolved_population, stats) =
+                self.optimizer.try_evolve(rng, &current_population)?;
+
+            (evolved_population, None, stats)
+        };
+
+        self.age = 0;
+
+        self.hall_of_fame
+            .update(&current_population, self.generation);
+
+        if let Some(lineage) = &mut self.lineage {
+            lineage.set_fitness(&self.world.animals);
+        }
+
+        let parents: Vec<_> = self.world.animals.iter().map(Animal::id).collect();
+
+        // Transforms `Vec<AnimalIndividual>` back into `Vec<Animal>`
+        self.world.populate(animals(
+            evolved_population,
+            &self.config.eye,
+            &self.config.sensors,
+            rng,
+        ));
+
+        if let (Some(lineage), Some(births)) = (&mut self.lineage, births) {
+            lineage.add_children(self.generation + 1, &parents, &self.world.animals, births);
         }
+
+        scatter_foods(&mut self.world, rng);
+
+        self.generation += 1;
+        self.history.push(stats.clone());
+
+        Ok(stats)
     }
+}
+
+/// Advances given world by a single step - that's the whole "physics" of
+/// our simulation, shared by `Simulation` and `IslandSimulation`.
+pub(crate) fn step_world(world: &mut World, rng: &mut dyn RngCore, config: &Config) {
+    process_collisions(world, rng);
+    process_brains(world, config, None);
+    process_movements(world);
+}
 
-    fn process_brains(&mut self) {
-        for animal in &mut self.world.animals {
-            let vision =
-                animal
-                    .eye
-                    .process_vision(animal.position, animal.rotation, &self.world.foods);
-
-            let response = animal.brain.nn.propagate(vision);
-            // ---
-            // | Limits number to given range.
-            // -------------------- v---v
-            let speed = response[0].clamp(-SPEED_ACCEL, SPEED_ACCEL);
-            let rotation = response[1].clamp(-ROTATION_ACCEL, ROTATION_ACCEL);
-
-            // Our speed & rotation here are *relative* - that is: when
-            // they are equal to zero, what the brain says is "keep
-            // flying as you are now", not "stop flying".
-            //
-            // Both values being relative is crucial, because our bird's
-            // brain doesn't know its own speed and rotation*, meaning
-            // that it fundamentally cannot return absolute values.
-            //
-            // * they'd have to be provided as separate inputs to the
-            //   neural network, which would make the evolution process
-            //   waaay longer, if even possible.
-
-            animal.speed = (animal.speed + speed).clamp(SPEED_MIN, SPEED_MAX);
-            animal.rotation = na::Rotation2::new(animal.rotation.angle() + rotation);
-
-            // (btw, there is no need for ROTATION_MIN or ROTATION_MAX,
-            // because rotation automatically wraps from 2*PI back to 0 -
-            // we've already witnessed that when we were testing eyes,
-            // inside `fn rotations { ... }`.)
+/// Same as `step_world()`, but all birds get driven by given controller
+/// instead of their own brains.
+pub(crate) fn step_world_with(
+    world: &mut World,
+    rng: &mut dyn RngCore,
+    config: &Config,
+    controller: &mut dyn Controller,
+) {
+    process_collisions(world, rng);
+    process_brains(world, config, Some(controller));
+    process_movements(world);
+}
+
+pub(crate) fn population(animals: &[Animal]) -> Vec<AnimalIndividual> {
+    animals.iter().map(AnimalIndividual::from_animal).collect()
+}
+
+pub(crate) fn animals(
+    population: Vec<AnimalIndividual>,
+    eye: &Eye,
+    sensors: &[SensorConfig],
+    rng: &mut dyn RngCore,
+) -> Vec<Animal> {
+    population
+        .into_iter()
+        .map(|individual| individual.into_animal_with_senses(eye, sensors, rng))
+        .collect()
+}
+
+pub(crate) fn scatter_foods(world: &mut World, rng: &mut dyn RngCore) {
+    for food in &mut world.foods {
+        food.position = rng.gen();
+    }
+}
+
+fn process_movements(world: &mut World) {
+    for animal in &mut world.animals {
+        animal.position += animal.rotation * na::Vector2::new(0.0, animal.speed);
+        animal.position.x = na::wrap(animal.position.x, 0.0, 1.0);
+        animal.position.y = na::wrap(animal.position.y, 0.0, 1.0);
+    }
+}
+
+fn process_collisions(world: &mut World, rng: &mut dyn RngCore) {
+    for animal in &mut world.animals {
+        animal.age += 1;
+        animal.hunger += 1;
+
+        for food in &mut world.foods {
+            let distance = na::distance(&animal.position, &food.position);
+
+            if distance <= 0.01 {
+                animal.satiation += 1;
+                animal.hunger = 0;
+                food.position = rng.gen();
+            }
         }
     }
+}
 
-    fn evolve(&mut self, rng: &mut dyn RngCore) -> ga::Statistics {
-        self.age = 0;
+fn process_brains(world: &mut World, config: &Config, mut controller: Option<&mut dyn Controller>) {
+    for animal in &mut world.animals {
+        let inputs = animal.perceive(&world.foods, config);
 
-        // Transforms `Vec<Animal>` to `Vec<AnimalIndividual>`
-        let current_population: Vec<_> = self
-            .world
-            .animals
+        animal.activations = match controller.as_deref_mut() {
+            Some(controller) => {
+                let outputs = Brain::outputs(controller.act(&inputs));
+                vec![inputs, outputs]
+            }
+
+            None => animal.brain.nn.propagate_traced(inputs),
+        };
+
+        let [speed, rotation] = Brain::response(animal.activations.last().unwrap());
+        // ---
+        // | Limits number to given range.
+        // -------------- v---v
+        let speed = speed.clamp(-SPEED_ACCEL, SPEED_ACCEL);
+        let rotation = rotation.clamp(-ROTATION_ACCEL, ROTATION_ACCEL);
+
+        // Our speed & rotation here are *relative* - that is: when
+        // they are equal to zero, what the brain says is "keep
+        // flying as you are now", not "stop flying".
+        //
+        // Both values being relative is crucial, because our bird's
+        // brain doesn't know its own speed and rotation*, meaning
+        // that it fundamentally cannot return absolute values.
+        //
+        // * unless it's been given `SensorConfig::Speed` and
+        //   `SensorConfig::Compass`, which make the network larger and
+        //   so the evolution process longer.
+
+        animal.speed = (animal.speed + speed).clamp(config.speed_min, config.speed_max);
+        animal.rotation = na::Rotation2::new(animal.rotation.angle() + rotation);
+
+        // (btw, there is no need for ROTATION_MIN or ROTATION_MAX,
+        // because rotation automatically wraps from 2*PI back to 0 -
+        // we've already witnessed that when we were testing eyes,
+        // inside `fn rotations { ... }`.)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        CrossoverConfig, MutationConfig, MutationSchedule, OptimizerConfig, SelectionConfig,
+        TunableMutationConfig,
+    };
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use test_case::test_case;
+
+    fn simulation(optimizer: OptimizerConfig) -> (Simulation, ChaCha8Rng) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let sim = Simulation::random_with_config(
+            &mut rng,
+            Config {
+                optimizer,
+                ..Config::default()
+            },
+        );
+
+        (sim, rng)
+    }
+
+    #[test_case(OptimizerConfig::CmaEs { sigma: 0.1 })]
+    #[test_case(OptimizerConfig::SepCmaEs { sigma: 0.1 })]
+    #[test_case(OptimizerConfig::DifferentialEvolution {
+        strategy: crate::DifferentialEvolutionStrategy::Rand1Bin,
+        f: 0.5,
+        cr: 0.9,
+    })]
+    #[test_case(OptimizerConfig::ParticleSwarm {
+        inertia: 0.7,
+        cognitive: 1.5,
+        social: 1.5,
+        max_velocity: 0.1,
+    })]
+    fn optimizers_share_the_world(optimizer: OptimizerConfig) {
+        let (default, _) = simulation(Config::default().optimizer);
+        let (mut sim, mut rng) = simulation(optimizer);
+
+        let positions = |sim: &Simulation| -> Vec<_> {
+            sim.world()
+                .animals()
+                .iter()
+                .map(|animal| animal.position())
+                .collect()
+        };
+
+        assert_eq!(positions(&default), positions(&sim));
+
+        sim.train(&mut rng);
+        assert_eq!(sim.world().animals().len(), 10);
+    }
+
+    #[test]
+    fn tracks_lineage() {
+        let (mut sim, mut rng) = simulation(Config::default().optimizer);
+
+        assert!(sim.lineage().is_none());
+        sim.track_lineage();
+
+        let founders: Vec<_> = sim.world().animals().iter().map(Animal::id).collect();
+        assert_eq!(founders, (0..10).collect::<Vec<_>>());
+
+        sim.train(&mut rng);
+
+        let children: Vec<_> = sim.world().animals().iter().map(Animal::id).collect();
+        assert_eq!(children, (10..20).collect::<Vec<_>>());
+
+        let lineage = sim.lineage().unwrap();
+        assert_eq!(lineage.len(), 20);
+
+        // Founders' fitness is known once their generation has ended
+        assert!(founders
             .iter()
-            .map(AnimalIndividual::from_animal)
-            .collect();
+            .all(|id| lineage.get(*id).unwrap().fitness.is_some()));
 
-        // Evolves this `Vec<AnimalIndividual>`
-        let (evolved_population, stats) = self.ga.evolve(rng, &current_population);
+        for id in children {
+            let entry = lineage.get(id).unwrap();
 
-        // Transforms `Vec<AnimalIndividual>` back into `Vec<Animal>`
-        self.world.animals = evolved_population
-            .into_iter()
-            .map(|individual| individual.into_animal(rng))
+            assert_eq!(entry.generation, 1);
+            assert!(entry.fitness.is_none());
+
+            let [a, b] = entry.parents.unwrap();
+            assert!(a < 10 && b < 10);
+            assert!(lineage.descendants(a).contains(&id));
+            assert!(lineage.descendants(b).contains(&id));
+        }
+
+        // Family tree can grow large, so checkpoints leave it out
+        let (restored, _) = Simulation::load_checkpoint(&sim.save_checkpoint(&rng)).unwrap();
+        assert!(restored.lineage().is_none());
+    }
+
+    #[test]
+    fn remembers_brain_activations() {
+        let (mut sim, mut rng) = simulation(Config::default().optimizer);
+
+        assert!(sim.world().animals()[0].activations().is_empty());
+
+        sim.step(&mut rng);
+
+        for animal in sim.world().animals() {
+            let sizes: Vec<_> = animal.activations().iter().map(Vec::len).collect();
+
+            assert_eq!(sizes, animal.brain().network().layer_sizes());
+            assert_eq!(animal.vision().len(), animal.eye().cells());
+            assert_eq!(
+                animal.activations().last().unwrap(),
+                &animal.brain().propagate(animal.vision().to_vec())
+            );
+        }
+    }
+
+    #[test]
+    fn custom_config() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut sim = Simulation::random_with_config(
+            &mut rng,
+            Config {
+                animals: 6,
+                foods: 20,
+                eye: Eye::new(0.5, FRAC_PI_4, 5),
+                generation_length: 100,
+                optimizer: OptimizerConfig::GeneticAlgorithm {
+                    selection: SelectionConfig::Tournament { size: 2 },
+                    crossover: CrossoverConfig::Neuron,
+                    mutation: MutationConfig::Scheduled {
+                        mutation: TunableMutationConfig::Normal {
+                            chance: 0.1,
+                            sigma: 0.3,
+                        },
+                        schedule: MutationSchedule::OneFifthRule {
+                            factor: 0.85,
+                            window: 5,
+                        },
+                    },
+                },
+                ..Config::default()
+            },
+        );
+
+        assert_eq!(sim.world().animals().len(), 6);
+        assert_eq!(sim.world().foods().len(), 20);
+
+        for _ in 0..100 {
+            assert!(sim.step(&mut rng).is_none());
+        }
+
+        assert!(sim.step(&mut rng).is_some());
+        assert_eq!(sim.generation(), 1);
+
+        for animal in sim.world().animals() {
+            assert_eq!(animal.eye().cells(), 5);
+            assert_eq!(animal.brain().network().layer_sizes(), [5, 10, 3]);
+        }
+    }
+
+    #[test]
+    fn sensors() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            generation_length: 100,
+            sensors: vec![
+                SensorConfig::Speed,
+                SensorConfig::Compass,
+                SensorConfig::Hunger,
+                SensorConfig::Wall,
+                SensorConfig::Clock { period: 50 },
+            ],
+            optimizer: OptimizerConfig::GeneticAlgorithm {
+                selection: SelectionConfig::RouletteWheel,
+                crossover: CrossoverConfig::Neuron,
+                mutation: MutationConfig::UniformPerturbation {
+                    chance: 0.01,
+                    coeff: 0.2,
+                },
+            },
+            ..Config::default()
+        };
+
+        let mut sim = Simulation::random_with_config(&mut rng, config.clone());
+        let stats = sim.train(&mut rng);
+
+        assert!(stats.avg_fitness > 0.0);
+
+        // Sensors survive the evolution, making the brains (and so the
+        // chromosomes) larger
+        sim.step(&mut rng);
+
+        for animal in sim.world().animals() {
+            assert_eq!(animal.sensors(), config.sensors.as_slice());
+            assert_eq!(animal.brain().network().layer_sizes(), [15, 30, 3]);
+            assert_eq!(animal.as_chromosome().len(), 15 * 30 + 30 + 30 * 3 + 3);
+            assert_eq!(animal.inputs().len(), 15);
+            assert_eq!(animal.vision().len(), 9);
+            assert_eq!(animal.age(), 1);
+
+            assert_eq!(
+                animal.activations().last().unwrap(),
+                &animal.brain().propagate(animal.inputs().to_vec())
+            );
+        }
+
+        // ... and can't be changed mid-run
+        sim.reconfigure(Config::default());
+        sim.train(&mut rng);
+
+        assert_eq!(sim.config().sensors, config.sensors);
+    }
+
+    #[test]
+    fn train_until() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut sim = Simulation::random_with_config(
+            &mut rng,
+            Config {
+                generation_length: 100,
+                ..Config::default()
+            },
+        );
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+
+        let mut monitor = ga::Monitor::new()
+            .with_criterion(ga::MaxGenerations::new(3))
+            .with_observer({
+                let seen = Rc::clone(&seen);
+                move |generation, stats: &ga::Statistics| {
+                    seen.borrow_mut().push((generation, stats.max_fitness))
+                }
+            });
+
+        assert_eq!(
+            sim.train_until(&mut rng, &mut monitor),
+            ga::StopReason::Criterion(0)
+        );
+        assert_eq!(sim.generation(), 3);
+
+        let fitness: Vec<_> = sim
+            .history()
+            .iter()
+            .map(|stats| stats.max_fitness)
             .collect();
 
-        for food in &mut self.world.foods {
-            food.position = rng.gen();
+        assert_eq!(
+            *seen.borrow(),
+            vec![(1, fitness[0]), (2, fitness[1]), (3, fitness[2])]
+        );
+
+        // Training can be cancelled from the outside as well
+        let cancellation = ga::Cancellation::new();
+
+        let mut monitor = ga::Monitor::new()
+            .with_cancellation(cancellation.clone())
+            .with_observer(move |generation, _: &ga::Statistics| {
+                if generation == 2 {
+                    cancellation.cancel();
+                }
+            });
+
+        assert_eq!(
+            sim.train_until(&mut rng, &mut monitor),
+            ga::StopReason::Cancelled
+        );
+        assert_eq!(sim.generation(), 5);
+        assert_eq!(
+            sim.train_until(&mut rng, &mut monitor),
+            ga::StopReason::Cancelled
+        );
+        assert_eq!(sim.generation(), 5);
+    }
+
+    #[test]
+    fn reconfigure_between_generations() {
+        let (mut sim, mut rng) = simulation(Config::default().optimizer);
+
+        sim.step(&mut rng);
+
+        sim.reconfigure(Config {
+            animals: 3,
+            foods: 5,
+            eye: Eye::new(0.5, FRAC_PI_4, 3),
+            speed_max: 0.001,
+            generation_length: 10,
+            optimizer: OptimizerConfig::GeneticAlgorithm {
+                selection: SelectionConfig::Tournament { size: 3 },
+                crossover: CrossoverConfig::KPoint { k: 2 },
+                mutation: MutationConfig::Adaptive {
+                    mutations: vec![
+                        MutationConfig::UniformPerturbation {
+                            chance: 0.5,
+                            coeff: 0.5,
+                        },
+                        MutationConfig::Cauchy {
+                            chance: 0.5,
+                            scale: 0.5,
+                        },
+                    ],
+                    exploration: 1.0,
+                },
+            },
+            ..Config::default()
+        });
+
+        // Current generation lives by the rules it was born with
+        assert_eq!(sim.config(), &Config::default());
+        assert_eq!(sim.next_config().generation_length, 10);
+        assert_eq!(sim.world().foods().len(), 60);
+
+        sim.train(&mut rng);
+
+        assert_eq!(sim.generation(), 1);
+        assert_eq!(sim.config(), sim.next_config());
+        assert_eq!(sim.world().foods().len(), 5);
+
+        // Birds' number and eyes can't change mid-run
+        assert_eq!(sim.config().animals, 10);
+        assert_eq!(sim.config().eye, Eye::default());
+        assert_eq!(sim.world().animals().len(), 10);
+
+        for _ in 0..10 {
+            assert!(sim.step(&mut rng).is_none());
         }
 
-        stats
+        assert!(sim
+            .world()
+            .animals()
+            .iter()
+            .all(|animal| animal.speed() <= 0.001));
+
+        assert!(sim.step(&mut rng).is_some());
+    }
+
+    #[test_case(Config { animals: 0, ..Config::default() }, "animals must be within 1.., but got 0")]
+    #[test_case(Config { speed_min: 0.5, speed_max: 0.1, ..Config::default() }, "speed_max must be within speed_min.., but got 0.1")]
+    #[test_case(Config { speed_min: 0.0, speed_max: 0.0, ..Config::default() }, "speed_max must be within (0.0, inf), but got 0")]
+    #[test_case(Config { optimizer: OptimizerConfig::GeneticAlgorithm {
+        selection: SelectionConfig::Tournament { size: 0 },
+        crossover: CrossoverConfig::Uniform,
+        mutation: MutationConfig::UniformPerturbation { chance: 0.01, coeff: 0.2 },
+    }, ..Config::default() }, "optimizer: tournament size must be within 1.., but got 0")]
+    #[test_case(Config { optimizer: OptimizerConfig::GeneticAlgorithm {
+        selection: SelectionConfig::RouletteWheel,
+        crossover: CrossoverConfig::Uniform,
+        mutation: MutationConfig::UniformPerturbation { chance: 1.5, coeff: 0.2 },
+    }, ..Config::default() }, "optimizer: mutation chance must be within 0.0..=1.0, but got 1.5")]
+    #[test_case(Config { optimizer: OptimizerConfig::GeneticAlgorithm {
+        selection: SelectionConfig::RouletteWheel,
+        crossover: CrossoverConfig::Uniform,
+        mutation: MutationConfig::Scheduled {
+            mutation: TunableMutationConfig::UniformPerturbation { chance: 0.01, coeff: 0.2 },
+            schedule: MutationSchedule::OneFifthRule { factor: 0.0, window: 10 },
+        },
+    }, ..Config::default() }, "optimizer: factor must be within (0.0, 1.0), but got 0")]
+    #[test_case(Config { optimizer: OptimizerConfig::CmaEs { sigma: 0.0 }, ..Config::default() }, "optimizer: sigma must be within (0.0, inf), but got 0")]
+    #[test_case(Config { optimizer: OptimizerConfig::SepCmaEs { sigma: -1.0 }, ..Config::default() }, "optimizer: sigma must be within (0.0, inf), but got -1")]
+    #[test_case(Config { optimizer: OptimizerConfig::DifferentialEvolution {
+        strategy: crate::DifferentialEvolutionStrategy::Rand1Bin,
+        f: 0.8,
+        cr: 2.0,
+    }, ..Config::default() }, "optimizer: crossover probability must be within 0.0..=1.0, but got 2")]
+    #[test_case(Config { optimizer: OptimizerConfig::ParticleSwarm {
+        inertia: 0.7,
+        cognitive: 1.5,
+        social: 1.5,
+        max_velocity: 0.0,
+    }, ..Config::default() }, "optimizer: max velocity must be within (0.0, inf), but got 0")]
+    #[test_case(Config { sensors: vec![SensorConfig::Clock { period: 0 }], ..Config::default() }, "clock period must be within 1.., but got 0")]
+    fn invalid_config(config: Config, expected: &str) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let err = Simulation::try_random_with_config(&mut rng, config.clone())
+            .map(drop)
+            .expect_err("config should've been rejected");
+
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test]
+    fn invalid_reconfiguration() {
+        let (mut sim, _) = simulation(Config::default().optimizer);
+
+        let err = sim
+            .try_reconfigure(Config {
+                speed_min: -1.0,
+                ..Config::default()
+            })
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "speed_min must be within 0.0.., but got -1"
+        );
+        assert_eq!(sim.next_config(), &Config::default());
+    }
+
+    #[test]
+    fn starving_birds() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut sim = Simulation::random_with_config(
+            &mut rng,
+            Config {
+                foods: 0,
+                generation_length: 10,
+                ..Config::default()
+            },
+        );
+
+        for _ in 0..10 {
+            assert!(sim.try_step(&mut rng).unwrap().is_none());
+        }
+
+        // Nobody's eaten anything, so the roulette has nothing to spin on
+        let err = sim.try_step(&mut rng).unwrap_err();
+
+        assert!(matches!(err, Error::Optimizer(ga::Error::InvalidFitness)));
+        assert_eq!(sim.generation(), 0);
+        assert_eq!(sim.history().len(), 0);
+
+        // ... but the simulation stays usable - evolution is retried after
+        // each step, until someone finally eats something
+        sim.reconfigure(Config {
+            generation_length: 10,
+            ..Config::default()
+        });
+
+        let stats = loop {
+            if let Ok(Some(stats)) = sim.try_step(&mut rng) {
+                break stats;
+            }
+        };
+
+        assert!(stats.max_fitness > 0.0);
+        assert_eq!(sim.generation(), 1);
+        assert_eq!(sim.world().foods().len(), 60);
     }
 }
@@ -1,18 +1,36 @@
 use rand::RngCore;
-use crate::{Animal, Food};
+use crate::{Animal, Champion, CheckpointError, Config, Eye, Food, SensorConfig};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct World {
     pub(crate) animals: Vec<Animal>,
     pub(crate) foods: Vec<Food>,
+
+    /// Id the next bird released into this world will get.
+    pub(crate) next_id: u64,
 }
 impl World {
     pub fn random(rng: &mut dyn RngCore) -> Self {
-        let animals = (0..10)
-            .map(|_| Animal::random(rng))
+        Self::random_sized(rng, 10, 60)
+    }
+
+    pub fn random_sized(rng: &mut dyn RngCore, animals: usize, foods: usize) -> Self {
+        Self::random_with_senses(rng, animals, foods, &Eye::default(), &[])
+    }
+
+    pub(crate) fn random_with_senses(
+        rng: &mut dyn RngCore,
+        animals: usize,
+        foods: usize,
+        eye: &Eye,
+        sensors: &[SensorConfig],
+    ) -> Self {
+        let animals = (0..animals)
+            .map(|_| Animal::random_with_senses(rng, eye.clone(), sensors.to_vec()))
             .collect();
 
-        let foods = (0..60)
+        let foods = (0..foods)
             .map(|_| Food::random(rng))
             .collect();
 
@@ -25,7 +43,52 @@ impl World {
         // | https://en.wikipedia.org/wiki/Supersampling
         // ---
 
-        Self { animals, foods }
+        let mut world = Self {
+            animals: Vec::new(),
+            foods,
+            next_id: 0,
+        };
+
+        world.populate(animals);
+        world
+    }
+
+    /// Creates a world with champion as the only bird, e.g. to show off
+    /// how it flies.
+    pub fn demo(rng: &mut dyn RngCore, champion: &Champion) -> Self {
+        let animals = vec![champion.to_animal(rng)];
+        let mut world = Self::random_sized(rng, 0, 60);
+
+        world.populate(animals);
+        world
+    }
+
+    /// Creates a world (sized & shaped as described by `config`) whose
+    /// every bird has champion's brain - a head start for a new
+    /// population, which will diverge from it as soon as it's evolved.
+    ///
+    /// Panics if the champion doesn't fit `config` (see `try_seeded()`).
+    pub fn seeded(rng: &mut dyn RngCore, champion: &Champion, config: &Config) -> Self {
+        Self::try_seeded(rng, champion, config).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Fails if champion's brain doesn't fit birds with `config.eye` and
+    /// `config.sensors`.
+    pub fn try_seeded(
+        rng: &mut dyn RngCore,
+        champion: &Champion,
+        config: &Config,
+    ) -> Result<Self, CheckpointError> {
+        let animals = (0..config.animals)
+            .map(|_| champion.try_to_animal_with(config, rng))
+            .collect::<Result<_, _>>()?;
+
+        let mut world =
+            Self::random_with_senses(rng, 0, config.foods, &config.eye, &config.sensors);
+
+        world.populate(animals);
+
+        Ok(world)
     }
 
     pub fn animals(&self) -> &[Animal] {
@@ -36,4 +99,15 @@ impl World {
         &self.foods
     }
 
+    /// Replaces all the birds living in this world with given ones, giving
+    /// each of them a fresh id.
+    pub(crate) fn populate(&mut self, animals: Vec<Animal>) {
+        self.animals = animals;
+
+        for animal in &mut self.animals {
+            animal.id = self.next_id;
+            self.next_id += 1;
+        }
+    }
+
 }
\ No newline at end of file